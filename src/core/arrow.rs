@@ -85,6 +85,21 @@ impl<Object: CategoryTrait> Arrow<Object, Object> {
             is_identity: true,
         })
     }
+
+    // an identity which also carries its action, e.g. x -> x on the elements of a set
+    pub fn new_identity_with_mappings(
+        id: String,
+        object: Rc<Object>,
+        mappings: HashMap<Rc<Object::Morphism>, Rc<Object::Morphism>>,
+    ) -> Rc<Self> {
+        Rc::new(Arrow {
+            id: ObjectId::Str(id),
+            source_object: object.clone(),
+            target_object: object,
+            mappings,
+            is_identity: true,
+        })
+    }
 }
 impl<SourceObject: CategoryTrait, TargetObject: CategoryTrait> Arrow<SourceObject, TargetObject> {
     pub fn new(
@@ -115,6 +130,48 @@ impl<SourceObject: CategoryTrait, TargetObject: CategoryTrait> Arrow<SourceObjec
             is_identity: false,
         }
     }
+
+    // objects are mapped through their identity morphisms: F(a) is the object of F(id_a)
+    pub fn map_object(
+        &self,
+        object: &SourceObject::Object,
+    ) -> Result<&Rc<TargetObject::Object>, Errors> {
+        let identity_morphism = self.source_object.get_identity_morphism(object)?;
+        let mapped_identity = self
+            .mappings
+            .get(identity_morphism)
+            .ok_or(Errors::InvalidFunctorMappings)?;
+        Ok(mapped_identity.target_object())
+    }
+
+    pub fn map_morphism(
+        &self,
+        morphism: &SourceObject::Morphism,
+    ) -> Result<&Rc<TargetObject::Morphism>, Errors> {
+        self.mappings
+            .get(morphism)
+            .ok_or(Errors::InvalidFunctorMappings)
+    }
+
+    // functor composition, self followed by next: (next ∘ self)(f) = next(self(f))
+    pub fn compose_functor<NextObject: CategoryTrait>(
+        &self,
+        next: &Arrow<TargetObject, NextObject>,
+    ) -> Result<Arrow<SourceObject, NextObject>, Errors> {
+        if self.target_object.category_id() != next.source_object.category_id() {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        let mappings = self
+            .mappings
+            .iter()
+            .map(|(source, target)| Ok((source.clone(), next.map_morphism(target)?.clone())))
+            .collect::<Result<HashMap<_, _>, Errors>>()?;
+        Ok(Arrow::new_with_mappings(
+            self.source_object.clone(),
+            next.target_object.clone(),
+            mappings,
+        ))
+    }
 }
 
 impl<SourceObject, TargetObject> ArrowTrait<SourceObject, TargetObject>
@@ -198,62 +255,55 @@ where
         have a corresponding object in the target category.
 
         And that all morphisms in the source category are mapped to morphisms in the target category,
-        such that they commute with morphism in the target category.
+        such that they respect sources, targets and composition.
         i.e        for each morphism f: A -> B in the source category,
-        there exists a morphism f': F(A) -> F(B) in the target category such that
-        F(B) ∘ F(f) = F(f') ∘ F(A)
+        F(f) goes from F(A) to F(B), and for each g: B -> C
+        F(g ∘ f) = F(g) ∘ F(f)
          */
-
-        // start with checking if all objects in the source category have a corresponding object in the target category
         let mapping = self.arrow_mappings();
-        for source_object in self.source_object().get_all_objects()? {
-            let identity_morphism = self
-                .source_object()
-                .get_identity_morphism(&**source_object)?;
-
+        let source = self.source_object();
+        let target = self.target_object();
+        let mapped = |morphism: &SourceObject::Morphism| {
+            mapping.get(morphism).ok_or(Errors::InvalidFunctor(format!(
+                "No functor found for morphism {}",
+                morphism.arrow_id()
+            )))
+        };
+        for source_object in source.get_all_objects()? {
             // a -> F(a)
-            let mapped_identity_morphism =
-                mapping
-                    .get(identity_morphism)
-                    .ok_or(Errors::InvalidFunctor(
-                        "No functor found for identity morphism".to_string(),
-                    ))?;
-
-            // now get the hom-set for the source object
-            let hom_set_x = self.source_object().get_hom_set_x(&**source_object)?;
-
-            for morphism in hom_set_x {
-                if morphism.is_identity() {
-                    // just check its identity mapping
-                    if !mapping.contains_key(morphism) {
-                        return Err(Errors::InvalidFunctor(
-                            "No functor found for identity morphism".to_string(),
-                        ));
+            let mapped_identity = mapped(source.get_identity_morphism(source_object)?)?;
+            if !mapped_identity.is_identity() {
+                return Err(Errors::InvalidFunctor(format!(
+                    "Identity of {} is not mapped to an identity",
+                    source_object.category_id()
+                )));
+            }
+
+            for morphism in source.get_hom_set_x(source_object)? {
+                // F(f): F(a) -> F(b)
+                let target_morphism = mapped(morphism)?;
+                let mapped_target_identity =
+                    mapped(source.get_identity_morphism(morphism.target_object())?)?;
+                if target_morphism.source_object() != mapped_identity.source_object()
+                    || target_morphism.target_object() != mapped_target_identity.target_object()
+                {
+                    return Err(Errors::InvalidFunctor(format!(
+                        "Morphism {} is not mapped between the images of its objects",
+                        morphism.arrow_id()
+                    )));
+                }
+
+                for next in source.get_hom_set_x(morphism.target_object())? {
+                    let composite = source.compose_morphisms(morphism, next)?;
+                    let mapped_composite =
+                        target.compose_morphisms(target_morphism, mapped(next)?)?;
+                    if mapped_composite != *mapped(&composite)? {
+                        return Err(Errors::InvalidFunctor(format!(
+                            "Composite of {} and {} is not preserved",
+                            morphism.arrow_id(),
+                            next.arrow_id()
+                        )));
                     }
-                } else {
-                    // F(a) -> F(b)
-                    let target_morphism = mapping.get(morphism).ok_or(Errors::InvalidFunctor(
-                        "No functor found for morphism".to_string(),
-                    ))?;
-                    // now we check the commutation condition
-                    // from the source object to the target object to the mapped target object
-                    // a -> F(a) -> F(b)
-                    let first_path = mapped_identity_morphism.compose(&**target_morphism)?;
-
-                    let identity_of_target = self
-                        .source_object()
-                        .get_identity_morphism(&**morphism.target_object())?;
-                    // b -> F(b)
-                    let mapped_identity_target_morphism =
-                        mapping
-                            .get(identity_of_target)
-                            .ok_or(Errors::InvalidFunctor(
-                                "No functor found for identity morphism in target".to_string(),
-                            ))?;
-                    // // a -> b -> F(b)
-                    // let second_path = morphism.compose(&**mapped_identity_target_morphism)?;
-                    //
-                    // first_path.validate_commutation(&*second_path)?;
                 }
             }
         }
@@ -329,4 +379,14 @@ where
     ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
         todo!()
     }
+
+    fn compose_morphisms(
+        &self,
+        _first: &Rc<Self::Morphism>,
+        _second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        Err(Errors::InvalidOperation(
+            "Arrows do not compose as morphisms of an arrow category".to_string(),
+        ))
+    }
 }
@@ -7,6 +7,7 @@ use crate::core::identifier::Identifier;
 use crate::core::object_id::ObjectId;
 use crate::core::traits::arrow_trait::ArrowTrait;
 use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::traits::finite_limits_trait::{FiniteColimitsTrait, FiniteLimitsTrait};
 use dyn_clone::DynClone;
 use std::borrow::Borrow;
 use std::collections::{HashMap, HashSet};
@@ -20,6 +21,8 @@ pub struct BaseCategory<Object: CategoryTrait> {
     objects: HashMap<ObjectId, Rc<Object>>,
     object_mappings: HashMap<ObjectId, HashSet<String>>,
     morphism: HashMap<String, Rc<Morphism<Object>>>,
+    // second ∘ first for pairs of morphisms (first, second), keyed by their ids
+    compositions: HashMap<(String, String), String>,
 }
 
 impl<Object: CategoryTrait> Debug for BaseCategory<Object> {
@@ -54,12 +57,71 @@ impl<Object: CategoryTrait> BaseCategory<Object> {
             objects: HashMap::new(),
             object_mappings: HashMap::new(),
             morphism: HashMap::new(),
+            compositions: HashMap::new(),
         }
     }
 
     pub fn id(&self) -> &ObjectId {
         &self.id
     }
+
+    pub fn morphism(&self, id: &str) -> Result<&Rc<Morphism<Object>>, Errors> {
+        self.morphism.get(id).ok_or(Errors::MorphismNotFound)
+    }
+
+    // records the composite second ∘ first, which composition looks up before the actions
+    pub fn add_composition(
+        &mut self,
+        first: &str,
+        second: &str,
+        composite: &str,
+    ) -> Result<(), Errors> {
+        let first_morphism = self.morphism(first)?;
+        let second_morphism = self.morphism(second)?;
+        let composite_morphism = self.morphism(composite)?;
+        if first_morphism.target_object().category_id()
+            != second_morphism.source_object().category_id()
+            || composite_morphism.source_object().category_id()
+                != first_morphism.source_object().category_id()
+            || composite_morphism.target_object().category_id()
+                != second_morphism.target_object().category_id()
+        {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        let key = (first.to_string(), second.to_string());
+        match self.compositions.get(&key) {
+            Some(existing) if existing != composite => Err(Errors::InvalidMorphismComposition),
+            _ => {
+                self.compositions.insert(key, composite.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    // adds the source of the identity with it as its identity, e.g. one carrying its action
+    pub fn add_object_with_identity(&mut self, identity: Rc<Morphism<Object>>) -> Result<(), Errors>
+    where
+        Object: Hash + Eq + DynClone + Clone,
+    {
+        if !identity.is_identity() || identity.source_object() != identity.target_object() {
+            return Err(Errors::InvalidOperation(format!(
+                "{} is not an identity",
+                identity.arrow_id()
+            )));
+        }
+        let object = identity.source_object().clone();
+        if self.objects.contains_key(object.category_id()) {
+            return Err(Errors::ObjectAlreadyExists);
+        }
+        self.objects
+            .insert(object.category_id().clone(), object.clone());
+        self.object_mappings
+            .entry(object.category_id().clone())
+            .or_default()
+            .insert(identity.arrow_id().to_string());
+        self.add_morphism(identity)?;
+        Ok(())
+    }
 }
 
 impl<Object: CategoryTrait + Hash + Eq + DynClone + std::clone::Clone> CategoryTrait
@@ -89,17 +151,8 @@ impl<Object: CategoryTrait + Hash + Eq + DynClone + std::clone::Clone> CategoryT
     }
 
     fn add_object(&mut self, object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
-        if self.objects.contains_key(object.category_id()) {
-            return Err(Errors::ObjectAlreadyExists);
-        }
-        let object_id = object.category_id();
-        self.objects.insert(object_id.clone(), object.clone());
         let identity_cell = Morphism::new_identity(object.clone());
-        self.object_mappings
-            .entry(object_id.clone())
-            .or_default()
-            .insert(identity_cell.arrow_id().to_string());
-        self.add_morphism(identity_cell.clone())?;
+        self.add_object_with_identity(identity_cell.clone())?;
         Ok(identity_cell)
     }
 
@@ -115,14 +168,18 @@ impl<Object: CategoryTrait + Hash + Eq + DynClone + std::clone::Clone> CategoryT
             .objects
             .contains_key(&morphism.target_object().category_id())
         {
-            return Err(Errors::ObjectNotFound);
+            return Err(Errors::ObjectNotFound(
+                morphism.target_object().category_id().clone(),
+            ));
         }
 
         // if its not identity morphism add it to the objects as part of the hom-set
         if !morphism.is_identity() {
             self.object_mappings
                 .get_mut(&morphism.source_object().category_id())
-                .ok_or(Errors::ObjectNotFound)?
+                .ok_or_else(|| {
+                    Errors::ObjectNotFound(morphism.source_object().category_id().clone())
+                })?
                 .insert(morphism.arrow_id().to_string());
         }
 
@@ -136,7 +193,7 @@ impl<Object: CategoryTrait + Hash + Eq + DynClone + std::clone::Clone> CategoryT
     fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
         self.objects
             .get(object.category_id())
-            .ok_or(Errors::ObjectNotFound)
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))
     }
 
     fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
@@ -161,7 +218,7 @@ impl<Object: CategoryTrait + Hash + Eq + DynClone + std::clone::Clone> CategoryT
         let result = self
             .object_mappings
             .get(source_object.category_id().into())
-            .ok_or(Errors::ObjectNotFound)?
+            .ok_or_else(|| Errors::ObjectNotFound(source_object.category_id().clone()))?
             .iter()
             .map(|item| self.morphism.get(item).ok_or(Errors::MorphismNotFound))
             .collect::<Result<HashSet<&Rc<Morphism<Self::Object>>>, Errors>>()?;
@@ -175,12 +232,69 @@ impl<Object: CategoryTrait + Hash + Eq + DynClone + std::clone::Clone> CategoryT
         let result = self
             .object_mappings
             .get(object.category_id().into())
-            .ok_or(Errors::ObjectNotFound)?
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))?
             .iter()
             .map(|item| self.morphism.get(item).ok_or(Errors::MorphismNotFound))
             .collect::<Result<Vec<&Rc<Morphism<Self::Object>>>, Errors>>()?;
         Ok(result)
     }
+
+    /*
+    Identities compose to the other morphism and recorded composites are looked up by the ids
+    of the pair. Other morphisms compose by their actions on the objects, the composite being
+    the one morphism of the hom-set with the composite action.
+     */
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        if first.target_object() != second.source_object() {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        if first.is_identity() {
+            return Ok(second.clone());
+        }
+        if second.is_identity() {
+            return Ok(first.clone());
+        }
+        let key = (first.arrow_id().clone(), second.arrow_id().clone());
+        if let Some(composite) = self.compositions.get(&key) {
+            return Ok(self.morphism(composite)?.clone());
+        }
+
+        let second_mappings = second.arrow_mappings();
+        let composite_mappings = first
+            .arrow_mappings()
+            .iter()
+            .map(|(source, target)| {
+                second_mappings
+                    .get(target)
+                    .map(|mapped| (source.clone(), mapped.clone()))
+            })
+            .collect::<Option<HashMap<_, _>>>()
+            .filter(|mappings| !mappings.is_empty())
+            .ok_or(Errors::InvalidMorphismComposition)?;
+        let mut matches = self
+            .get_hom_set(first.source_object(), second.target_object())?
+            .into_iter()
+            .filter(|candidate| candidate.arrow_mappings() == &composite_mappings)
+            .collect::<Vec<_>>();
+        match matches.len() {
+            1 => Ok(matches.remove(0).clone()),
+            _ => Err(Errors::InvalidMorphismComposition),
+        }
+    }
+}
+
+impl<Object: CategoryTrait + Hash + Eq + DynClone + std::clone::Clone> FiniteLimitsTrait
+    for BaseCategory<Object>
+{
+}
+
+impl<Object: CategoryTrait + Hash + Eq + DynClone + std::clone::Clone> FiniteColimitsTrait
+    for BaseCategory<Object>
+{
 }
 
 impl<T: Eq + Clone + Hash + Debug> From<Vec<T>> for BaseCategory<DiscreteCategory>
@@ -0,0 +1,303 @@
+/*
+Any category with finite products is monoidal with the product as tensor
+and the terminal object as unit, A ⊗ B = A × B and I = 1.
+The structure morphisms are induced by the universal property of the products,
+and swapping the projections makes it symmetric.
+ */
+use crate::core::errors::Errors;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::braided_monoidal_category_trait::{
+    BraidedMonoidalCategoryTrait, SymmetricMonoidalCategoryTrait,
+};
+use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::traits::finite_limits_trait::{Cone, FiniteDiagram, FiniteLimitsTrait};
+use crate::core::traits::monoidal_category_trait::MonoidalCategoryTrait;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CartesianMonoidalCategory<InnerCategory>
+where
+    InnerCategory: CategoryTrait + Hash + Eq,
+{
+    category: InnerCategory,
+}
+
+impl<InnerCategory> CartesianMonoidalCategory<InnerCategory>
+where
+    InnerCategory: FiniteLimitsTrait + Hash + Eq,
+{
+    pub fn new(category: InnerCategory) -> Self {
+        CartesianMonoidalCategory { category }
+    }
+
+    pub fn category(&self) -> &InnerCategory {
+        &self.category
+    }
+
+    fn product_diagram(
+        left: &Rc<InnerCategory::Object>,
+        right: &Rc<InnerCategory::Object>,
+    ) -> FiniteDiagram<InnerCategory> {
+        let mut diagram = FiniteDiagram::new();
+        diagram.add_object(left.clone());
+        diagram.add_object(right.clone());
+        diagram
+    }
+
+    fn product(
+        &self,
+        left: &Rc<InnerCategory::Object>,
+        right: &Rc<InnerCategory::Object>,
+    ) -> Result<Cone<InnerCategory>, Errors> {
+        self.category.limit(&Self::product_diagram(left, right))
+    }
+
+    // ⟨left, right⟩: X -> A × B for left: X -> A and right: X -> B
+    fn pairing(
+        &self,
+        left: Rc<InnerCategory::Morphism>,
+        right: Rc<InnerCategory::Morphism>,
+    ) -> Result<Rc<InnerCategory::Morphism>, Errors> {
+        let diagram = Self::product_diagram(left.target_object(), right.target_object());
+        let product = self.category.limit(&diagram)?;
+        let cone = Cone::new(left.source_object().clone(), vec![left, right]);
+        self.category
+            .limit_mediating_morphism(&diagram, &product, &cone)
+    }
+}
+
+impl<InnerCategory> CategoryTrait for CartesianMonoidalCategory<InnerCategory>
+where
+    InnerCategory: FiniteLimitsTrait + Hash + Eq + Clone + 'static,
+{
+    type Object = InnerCategory::Object;
+
+    type Morphism = InnerCategory::Morphism;
+
+    fn new() -> Self {
+        CartesianMonoidalCategory::new(InnerCategory::new())
+    }
+
+    fn new_with_id(id: &ObjectId) -> Self
+    where
+        Self: Sized,
+    {
+        CartesianMonoidalCategory::new(InnerCategory::new_with_id(id))
+    }
+
+    fn category_id(&self) -> &ObjectId {
+        self.category.category_id()
+    }
+
+    fn update_category_id(&mut self, new_id: ObjectId) {
+        self.category.update_category_id(new_id);
+    }
+
+    fn add_object(&mut self, object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        self.category.add_object(object)
+    }
+
+    fn add_morphism(
+        &mut self,
+        morphism: Rc<Self::Morphism>,
+    ) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.category.add_morphism(morphism)
+    }
+
+    fn get_identity_morphism(&self, object: &Self::Object) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.category.get_identity_morphism(object)
+    }
+
+    fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
+        self.category.get_object(object)
+    }
+
+    fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
+        self.category.get_all_objects()
+    }
+
+    fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        self.category.get_all_morphisms()
+    }
+
+    fn get_hom_set_x(
+        &self,
+        source_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        self.category.get_hom_set_x(source_object)
+    }
+
+    fn get_object_morphisms(
+        &self,
+        object: &Self::Object,
+    ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
+        self.category.get_object_morphisms(object)
+    }
+
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        self.category.compose_morphisms(first, second)
+    }
+
+    fn inverse_morphism(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        self.category.inverse_morphism(morphism)
+    }
+}
+
+impl<InnerCategory> MonoidalCategoryTrait for CartesianMonoidalCategory<InnerCategory>
+where
+    InnerCategory: FiniteLimitsTrait + Hash + Eq + Clone + 'static,
+{
+    fn tensor_objects(
+        &self,
+        left: &Rc<Self::Object>,
+        right: &Rc<Self::Object>,
+    ) -> Result<Rc<Self::Object>, Errors> {
+        Ok(self.product(left, right)?.apex().clone())
+    }
+
+    // f × g = ⟨f ∘ π_1, g ∘ π_2⟩
+    fn tensor_morphisms(
+        &self,
+        left: &Rc<Self::Morphism>,
+        right: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        let source = self.product(left.source_object(), right.source_object())?;
+        self.pairing(
+            self.compose_morphisms(&source.legs()[0], left)?,
+            self.compose_morphisms(&source.legs()[1], right)?,
+        )
+    }
+
+    fn unit_object(&self) -> Result<Rc<Self::Object>, Errors> {
+        Ok(self.category.limit(&FiniteDiagram::new())?.apex().clone())
+    }
+
+    // ⟨π_A ∘ π_{A×B}, ⟨π_B ∘ π_{A×B}, π_C⟩⟩: (A × B) × C -> A × (B × C)
+    fn associator(
+        &self,
+        first: &Rc<Self::Object>,
+        second: &Rc<Self::Object>,
+        third: &Rc<Self::Object>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        let first_second = self.product(first, second)?;
+        let source = self.product(first_second.apex(), third)?;
+        let to_first_second = &source.legs()[0];
+        let inner = self.pairing(
+            self.compose_morphisms(to_first_second, &first_second.legs()[1])?,
+            source.legs()[1].clone(),
+        )?;
+        self.pairing(
+            self.compose_morphisms(to_first_second, &first_second.legs()[0])?,
+            inner,
+        )
+    }
+
+    fn left_unitor(&self, object: &Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        let unit = self.unit_object()?;
+        Ok(self.product(&unit, object)?.legs()[1].clone())
+    }
+
+    fn right_unitor(&self, object: &Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        let unit = self.unit_object()?;
+        Ok(self.product(object, &unit)?.legs()[0].clone())
+    }
+}
+
+impl<InnerCategory> BraidedMonoidalCategoryTrait for CartesianMonoidalCategory<InnerCategory>
+where
+    InnerCategory: FiniteLimitsTrait + Hash + Eq + Clone + 'static,
+{
+    // ⟨π_2, π_1⟩: A × B -> B × A
+    fn braiding(
+        &self,
+        left: &Rc<Self::Object>,
+        right: &Rc<Self::Object>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        let product = self.product(left, right)?;
+        self.pairing(product.legs()[1].clone(), product.legs()[0].clone())
+    }
+}
+
+impl<InnerCategory> SymmetricMonoidalCategoryTrait for CartesianMonoidalCategory<InnerCategory> where
+    InnerCategory: FiniteLimitsTrait + Hash + Eq + Clone + 'static
+{
+}
+
+impl<InnerCategory: CategoryTrait + Hash + Eq> Hash for CartesianMonoidalCategory<InnerCategory> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: std::hash::Hasher,
+    {
+        self.category.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::base_category::BaseCategory;
+    use crate::core::discrete_category::DiscreteCategory;
+    use crate::core::shapes;
+
+    // the chain 0 ≤ 1 ≤ 2, where products are minimums and 2 is terminal
+    fn chain() -> BaseCategory<DiscreteCategory> {
+        shapes::ordinal(2)
+    }
+
+    #[test]
+    fn test_cartesian_monoidal_chain() {
+        let monoidal = CartesianMonoidalCategory::new(chain());
+        let object = |id: i32| {
+            monoidal
+                .get_object(&DiscreteCategory::new_with_id(id.into()))
+                .unwrap()
+                .clone()
+        };
+
+        assert_eq!(monoidal.unit_object().unwrap(), object(2));
+        assert_eq!(
+            monoidal.tensor_objects(&object(1), &object(2)).unwrap(),
+            object(1)
+        );
+        assert_eq!(
+            monoidal.tensor_objects(&object(2), &object(0)).unwrap(),
+            object(0)
+        );
+        monoidal.validate_monoidal_structure().unwrap();
+    }
+
+    #[test]
+    fn test_cartesian_braiding_chain() {
+        let monoidal = CartesianMonoidalCategory::new(chain());
+        let object = |id: i32| {
+            monoidal
+                .get_object(&DiscreteCategory::new_with_id(id.into()))
+                .unwrap()
+                .clone()
+        };
+        // 0 × 2 and 2 × 0 are both 0, so the braiding is the identity on 0
+        let braiding = monoidal.braiding(&object(0), &object(2)).unwrap();
+        assert!(braiding.is_identity());
+        monoidal.validate_braided_structure().unwrap();
+        monoidal.validate_symmetric_structure().unwrap();
+    }
+
+    #[test]
+    fn test_cartesian_monoidal_without_products() {
+        // two unrelated objects have neither a product nor a terminal object
+        let monoidal =
+            CartesianMonoidalCategory::new(BaseCategory::<DiscreteCategory>::from(vec!["a", "b"]));
+        assert_eq!(monoidal.unit_object().err(), Some(Errors::LimitNotFound));
+        assert!(monoidal.validate_monoidal_structure().is_err());
+    }
+}
@@ -0,0 +1,1112 @@
+/*
+FinSet, the category of finite sets and total functions.
+
+A set is a DiscreteCategory whose objects are its elements, and a function f: A -> B
+maps the identity of every element a of A to the identity of f(a) in B.
+Functions built here are named after their graph, so two functions with the same
+source, target and table are equal, and identities are the functions a -> a.
+The hom-sets hold only the functions added, not every function between two sets, so monos,
+epis and their splittings are decided on the tables of the functions instead of searched for.
+
+Limits and colimits are constructed concretely rather than searched for:
+the limit of a diagram is the set of compatible tuples and the colimit the quotient
+of the disjoint union. Constructed sets are cached by id, so constructing the same product
+twice gives the same set and the legs compare equal, but they only become objects of the
+category once added, e.g. through add_limit and add_colimit.
+The elements of constructed sets have structured ids: tuples are Products, the elements
+of a disjoint union Coproducts, and functions and identified classes Generated ids.
+ */
+use crate::core::arrow::Morphism;
+use crate::core::discrete_category::DiscreteCategory;
+use crate::core::errors::Errors;
+use crate::core::identifier::Identifier;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::traits::finite_limits_trait::{
+    Cocone, Cone, FiniteColimitsTrait, FiniteDiagram, FiniteLimitsTrait,
+};
+use crate::core::utils::cartesian_product;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+pub type Function = Morphism<DiscreteCategory>;
+
+#[derive(Debug, Clone)]
+pub struct FinSet {
+    id: ObjectId,
+    objects: HashMap<ObjectId, Rc<DiscreteCategory>>,
+    identities: HashMap<ObjectId, Rc<Function>>,
+    // non-identity functions out of each set
+    hom_sets: HashMap<ObjectId, HashSet<String>>,
+    functions: HashMap<String, Rc<Function>>,
+    // the sets built by limits, colimits and exponentials, so building one twice gives the
+    // same set, which only becomes an object once it is added
+    constructions: RefCell<HashMap<ObjectId, Rc<DiscreteCategory>>>,
+}
+
+impl Default for FinSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for FinSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for FinSet {}
+
+impl Hash for FinSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl FinSet {
+    pub fn new() -> Self {
+        Self::new_with_id(ObjectId::generate())
+    }
+
+    pub fn new_with_id(id: ObjectId) -> Self {
+        FinSet {
+            id,
+            objects: HashMap::new(),
+            identities: HashMap::new(),
+            hom_sets: HashMap::new(),
+            functions: HashMap::new(),
+            constructions: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn new_set<T: Into<ObjectId>>(
+        id: ObjectId,
+        elements: Vec<T>,
+    ) -> Result<DiscreteCategory, Errors> {
+        let mut set = DiscreteCategory::new_with_id(id);
+        for element in elements {
+            set.insert_object(Rc::new(DiscreteCategory::new_with_id(element.into())))?;
+        }
+        Ok(set)
+    }
+
+    pub fn add_set<T: Into<ObjectId>>(
+        &mut self,
+        id: impl Into<ObjectId>,
+        elements: Vec<T>,
+    ) -> Result<Rc<DiscreteCategory>, Errors> {
+        let set = Rc::new(Self::new_set(id.into(), elements)?);
+        self.add_object(set.clone())?;
+        Ok(set)
+    }
+
+    // the elements of a set ordered by id
+    pub fn elements(set: &DiscreteCategory) -> Vec<ObjectId> {
+        let mut elements = set
+            .get_all_objects()
+            .map(|elements| {
+                elements
+                    .into_iter()
+                    .map(|element| element.category_id().clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        elements.sort_by_key(|element| element.to_string());
+        elements
+    }
+
+    // the total function with the given table, named after its graph
+    pub fn function(
+        source: &Rc<DiscreteCategory>,
+        target: &Rc<DiscreteCategory>,
+        table: &HashMap<ObjectId, ObjectId>,
+    ) -> Result<Rc<Function>, Errors> {
+        let mappings = Self::mappings(source, target, table)?;
+        let is_identity =
+            Rc::ptr_eq(source, target) && table.iter().all(|(element, image)| element == image);
+        if is_identity {
+            return Ok(Self::identity_function(source));
+        }
+        Ok(Rc::new(Function::new(
+            Self::graph_id(source, target, table),
+            source.clone(),
+            target.clone(),
+            mappings,
+        )))
+    }
+
+    pub fn identity_function(set: &Rc<DiscreteCategory>) -> Rc<Function> {
+        let mappings = Self::elements(set)
+            .into_iter()
+            .filter_map(|element| {
+                set.get_identity_morphism(&DiscreteCategory::new_with_id(element))
+                    .ok()
+                    .map(|identity| (identity.clone(), identity.clone()))
+            })
+            .collect();
+        Function::new_identity_with_mappings(
+            format!("id_{}", set.category_id()),
+            set.clone(),
+            mappings,
+        )
+    }
+
+    // a named function between sets of the category
+    pub fn add_function(
+        &mut self,
+        id: &str,
+        source: &Rc<DiscreteCategory>,
+        target: &Rc<DiscreteCategory>,
+        table: HashMap<ObjectId, ObjectId>,
+    ) -> Result<Rc<Function>, Errors> {
+        let function = Rc::new(Function::new(
+            id.to_string(),
+            source.clone(),
+            target.clone(),
+            Self::mappings(source, target, &table)?,
+        ));
+        self.add_morphism(function.clone())?;
+        Ok(function)
+    }
+
+    pub fn apply(function: &Function, element: &ObjectId) -> Result<ObjectId, Errors> {
+        let identity = function
+            .source_object()
+            .get_identity_morphism(&DiscreteCategory::new_with_id(element.clone()))?;
+        if function.is_identity() && function.arrow_mappings().is_empty() {
+            return Ok(element.clone());
+        }
+        let image = function
+            .arrow_mappings()
+            .get(identity)
+            .ok_or_else(|| Errors::ObjectNotFound(element.clone()))?;
+        Ok(image.source_object().category_id().clone())
+    }
+
+    pub fn function_table(function: &Function) -> Result<HashMap<ObjectId, ObjectId>, Errors> {
+        Self::elements(function.source_object())
+            .into_iter()
+            .map(|element| {
+                let image = Self::apply(function, &element)?;
+                Ok((element, image))
+            })
+            .collect()
+    }
+
+    pub fn product(
+        &self,
+        left: &Rc<DiscreteCategory>,
+        right: &Rc<DiscreteCategory>,
+    ) -> Result<Cone<Self>, Errors> {
+        let mut diagram = FiniteDiagram::new();
+        diagram.add_object(left.clone());
+        diagram.add_object(right.clone());
+        self.limit(&diagram)
+    }
+
+    pub fn coproduct(
+        &self,
+        left: &Rc<DiscreteCategory>,
+        right: &Rc<DiscreteCategory>,
+    ) -> Result<Cocone<Self>, Errors> {
+        let mut diagram = FiniteDiagram::new();
+        diagram.add_object(left.clone());
+        diagram.add_object(right.clone());
+        self.colimit(&diagram)
+    }
+
+    // the limit with its apex added to the objects, when it is not one already
+    pub fn add_limit(&mut self, diagram: &FiniteDiagram<Self>) -> Result<Cone<Self>, Errors> {
+        let limit = self.limit(diagram)?;
+        if self.get_object(limit.apex()).is_err() {
+            self.add_object(limit.apex().clone())?;
+        }
+        Ok(limit)
+    }
+
+    pub fn add_colimit(&mut self, diagram: &FiniteDiagram<Self>) -> Result<Cocone<Self>, Errors> {
+        let colimit = self.colimit(diagram)?;
+        if self.get_object(colimit.apex()).is_err() {
+            self.add_object(colimit.apex().clone())?;
+        }
+        Ok(colimit)
+    }
+
+    // the subset {a | f(a) = g(a)} with its inclusion
+    pub fn equalizer(
+        &self,
+        first: &Rc<Function>,
+        second: &Rc<Function>,
+    ) -> Result<(Rc<DiscreteCategory>, Rc<Function>), Errors> {
+        if first.source_object() != second.source_object()
+            || first.target_object() != second.target_object()
+        {
+            return Err(Errors::InvalidDiagram);
+        }
+        let mut elements = Vec::new();
+        for element in Self::elements(first.source_object()) {
+            if Self::apply(first, &element)? == Self::apply(second, &element)? {
+                elements.push(element);
+            }
+        }
+        let equalizer = self.construct(
+            ObjectId::generated(
+                "eq",
+                [first.arrow_id(), second.arrow_id()].map(|id| id.clone().into()),
+            ),
+            &elements,
+        )?;
+        let table = elements
+            .into_iter()
+            .map(|element| (element.clone(), element))
+            .collect();
+        let inclusion = Self::function(&equalizer, first.source_object(), &table)?;
+        Ok((equalizer, inclusion))
+    }
+
+    // B^A together with the evaluation B^A × A -> B
+    pub fn exponential(
+        &self,
+        base: &Rc<DiscreteCategory>,
+        target: &Rc<DiscreteCategory>,
+    ) -> Result<(Rc<DiscreteCategory>, Rc<Function>), Errors> {
+        let tables = Self::all_tables(base, target);
+        let elements = tables.iter().map(Self::table_id).collect::<Vec<_>>();
+        let exponential = self.construct(
+            ObjectId::generated(
+                "exp",
+                [target.category_id().clone(), base.category_id().clone()],
+            ),
+            &elements,
+        )?;
+        let product = self.product(&exponential, base)?;
+        let mut evaluation = HashMap::new();
+        for (table, element) in tables.iter().zip(elements.iter()) {
+            for (argument, image) in table {
+                evaluation.insert(
+                    Self::tuple_id(&[element.clone(), argument.clone()]),
+                    image.clone(),
+                );
+            }
+        }
+        let evaluation = Self::function(product.apex(), target, &evaluation)?;
+        Ok((exponential, evaluation))
+    }
+
+    // λf: C -> B^A for f: C × A -> B
+    pub fn transpose(
+        &self,
+        function: &Rc<Function>,
+        left: &Rc<DiscreteCategory>,
+        right: &Rc<DiscreteCategory>,
+    ) -> Result<Rc<Function>, Errors> {
+        let product = self.product(left, right)?;
+        if !Rc::ptr_eq(function.source_object(), product.apex()) {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        let (exponential, _) = self.exponential(right, function.target_object())?;
+        let mut transpose = HashMap::new();
+        for element in Self::elements(left) {
+            let mut table = HashMap::new();
+            for argument in Self::elements(right) {
+                let pair = Self::tuple_id(&[element.clone(), argument.clone()]);
+                table.insert(argument, Self::apply(function, &pair)?);
+            }
+            transpose.insert(element, Self::table_id(&table));
+        }
+        Self::function(left, &exponential, &transpose)
+    }
+
+    fn mappings(
+        source: &Rc<DiscreteCategory>,
+        target: &Rc<DiscreteCategory>,
+        table: &HashMap<ObjectId, ObjectId>,
+    ) -> Result<HashMap<Rc<Function>, Rc<Function>>, Errors> {
+        let elements = Self::elements(source);
+        if elements.len() != table.len() {
+            return Err(Errors::InvalidObjectMapping);
+        }
+        elements
+            .into_iter()
+            .map(|element| {
+                let image = table
+                    .get(&element)
+                    .ok_or(Errors::InvalidObjectMapping)?
+                    .clone();
+                Ok((
+                    source
+                        .get_identity_morphism(&DiscreteCategory::new_with_id(element))?
+                        .clone(),
+                    target
+                        .get_identity_morphism(&DiscreteCategory::new_with_id(image))?
+                        .clone(),
+                ))
+            })
+            .collect()
+    }
+
+    // a set built by a construction, the same object every time it is built
+    fn construct(
+        &self,
+        id: ObjectId,
+        elements: &[ObjectId],
+    ) -> Result<Rc<DiscreteCategory>, Errors> {
+        if let Some(set) = self.objects.get(&id) {
+            return Ok(set.clone());
+        }
+        if let Some(set) = self.constructions.borrow().get(&id) {
+            return Ok(set.clone());
+        }
+        let set = Rc::new(Self::new_set(id.clone(), elements.to_vec())?);
+        self.constructions.borrow_mut().insert(id, set.clone());
+        Ok(set)
+    }
+
+    // every table A -> B, ordered by the elements of A and B
+    pub(crate) fn all_tables(
+        source: &DiscreteCategory,
+        target: &DiscreteCategory,
+    ) -> Vec<HashMap<ObjectId, ObjectId>> {
+        let arguments = Self::elements(source);
+        let choices = vec![Self::elements(target); arguments.len()];
+        cartesian_product(&choices)
+            .into_iter()
+            .map(|images| arguments.iter().cloned().zip(images).collect())
+            .collect()
+    }
+
+    // fn((a,f(a)),...) with the pairs ordered by their display
+    fn table_id(table: &HashMap<ObjectId, ObjectId>) -> ObjectId {
+        let mut entries = table
+            .iter()
+            .map(|(element, image)| element.clone() + image.clone())
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.to_string());
+        ObjectId::generated("fn", entries)
+    }
+
+    fn graph_id(
+        source: &DiscreteCategory,
+        target: &DiscreteCategory,
+        table: &HashMap<ObjectId, ObjectId>,
+    ) -> String {
+        format!(
+            "{}->{}:{}",
+            source.category_id(),
+            target.category_id(),
+            Self::table_id(table)
+        )
+    }
+
+    fn tuple_id(elements: &[ObjectId]) -> ObjectId {
+        ObjectId::product(elements.iter().cloned())
+    }
+
+    fn injection_id(index: usize, element: &ObjectId) -> ObjectId {
+        ObjectId::coproduct(index, element.clone())
+    }
+
+    // name((objects),(edges)), e.g. lim((A,N),()) for the product A × N
+    fn diagram_id(diagram: &FiniteDiagram<Self>, name: &str) -> ObjectId {
+        let objects = diagram
+            .objects()
+            .iter()
+            .map(|object| object.category_id().clone());
+        let edges = diagram
+            .edges()
+            .iter()
+            .map(|(_, _, morphism)| morphism.arrow_id().clone().into());
+        ObjectId::generated(name, [ObjectId::product(objects), ObjectId::product(edges)])
+    }
+
+    // the tuples with one element per diagram object which every edge respects
+    fn compatible_tuples(diagram: &FiniteDiagram<Self>) -> Result<Vec<Vec<ObjectId>>, Errors> {
+        let choices = diagram
+            .objects()
+            .iter()
+            .map(|object| Self::elements(object))
+            .collect::<Vec<_>>();
+        let mut tuples = Vec::new();
+        'tuples: for tuple in cartesian_product(&choices) {
+            for (source, target, morphism) in diagram.edges() {
+                if Self::apply(morphism, &tuple[*source])? != tuple[*target] {
+                    continue 'tuples;
+                }
+            }
+            tuples.push(tuple);
+        }
+        Ok(tuples)
+    }
+
+    // the classes of the disjoint union identified along the edges, as (object index, element)
+    fn quotient_classes(
+        diagram: &FiniteDiagram<Self>,
+    ) -> Result<Vec<Vec<(usize, ObjectId)>>, Errors> {
+        let mut members = Vec::new();
+        let mut index_of = HashMap::new();
+        for (index, object) in diagram.objects().iter().enumerate() {
+            for element in Self::elements(object) {
+                index_of.insert((index, element.clone()), members.len());
+                members.push((index, element));
+            }
+        }
+        let mut parent = (0..members.len()).collect::<Vec<_>>();
+        fn find(parent: &mut [usize], member: usize) -> usize {
+            let mut root = member;
+            while parent[root] != root {
+                root = parent[root];
+            }
+            parent[member] = root;
+            root
+        }
+        for (source, target, morphism) in diagram.edges() {
+            for element in Self::elements(&diagram.objects()[*source]) {
+                let image = Self::apply(morphism, &element)?;
+                let first = find(&mut parent, index_of[&(*source, element)]);
+                let second = find(&mut parent, index_of[&(*target, image)]);
+                parent[first.max(second)] = first.min(second);
+            }
+        }
+        let mut classes: Vec<Vec<(usize, ObjectId)>> = Vec::new();
+        let mut class_of_root = HashMap::new();
+        for (member, element) in members.into_iter().enumerate() {
+            let root = find(&mut parent, member);
+            let class = *class_of_root.entry(root).or_insert_with(|| {
+                classes.push(Vec::new());
+                classes.len() - 1
+            });
+            classes[class].push(element);
+        }
+        Ok(classes)
+    }
+
+    fn class_id(class: &[(usize, ObjectId)]) -> ObjectId {
+        if let [(index, element)] = class {
+            return Self::injection_id(*index, element);
+        }
+        let members = class
+            .iter()
+            .map(|(index, element)| Self::injection_id(*index, element));
+        ObjectId::generated("class", members)
+    }
+
+    // the stored function with the graph of the given one, or the function itself
+    fn stored_or_new(&self, function: Rc<Function>) -> Rc<Function> {
+        if function.is_identity() {
+            return self
+                .get_identity_morphism(function.source_object())
+                .cloned()
+                .unwrap_or(function);
+        }
+        if let Ok(hom_set) = self.get_hom_set(function.source_object(), function.target_object()) {
+            for candidate in hom_set {
+                if Rc::ptr_eq(candidate.target_object(), function.target_object())
+                    && candidate.arrow_mappings() == function.arrow_mappings()
+                {
+                    return candidate.clone();
+                }
+            }
+        }
+        function
+    }
+}
+
+impl CategoryTrait for FinSet {
+    type Object = DiscreteCategory;
+
+    type Morphism = Function;
+
+    fn new() -> Self {
+        FinSet::new()
+    }
+
+    fn new_with_id(id: &ObjectId) -> Self {
+        FinSet::new_with_id(id.clone())
+    }
+
+    fn category_id(&self) -> &ObjectId {
+        &self.id
+    }
+
+    fn update_category_id(&mut self, new_id: ObjectId) {
+        self.id = new_id;
+    }
+
+    fn add_object(&mut self, object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        if self.objects.contains_key(object.category_id()) {
+            return Err(Errors::ObjectAlreadyExists);
+        }
+        let identity = Self::identity_function(&object);
+        self.objects
+            .insert(object.category_id().clone(), object.clone());
+        self.identities
+            .insert(object.category_id().clone(), identity.clone());
+        self.hom_sets
+            .insert(object.category_id().clone(), HashSet::new());
+        Ok(identity)
+    }
+
+    fn add_morphism(
+        &mut self,
+        morphism: Rc<Self::Morphism>,
+    ) -> Result<&Rc<Self::Morphism>, Errors> {
+        if self.functions.contains_key(morphism.arrow_id()) {
+            return Err(Errors::MorphismAlreadyExists);
+        }
+        for object in [morphism.source_object(), morphism.target_object()] {
+            match self.get_object(object) {
+                Ok(stored) if Rc::ptr_eq(stored, object) => {}
+                _ => return Err(Errors::ObjectNotFound(object.category_id().clone())),
+            }
+        }
+        // only total functions between the sets
+        let table = Self::function_table(&morphism)?;
+        Self::mappings(morphism.source_object(), morphism.target_object(), &table)?;
+        if morphism.arrow_mappings().len() != table.len() {
+            return Err(Errors::InvalidObjectMapping);
+        }
+        if morphism.is_identity() {
+            return self.get_identity_morphism(morphism.source_object());
+        }
+        self.hom_sets
+            .entry(morphism.source_object().category_id().clone())
+            .or_default()
+            .insert(morphism.arrow_id().clone());
+        Ok(self
+            .functions
+            .entry(morphism.arrow_id().clone())
+            .or_insert(morphism))
+    }
+
+    fn get_identity_morphism(&self, object: &Self::Object) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.identities
+            .get(object.category_id())
+            .ok_or(Errors::IdentityMorphismNotFound)
+    }
+
+    fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
+        self.objects
+            .get(object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))
+    }
+
+    fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
+        Ok(self.objects.values().collect())
+    }
+
+    fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .identities
+            .values()
+            .chain(self.functions.values())
+            .collect())
+    }
+
+    fn get_hom_set_x(
+        &self,
+        source_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .get_object_morphisms(source_object)?
+            .into_iter()
+            .collect())
+    }
+
+    fn get_object_morphisms(
+        &self,
+        object: &Self::Object,
+    ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
+        let object = self.get_object(object)?;
+        let mut result = vec![self.get_identity_morphism(object)?];
+        let functions = self
+            .hom_sets
+            .get(object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))?;
+        for function in functions {
+            result.push(
+                self.functions
+                    .get(function)
+                    .ok_or(Errors::MorphismNotFound)?,
+            );
+        }
+        Ok(result)
+    }
+
+    // function composition, a stored function with the same graph is preferred
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        if !Rc::ptr_eq(first.target_object(), second.source_object()) {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        if first.is_identity() {
+            return Ok(second.clone());
+        }
+        if second.is_identity() {
+            return Ok(first.clone());
+        }
+        let table = Self::function_table(first)?
+            .into_iter()
+            .map(|(element, image)| Ok((element, Self::apply(second, &image)?)))
+            .collect::<Result<HashMap<_, _>, Errors>>()?;
+        let composite = Self::function(first.source_object(), second.target_object(), &table)?;
+        Ok(self.stored_or_new(composite))
+    }
+
+    // the inverse function of a bijection, a stored function with the same graph is preferred
+    fn inverse_morphism(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        if morphism.is_identity() {
+            return Ok(Some(morphism.clone()));
+        }
+        let table = Self::function_table(morphism)?
+            .into_iter()
+            .map(|(element, image)| (image, element))
+            .collect::<HashMap<_, _>>();
+        if table.len() != Self::elements(morphism.target_object()).len()
+            || table.len() != Self::elements(morphism.source_object()).len()
+        {
+            return Ok(None);
+        }
+        let inverse = Self::function(morphism.target_object(), morphism.source_object(), &table)?;
+        Ok(Some(self.stored_or_new(inverse)))
+    }
+
+    // the injections, decided on the table as the hom-sets only hold the functions added
+    fn is_mono(&self, morphism: &Rc<Self::Morphism>) -> Result<bool, Errors> {
+        let table = Self::function_table(morphism)?;
+        Ok(table.values().collect::<HashSet<_>>().len() == table.len())
+    }
+
+    // the surjections
+    fn is_epi(&self, morphism: &Rc<Self::Morphism>) -> Result<bool, Errors> {
+        let images = Self::function_table(morphism)?
+            .into_values()
+            .collect::<HashSet<_>>();
+        Ok(images.len() == Self::elements(morphism.target_object()).len())
+    }
+
+    // an injection sends its image back and the rest to the first element, if there is one
+    fn is_split_mono(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        if !self.is_mono(morphism)? {
+            return Ok(None);
+        }
+        let table = Self::function_table(morphism)?;
+        let first = Self::elements(morphism.source_object()).into_iter().next();
+        let mut retraction = HashMap::new();
+        for element in Self::elements(morphism.target_object()) {
+            let preimage = table
+                .iter()
+                .find(|(_, image)| **image == element)
+                .map(|(argument, _)| argument.clone())
+                .or_else(|| first.clone());
+            match preimage {
+                Some(preimage) => retraction.insert(element, preimage),
+                None => return Ok(None),
+            };
+        }
+        let retraction = Self::function(
+            morphism.target_object(),
+            morphism.source_object(),
+            &retraction,
+        )?;
+        Ok(Some(self.stored_or_new(retraction)))
+    }
+
+    // a surjection has the section picking the first element of every preimage
+    fn is_split_epi(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        let mut section = HashMap::new();
+        for argument in Self::elements(morphism.source_object()).into_iter().rev() {
+            section.insert(Self::apply(morphism, &argument)?, argument);
+        }
+        if section.len() != Self::elements(morphism.target_object()).len() {
+            return Ok(None);
+        }
+        let section = Self::function(morphism.target_object(), morphism.source_object(), &section)?;
+        Ok(Some(self.stored_or_new(section)))
+    }
+}
+
+impl FiniteLimitsTrait for FinSet {
+    fn limit(&self, diagram: &FiniteDiagram<Self>) -> Result<Cone<Self>, Errors> {
+        let tuples = Self::compatible_tuples(diagram)?;
+        let elements = tuples
+            .iter()
+            .map(|tuple| Self::tuple_id(tuple))
+            .collect::<Vec<_>>();
+        let apex = self.construct(Self::diagram_id(diagram, "lim"), &elements)?;
+        let mut legs = Vec::new();
+        for (index, object) in diagram.objects().iter().enumerate() {
+            let table = tuples
+                .iter()
+                .zip(elements.iter())
+                .map(|(tuple, element)| (element.clone(), tuple[index].clone()))
+                .collect();
+            legs.push(Self::function(&apex, object, &table)?);
+        }
+        Ok(Cone::new(apex, legs))
+    }
+
+    // c -> (leg_1(c), ..., leg_n(c))
+    fn limit_mediating_morphism(
+        &self,
+        diagram: &FiniteDiagram<Self>,
+        limit: &Cone<Self>,
+        cone: &Cone<Self>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        if cone.legs().len() != diagram.objects().len() {
+            return Err(Errors::InvalidDiagram);
+        }
+        let mut table = HashMap::new();
+        for element in Self::elements(cone.apex()) {
+            let tuple = cone
+                .legs()
+                .iter()
+                .map(|leg| Self::apply(leg, &element))
+                .collect::<Result<Vec<_>, Errors>>()?;
+            table.insert(element, Self::tuple_id(&tuple));
+        }
+        Self::function(cone.apex(), limit.apex(), &table).map_err(|_| Errors::LimitNotFound)
+    }
+}
+
+impl FiniteColimitsTrait for FinSet {
+    fn colimit(&self, diagram: &FiniteDiagram<Self>) -> Result<Cocone<Self>, Errors> {
+        let classes = Self::quotient_classes(diagram)?;
+        let elements = classes
+            .iter()
+            .map(|class| Self::class_id(class))
+            .collect::<Vec<_>>();
+        let apex = self.construct(Self::diagram_id(diagram, "colim"), &elements)?;
+        let mut tables = vec![HashMap::new(); diagram.objects().len()];
+        for (class, element) in classes.iter().zip(elements.iter()) {
+            for (index, member) in class {
+                tables[*index].insert(member.clone(), element.clone());
+            }
+        }
+        let legs = diagram
+            .objects()
+            .iter()
+            .zip(tables.iter())
+            .map(|(object, table)| Self::function(object, &apex, table))
+            .collect::<Result<Vec<_>, Errors>>()?;
+        Ok(Cocone::new(apex, legs))
+    }
+
+    // [x] -> leg_i(x), which has to agree on every member of the class
+    fn colimit_mediating_morphism(
+        &self,
+        diagram: &FiniteDiagram<Self>,
+        colimit: &Cocone<Self>,
+        cocone: &Cocone<Self>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        if cocone.legs().len() != diagram.objects().len() {
+            return Err(Errors::InvalidDiagram);
+        }
+        let mut table = HashMap::new();
+        for class in Self::quotient_classes(diagram)? {
+            let mut images = class
+                .iter()
+                .map(|(index, member)| Self::apply(&cocone.legs()[*index], member))
+                .collect::<Result<HashSet<_>, Errors>>()?;
+            if images.len() != 1 {
+                return Err(Errors::ColimitNotFound);
+            }
+            let image = images.drain().next().ok_or(Errors::ColimitNotFound)?;
+            table.insert(Self::class_id(&class), image);
+        }
+        Self::function(colimit.apex(), cocone.apex(), &table).map_err(|_| Errors::ColimitNotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(entries: &[(&str, i32)]) -> HashMap<ObjectId, ObjectId> {
+        entries
+            .iter()
+            .map(|(element, image)| ((*element).into(), (*image).into()))
+            .collect()
+    }
+
+    #[test]
+    fn test_functions_and_composition() {
+        let mut fin_set = FinSet::new();
+        let letters = fin_set.add_set("letters", vec!["a", "b", "c"]).unwrap();
+        let numbers = fin_set.add_set("numbers", vec![1, 2]).unwrap();
+        let f = fin_set
+            .add_function(
+                "f",
+                &letters,
+                &numbers,
+                table(&[("a", 1), ("b", 2), ("c", 1)]),
+            )
+            .unwrap();
+        // a partial function is not a morphism
+        assert!(
+            fin_set
+                .add_function("partial", &letters, &numbers, table(&[("a", 1)]))
+                .is_err()
+        );
+
+        let identity = fin_set.get_identity_morphism(&letters).unwrap().clone();
+        assert_eq!(
+            FinSet::apply(&identity, &"b".into()).unwrap(),
+            ObjectId::from("b")
+        );
+        assert_eq!(fin_set.compose_morphisms(&identity, &f).unwrap(), f);
+
+        let mut section = HashMap::new();
+        section.insert(ObjectId::Int(1), ObjectId::from("a"));
+        section.insert(ObjectId::Int(2), ObjectId::from("b"));
+        let g = fin_set
+            .add_function("g", &numbers, &letters, section)
+            .unwrap();
+        let g_after_f = fin_set.compose_morphisms(&f, &g).unwrap();
+        assert_eq!(
+            FinSet::function_table(&g_after_f).unwrap(),
+            HashMap::from([
+                ("a".into(), "a".into()),
+                ("b".into(), "b".into()),
+                ("c".into(), "a".into())
+            ])
+        );
+        // f ∘ g is the identity on numbers
+        let f_after_g = fin_set.compose_morphisms(&g, &f).unwrap();
+        assert!(f_after_g.is_identity());
+        assert_eq!(&f_after_g, fin_set.get_identity_morphism(&numbers).unwrap());
+    }
+
+    #[test]
+    fn test_limits_and_colimits() {
+        let mut fin_set = FinSet::new();
+        let letters = fin_set.add_set("A", vec!["a", "b", "c"]).unwrap();
+        let numbers = fin_set.add_set("N", vec![1, 2]).unwrap();
+
+        let product = fin_set.product(&letters, &numbers).unwrap();
+        assert_eq!(FinSet::elements(product.apex()).len(), 6);
+        assert_eq!(
+            FinSet::apply(&product.legs()[1], &(ObjectId::from("b") + 2.into())).unwrap(),
+            ObjectId::Int(2)
+        );
+        // built once, so the legs are the same morphisms
+        let again = fin_set.product(&letters, &numbers).unwrap();
+        assert!(Rc::ptr_eq(product.apex(), again.apex()));
+        assert_eq!(product.legs(), again.legs());
+
+        let coproduct = fin_set.coproduct(&letters, &numbers).unwrap();
+        assert_eq!(FinSet::elements(coproduct.apex()).len(), 5);
+
+        // the apexes become objects of the category once added
+        let apex = product.apex();
+        assert!(fin_set.get_object(apex).is_err());
+        let mut pair = FiniteDiagram::new();
+        pair.add_object(letters.clone());
+        pair.add_object(numbers.clone());
+        assert_eq!(fin_set.add_limit(&pair).unwrap().legs(), product.legs());
+        fin_set.add_colimit(&pair).unwrap();
+        assert!(Rc::ptr_eq(fin_set.get_object(apex).unwrap(), apex));
+        let identity = fin_set.get_identity_morphism(apex).unwrap().clone();
+        assert!(identity.is_identity());
+        assert_eq!(
+            fin_set
+                .compose_morphisms(&identity, &product.legs()[0])
+                .unwrap(),
+            product.legs()[0]
+        );
+        assert_eq!(fin_set.get_hom_set_x(apex).unwrap().len(), 1);
+        assert!(
+            fin_set
+                .get_all_objects()
+                .unwrap()
+                .contains(coproduct.apex())
+        );
+        fin_set.add_morphism(product.legs()[0].clone()).unwrap();
+        assert_eq!(fin_set.get_hom_set(apex, &letters).unwrap().len(), 1);
+
+        let f = fin_set
+            .add_function(
+                "f",
+                &letters,
+                &numbers,
+                table(&[("a", 1), ("b", 2), ("c", 1)]),
+            )
+            .unwrap();
+        let g = fin_set
+            .add_function(
+                "g",
+                &letters,
+                &numbers,
+                table(&[("a", 1), ("b", 1), ("c", 2)]),
+            )
+            .unwrap();
+        let (equalizer, inclusion) = fin_set.equalizer(&f, &g).unwrap();
+        assert_eq!(FinSet::elements(&equalizer), vec![ObjectId::from("a")]);
+        assert_eq!(
+            fin_set.compose_morphisms(&inclusion, &f).unwrap(),
+            fin_set.compose_morphisms(&inclusion, &g).unwrap()
+        );
+
+        // the coequalizer identifies b and c with 1 and 2
+        let mut diagram = FiniteDiagram::new();
+        diagram.add_object(letters.clone());
+        diagram.add_object(numbers.clone());
+        diagram.add_edge(0, 1, f.clone()).unwrap();
+        diagram.add_edge(0, 1, g.clone()).unwrap();
+        let coequalizer = fin_set.colimit(&diagram).unwrap();
+        assert_eq!(FinSet::elements(coequalizer.apex()).len(), 1);
+        let equalizer = fin_set.limit(&diagram).unwrap();
+        assert_eq!(FinSet::elements(equalizer.apex()).len(), 1);
+    }
+
+    #[test]
+    fn test_many_constructions() {
+        let mut fin_set = FinSet::new();
+        let point = fin_set.add_set("P", vec!["p"]).unwrap();
+        // P, P × P, (P × P) × P, ... each added as it is built
+        let mut apexes = vec![point.clone()];
+        for _ in 0..40 {
+            let mut diagram = FiniteDiagram::new();
+            diagram.add_object(apexes.last().unwrap().clone());
+            diagram.add_object(point.clone());
+            apexes.push(fin_set.add_limit(&diagram).unwrap().apex().clone());
+        }
+        assert_eq!(fin_set.get_all_objects().unwrap().len(), 41);
+        for apex in &apexes {
+            assert!(Rc::ptr_eq(fin_set.get_object(apex).unwrap(), apex));
+            assert!(fin_set.get_identity_morphism(apex).unwrap().is_identity());
+        }
+        let again = fin_set.product(&apexes[20], &point).unwrap();
+        assert!(Rc::ptr_eq(again.apex(), &apexes[21]));
+    }
+
+    #[test]
+    fn test_exponential() {
+        let mut fin_set = FinSet::new();
+        let letters = fin_set.add_set("A", vec!["a", "b"]).unwrap();
+        let numbers = fin_set.add_set("N", vec![1, 2, 3]).unwrap();
+        let (exponential, evaluation) = fin_set.exponential(&letters, &numbers).unwrap();
+        assert_eq!(FinSet::elements(&exponential).len(), 9);
+
+        // transposing the evaluation gives the identity on N^A
+        let transpose = fin_set
+            .transpose(&evaluation, &exponential, &letters)
+            .unwrap();
+        assert!(transpose.is_identity());
+
+        // ev ∘ (λf × id) = f for f: 1 × A -> N
+        let unit = fin_set.limit(&FiniteDiagram::new()).unwrap();
+        let point = fin_set.product(unit.apex(), &letters).unwrap();
+        let f = FinSet::function(
+            point.apex(),
+            &numbers,
+            &[("a", 3), ("b", 1)]
+                .into_iter()
+                .map(|(letter, number)| (ObjectId::product([]) + letter.into(), number.into()))
+                .collect(),
+        )
+        .unwrap();
+        let curried = fin_set.transpose(&f, unit.apex(), &letters).unwrap();
+        let element = FinSet::apply(&curried, &ObjectId::product([])).unwrap();
+        assert_eq!(element.to_string(), "fn((a,3),(b,1))");
+        assert_eq!(
+            FinSet::apply(&evaluation, &(element.clone() + "b".into())).unwrap(),
+            ObjectId::Int(1)
+        );
+    }
+
+    #[test]
+    fn test_monos_epis_and_splittings() {
+        // every function between {x} and {a, b}
+        let mut fin_set = FinSet::new();
+        let point = fin_set.add_set("point", vec!["x"]).unwrap();
+        let pair = fin_set.add_set("pair", vec!["a", "b"]).unwrap();
+        let mut add = |id: &str, source, target, entries: &[(&str, &str)]| {
+            let table = entries
+                .iter()
+                .map(|(element, image)| ((*element).into(), (*image).into()))
+                .collect();
+            fin_set.add_function(id, source, target, table).unwrap()
+        };
+        let pick_a = add("pick_a", &point, &pair, &[("x", "a")]);
+        add("pick_b", &point, &pair, &[("x", "b")]);
+        let collapse = add("collapse", &pair, &point, &[("a", "x"), ("b", "x")]);
+        let swap = add("swap", &pair, &pair, &[("a", "b"), ("b", "a")]);
+        let constant = add("constant", &pair, &pair, &[("a", "a"), ("b", "a")]);
+        add("constant_b", &pair, &pair, &[("a", "b"), ("b", "b")]);
+
+        assert!(fin_set.is_mono(&pick_a).unwrap());
+        assert!(!fin_set.is_epi(&pick_a).unwrap());
+        assert_eq!(
+            fin_set.is_split_mono(&pick_a).unwrap(),
+            Some(collapse.clone())
+        );
+        assert_eq!(fin_set.is_split_epi(&pick_a).unwrap(), None);
+
+        assert!(fin_set.is_epi(&collapse).unwrap());
+        assert!(!fin_set.is_mono(&collapse).unwrap());
+        assert_eq!(fin_set.is_split_epi(&collapse).unwrap(), Some(pick_a));
+
+        assert!(fin_set.is_mono(&swap).unwrap() && fin_set.is_epi(&swap).unwrap());
+        assert_eq!(fin_set.is_split_mono(&swap).unwrap(), Some(swap.clone()));
+        assert!(!fin_set.is_mono(&constant).unwrap());
+        assert!(!fin_set.is_epi(&constant).unwrap());
+        assert_eq!(fin_set.is_split_epi(&constant).unwrap(), None);
+    }
+
+    #[test]
+    fn test_monos_and_epis_without_rival_functions() {
+        // only the collapse of {a, b} onto {x}, no function picks a or b
+        let mut fin_set = FinSet::new();
+        let point = fin_set.add_set("point", vec!["x"]).unwrap();
+        let pair = fin_set.add_set("pair", vec!["a", "b"]).unwrap();
+        let collapse = fin_set
+            .add_function(
+                "collapse",
+                &pair,
+                &point,
+                HashMap::from([("a".into(), "x".into()), ("b".into(), "x".into())]),
+            )
+            .unwrap();
+        assert!(!fin_set.is_mono(&collapse).unwrap());
+        assert!(fin_set.is_epi(&collapse).unwrap());
+        assert_eq!(fin_set.is_split_mono(&collapse).unwrap(), None);
+        let section = fin_set.is_split_epi(&collapse).unwrap().unwrap();
+        assert_eq!(
+            FinSet::function_table(&section).unwrap(),
+            HashMap::from([("x".into(), "a".into())])
+        );
+        assert!(
+            fin_set
+                .compose_morphisms(&section, &collapse)
+                .unwrap()
+                .is_identity()
+        );
+
+        // its section is mono, with the collapse as retraction, but not epi
+        assert!(fin_set.is_mono(&section).unwrap());
+        assert!(!fin_set.is_epi(&section).unwrap());
+        assert_eq!(fin_set.is_split_mono(&section).unwrap(), Some(collapse));
+
+        // the empty function into a point is mono without a retraction
+        let empty = fin_set.add_set("empty", Vec::<ObjectId>::new()).unwrap();
+        let from_empty = FinSet::function(&empty, &point, &HashMap::new()).unwrap();
+        assert!(fin_set.is_mono(&from_empty).unwrap());
+        assert_eq!(fin_set.is_split_mono(&from_empty).unwrap(), None);
+    }
+}
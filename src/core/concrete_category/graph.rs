@@ -0,0 +1,1310 @@
+/*
+Graph, the category of finite directed multigraphs and graph homomorphisms.
+
+A DirectedGraph has vertices as objects and edges as morphisms between them, any number
+of them between two vertices. Vertices keep their identities so that graph homomorphisms
+are arrows like functors, mapping the identity of a vertex to the identity of its image
+and every edge to an edge.
+
+A DirectedGraph is a category only as far as its identities go: they compose with every
+edge, while two edges have no composite in the graph and composing them is an error, so the
+searches over a category (monos, limits) fail on it instead of missing composites. The free
+category Free(G) on an acyclic graph is its PathCategory, with the paths as morphisms and
+g ∘ f the path f then g. A graph with a cycle has infinitely many paths, and no PathCategory.
+
+The forgetful functor sends a category C to its underlying graph U(C): the same objects,
+and an edge for every morphism, identities included, as they are forgotten along with
+composition. A functor F: C -> D becomes the homomorphism U(F): U(C) -> U(D). It is right
+adjoint to the free category: a homomorphism G -> U(C) is a functor Free(G) -> C given on
+the edges, see validate_free_forgetful_adjunction.
+ */
+use crate::core::arrow::{Arrow, Functor, Morphism};
+use crate::core::base_category::BaseCategory;
+use crate::core::discrete_category::DiscreteCategory;
+use crate::core::errors::Errors;
+use crate::core::identifier::Identifier;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::utils::{cartesian_product, sorted_morphisms, sorted_objects};
+use dyn_clone::DynClone;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+pub type GraphHomomorphism<Vertex> = Morphism<DirectedGraph<Vertex>>;
+
+#[derive(Debug)]
+pub struct DirectedGraph<Vertex: CategoryTrait = DiscreteCategory> {
+    id: ObjectId,
+    vertices: HashMap<ObjectId, Rc<Vertex>>,
+    identities: HashMap<ObjectId, Rc<Morphism<Vertex>>>,
+    edges: HashMap<String, Rc<Morphism<Vertex>>>,
+    // ids of the edges out of each vertex
+    out_edges: HashMap<ObjectId, Vec<String>>,
+}
+
+impl<Vertex: CategoryTrait> Clone for DirectedGraph<Vertex> {
+    fn clone(&self) -> Self {
+        DirectedGraph {
+            id: self.id.clone(),
+            vertices: self.vertices.clone(),
+            identities: self.identities.clone(),
+            edges: self.edges.clone(),
+            out_edges: self.out_edges.clone(),
+        }
+    }
+}
+
+impl<Vertex: CategoryTrait> PartialEq for DirectedGraph<Vertex> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<Vertex: CategoryTrait> Eq for DirectedGraph<Vertex> {}
+
+impl<Vertex: CategoryTrait> Hash for DirectedGraph<Vertex> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<Vertex: CategoryTrait + Hash + Eq + Clone> DirectedGraph<Vertex> {
+    pub fn new_with_id(id: ObjectId) -> Self {
+        DirectedGraph {
+            id,
+            vertices: HashMap::new(),
+            identities: HashMap::new(),
+            edges: HashMap::new(),
+            out_edges: HashMap::new(),
+        }
+    }
+
+    pub fn vertex(&self, id: &ObjectId) -> Result<&Rc<Vertex>, Errors> {
+        self.vertices
+            .get(id)
+            .ok_or_else(|| Errors::ObjectNotFound(id.clone()))
+    }
+
+    pub fn edge(&self, id: &str) -> Result<&Rc<Morphism<Vertex>>, Errors> {
+        self.edges.get(id).ok_or(Errors::MorphismNotFound)
+    }
+
+    pub fn add_edge(
+        &mut self,
+        id: &str,
+        source: &ObjectId,
+        target: &ObjectId,
+    ) -> Result<Rc<Morphism<Vertex>>, Errors> {
+        let edge = Rc::new(Arrow::new(
+            id.to_string(),
+            self.vertex(source)?.clone(),
+            self.vertex(target)?.clone(),
+            HashMap::new(),
+        ));
+        Ok(self.add_morphism(edge)?.clone())
+    }
+
+    // the edges out of a vertex, without its identity
+    pub fn edges_out(&self, vertex: &Vertex) -> Result<Vec<&Rc<Morphism<Vertex>>>, Errors> {
+        Ok(self.get_object_morphisms(vertex)?.split_off(1))
+    }
+
+    // the edges ordered by id, without the identities of the vertices
+    pub fn edges(&self) -> Vec<&Rc<Morphism<Vertex>>> {
+        sorted_morphisms(self.edges.values().collect())
+    }
+
+    // the underlying graph U(C) of a category
+    pub fn underlying(category: &BaseCategory<Vertex>) -> Result<Self, Errors>
+    where
+        Vertex: DynClone,
+    {
+        let mut graph =
+            DirectedGraph::new_with_id(ObjectId::generated("U", [category.category_id().clone()]));
+        let mut morphisms = Vec::new();
+        for object in sorted_objects(category.get_all_objects()?) {
+            graph.add_object(object.clone())?;
+            morphisms.extend(sorted_morphisms(category.get_hom_set_x(object)?));
+        }
+        for morphism in morphisms {
+            let edge = match morphism.is_identity() {
+                true => Rc::new(Arrow::new(
+                    morphism.arrow_id().clone(),
+                    morphism.source_object().clone(),
+                    morphism.target_object().clone(),
+                    morphism.arrow_mappings().clone(),
+                )),
+                false => morphism.clone(),
+            };
+            graph.add_morphism(edge)?;
+        }
+        Ok(graph)
+    }
+}
+
+impl DirectedGraph<DiscreteCategory> {
+    /*
+    Reads a quiver, one vertex or edge per line:
+
+        a
+        f: a -> b
+
+    Vertices of edges are added when they are first seen, blank lines are skipped.
+     */
+    pub fn from_quiver(id: impl Into<ObjectId>, quiver: &str) -> Result<Self, Errors> {
+        let mut graph = Self::new_with_id(id.into());
+        for line in quiver
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+        {
+            let Some((edge, endpoints)) = line.split_once(':') else {
+                graph.add_vertex(line)?;
+                continue;
+            };
+            let (source, target) = endpoints.split_once("->").ok_or_else(|| {
+                Errors::InvalidOperation(format!("Cannot read the edge {}", line))
+            })?;
+            let source = graph.add_vertex(source.trim())?;
+            let target = graph.add_vertex(target.trim())?;
+            graph.add_edge(edge.trim(), source.category_id(), target.category_id())?;
+        }
+        Ok(graph)
+    }
+
+    // adds a vertex, returning the existing one with the same id
+    pub fn add_vertex(&mut self, id: impl Into<ObjectId>) -> Result<Rc<DiscreteCategory>, Errors> {
+        let id = id.into();
+        if let Ok(vertex) = self.vertex(&id) {
+            return Ok(vertex.clone());
+        }
+        let vertex = Rc::new(DiscreteCategory::new_with_id(id));
+        self.add_object(vertex.clone())?;
+        Ok(vertex)
+    }
+}
+
+impl<Vertex: CategoryTrait + Hash + Eq + Clone> CategoryTrait for DirectedGraph<Vertex> {
+    type Object = Vertex;
+
+    type Morphism = Morphism<Vertex>;
+
+    fn new() -> Self {
+        Self::new_with_id(ObjectId::generate())
+    }
+
+    fn new_with_id(id: &ObjectId) -> Self {
+        DirectedGraph::new_with_id(id.clone())
+    }
+
+    fn category_id(&self) -> &ObjectId {
+        &self.id
+    }
+
+    fn update_category_id(&mut self, new_id: ObjectId) {
+        self.id = new_id;
+    }
+
+    fn add_object(&mut self, object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        let id = object.category_id().clone();
+        if self.vertices.contains_key(&id) {
+            return Err(Errors::ObjectAlreadyExists);
+        }
+        let identity =
+            Arrow::new_identity_with_mappings(format!("id_{}", id), object.clone(), HashMap::new());
+        self.vertices.insert(id.clone(), object);
+        self.identities.insert(id.clone(), identity.clone());
+        self.out_edges.insert(id, Vec::new());
+        Ok(identity)
+    }
+
+    fn add_morphism(
+        &mut self,
+        morphism: Rc<Self::Morphism>,
+    ) -> Result<&Rc<Self::Morphism>, Errors> {
+        if morphism.is_identity() {
+            return Err(Errors::InvalidOperation(
+                "Identities of a graph come with its vertices".to_string(),
+            ));
+        }
+        if self.edges.contains_key(morphism.arrow_id()) {
+            return Err(Errors::MorphismAlreadyExists);
+        }
+        for vertex in [morphism.source_object(), morphism.target_object()] {
+            match self.vertices.get(vertex.category_id()) {
+                Some(stored) if Rc::ptr_eq(stored, vertex) => {}
+                _ => return Err(Errors::ObjectNotFound(vertex.category_id().clone())),
+            }
+        }
+        self.out_edges
+            .entry(morphism.source_object().category_id().clone())
+            .or_default()
+            .push(morphism.arrow_id().clone());
+        Ok(self
+            .edges
+            .entry(morphism.arrow_id().clone())
+            .or_insert(morphism))
+    }
+
+    fn get_identity_morphism(&self, object: &Self::Object) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.identities
+            .get(object.category_id())
+            .ok_or(Errors::IdentityMorphismNotFound)
+    }
+
+    fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
+        self.vertex(object.category_id())
+    }
+
+    fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
+        Ok(self.vertices.values().collect())
+    }
+
+    fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .identities
+            .values()
+            .chain(self.edges.values())
+            .collect())
+    }
+
+    fn get_hom_set_x(
+        &self,
+        source_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .get_object_morphisms(source_object)?
+            .into_iter()
+            .collect())
+    }
+
+    fn get_object_morphisms(
+        &self,
+        object: &Self::Object,
+    ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
+        let edges = self
+            .out_edges
+            .get(object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))?;
+        let mut result = vec![self.get_identity_morphism(object)?];
+        for edge in edges {
+            result.push(self.edge(edge)?);
+        }
+        Ok(result)
+    }
+
+    // only identities compose, the paths of edges are morphisms of the PathCategory
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        if first.target_object() != second.source_object() {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        match (first.is_identity(), second.is_identity()) {
+            (true, _) => Ok(second.clone()),
+            (_, true) => Ok(first.clone()),
+            _ => Err(Errors::InvalidOperation(format!(
+                "The edges {} and {} of a graph do not compose",
+                first.arrow_id(),
+                second.arrow_id()
+            ))),
+        }
+    }
+}
+
+// Free(G), the vertices of an acyclic graph and its paths, the edges being the paths of length one
+#[derive(Debug)]
+pub struct PathCategory<Vertex: CategoryTrait = DiscreteCategory> {
+    id: ObjectId,
+    graph: Rc<DirectedGraph<Vertex>>,
+    // the paths out of each vertex, its identity first
+    paths: HashMap<ObjectId, Vec<Rc<Morphism<Vertex>>>>,
+    // the edges along each path, none along the identities
+    path_edges: HashMap<Rc<Morphism<Vertex>>, Vec<Rc<Morphism<Vertex>>>>,
+    // the path along the edges, for one edge or more
+    edge_paths: HashMap<Vec<Rc<Morphism<Vertex>>>, Rc<Morphism<Vertex>>>,
+}
+
+impl<Vertex: CategoryTrait> Clone for PathCategory<Vertex> {
+    fn clone(&self) -> Self {
+        PathCategory {
+            id: self.id.clone(),
+            graph: self.graph.clone(),
+            paths: self.paths.clone(),
+            path_edges: self.path_edges.clone(),
+            edge_paths: self.edge_paths.clone(),
+        }
+    }
+}
+
+impl<Vertex: CategoryTrait + Hash + Eq + Clone> PathCategory<Vertex> {
+    // all paths of the graph, failing on a cycle as the paths around it never end
+    pub fn from_graph(graph: &Rc<DirectedGraph<Vertex>>) -> Result<Self, Errors> {
+        let mut result = PathCategory {
+            id: ObjectId::generated("Free", [graph.category_id().clone()]),
+            graph: graph.clone(),
+            paths: HashMap::new(),
+            path_edges: HashMap::new(),
+            edge_paths: HashMap::new(),
+        };
+        for vertex in sorted_objects(graph.get_all_objects()?) {
+            let identity = graph.get_identity_morphism(vertex)?.clone();
+            result.path_edges.insert(identity.clone(), Vec::new());
+            let mut paths = vec![identity];
+            // the paths of one more edge than the last round, with the vertex they end at
+            let mut frontier = vec![(vertex.clone(), Vec::new())];
+            while !frontier.is_empty() {
+                let mut next = Vec::new();
+                for (end, edges) in frontier {
+                    for edge in graph.edges_out(&end)? {
+                        let mut extended: Vec<Rc<Morphism<Vertex>>> = edges.clone();
+                        extended.push(edge.clone());
+                        // n edges without a cycle pass n + 1 different vertices
+                        if extended.len() >= graph.vertices.len() {
+                            return Err(Errors::InvalidOperation(format!(
+                                "The graph {} has a cycle, its paths never end",
+                                graph.category_id()
+                            )));
+                        }
+                        next.push((edge.target_object().clone(), extended));
+                    }
+                }
+                for (_, edges) in &next {
+                    let path = match edges.as_slice() {
+                        [edge] => edge.clone(),
+                        _ => Rc::new(Arrow::new(
+                            edges
+                                .iter()
+                                .rev()
+                                .map(|edge| edge.arrow_id().as_str())
+                                .collect::<Vec<_>>()
+                                .join("∘"),
+                            vertex.clone(),
+                            edges[edges.len() - 1].target_object().clone(),
+                            HashMap::new(),
+                        )),
+                    };
+                    result.path_edges.insert(path.clone(), edges.clone());
+                    result.edge_paths.insert(edges.clone(), path.clone());
+                    paths.push(path);
+                }
+                frontier = next;
+            }
+            result.paths.insert(vertex.category_id().clone(), paths);
+        }
+        Ok(result)
+    }
+
+    pub fn graph(&self) -> &Rc<DirectedGraph<Vertex>> {
+        &self.graph
+    }
+
+    // the edges along a path, in order
+    pub fn path_edges(
+        &self,
+        path: &Morphism<Vertex>,
+    ) -> Result<&Vec<Rc<Morphism<Vertex>>>, Errors> {
+        self.path_edges.get(path).ok_or(Errors::MorphismNotFound)
+    }
+
+    // the path along one edge or more
+    pub fn path(&self, edges: &[Rc<Morphism<Vertex>>]) -> Result<&Rc<Morphism<Vertex>>, Errors> {
+        self.edge_paths.get(edges).ok_or(Errors::MorphismNotFound)
+    }
+}
+
+impl<Vertex: CategoryTrait + Hash + Eq + Clone> CategoryTrait for PathCategory<Vertex> {
+    type Object = Vertex;
+
+    type Morphism = Morphism<Vertex>;
+
+    fn new() -> Self {
+        PathCategory::from_graph(&Rc::new(DirectedGraph::new()))
+            .expect("an empty graph has no cycle")
+    }
+
+    fn new_with_id(id: &ObjectId) -> Self {
+        let mut result = Self::new();
+        result.update_category_id(id.clone());
+        result
+    }
+
+    fn category_id(&self) -> &ObjectId {
+        &self.id
+    }
+
+    fn update_category_id(&mut self, new_id: ObjectId) {
+        self.id = new_id;
+    }
+
+    fn add_object(&mut self, _object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        Err(Errors::InvalidOperation(
+            "A path category is built from its graph".to_string(),
+        ))
+    }
+
+    fn add_morphism(
+        &mut self,
+        _morphism: Rc<Self::Morphism>,
+    ) -> Result<&Rc<Self::Morphism>, Errors> {
+        Err(Errors::InvalidOperation(
+            "A path category is built from its graph".to_string(),
+        ))
+    }
+
+    fn get_identity_morphism(&self, object: &Self::Object) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.graph.get_identity_morphism(object)
+    }
+
+    fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
+        self.graph.get_object(object)
+    }
+
+    fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
+        self.graph.get_all_objects()
+    }
+
+    fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self.paths.values().flatten().collect())
+    }
+
+    fn get_hom_set_x(
+        &self,
+        source_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .get_object_morphisms(source_object)?
+            .into_iter()
+            .collect())
+    }
+
+    fn get_object_morphisms(
+        &self,
+        object: &Self::Object,
+    ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
+        let paths = self
+            .paths
+            .get(object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))?;
+        Ok(paths.iter().collect())
+    }
+
+    // paths compose by concatenation, the path f then g is g∘f
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        if first.target_object() != second.source_object() {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        let mut edges = self.path_edges(first)?.clone();
+        edges.extend(self.path_edges(second)?.iter().cloned());
+        match edges.is_empty() {
+            true => Ok(first.clone()),
+            false => Ok(self.path(&edges)?.clone()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Graph<Vertex: CategoryTrait + Hash + Eq + Clone = DiscreteCategory> {
+    id: ObjectId,
+    objects: HashMap<ObjectId, Rc<DirectedGraph<Vertex>>>,
+    identities: HashMap<ObjectId, Rc<GraphHomomorphism<Vertex>>>,
+    // non-identity homomorphisms out of each graph
+    hom_sets: HashMap<ObjectId, Vec<Rc<GraphHomomorphism<Vertex>>>>,
+}
+
+impl<Vertex: CategoryTrait + Hash + Eq + Clone> Clone for Graph<Vertex> {
+    fn clone(&self) -> Self {
+        Graph {
+            id: self.id.clone(),
+            objects: self.objects.clone(),
+            identities: self.identities.clone(),
+            hom_sets: self.hom_sets.clone(),
+        }
+    }
+}
+
+impl<Vertex: CategoryTrait + Hash + Eq + Clone> Graph<Vertex> {
+    pub fn new_with_id(id: ObjectId) -> Self {
+        Graph {
+            id,
+            objects: HashMap::new(),
+            identities: HashMap::new(),
+            hom_sets: HashMap::new(),
+        }
+    }
+
+    // the homomorphism with the given vertex and edge maps, checked to preserve endpoints
+    pub fn homomorphism(
+        id: &str,
+        source: &Rc<DirectedGraph<Vertex>>,
+        target: &Rc<DirectedGraph<Vertex>>,
+        vertex_map: &HashMap<ObjectId, ObjectId>,
+        edge_map: &HashMap<String, String>,
+    ) -> Result<Rc<GraphHomomorphism<Vertex>>, Errors> {
+        let mut mappings = HashMap::new();
+        let mut image = |vertex: &ObjectId| -> Result<Rc<Morphism<Vertex>>, Errors> {
+            let mapped = vertex_map
+                .get(vertex)
+                .ok_or_else(|| Errors::InvalidFunctor(format!("No image for vertex {}", vertex)))?;
+            let identity = target
+                .get_identity_morphism(target.vertex(mapped)?)?
+                .clone();
+            mappings.insert(
+                source
+                    .get_identity_morphism(source.vertex(vertex)?)?
+                    .clone(),
+                identity.clone(),
+            );
+            Ok(identity)
+        };
+        let mut edges = Vec::new();
+        for edge in source.edges() {
+            let mapped = edge_map.get(edge.arrow_id()).ok_or_else(|| {
+                Errors::InvalidFunctor(format!("No image for edge {}", edge.arrow_id()))
+            })?;
+            let mapped = target.edge(mapped)?;
+            let source_image = image(edge.source_object().category_id())?;
+            let target_image = image(edge.target_object().category_id())?;
+            if mapped.source_object() != source_image.source_object()
+                || mapped.target_object() != target_image.source_object()
+            {
+                return Err(Errors::InvalidFunctor(format!(
+                    "Edge {} is mapped to {} which does not preserve its endpoints",
+                    edge.arrow_id(),
+                    mapped.arrow_id()
+                )));
+            }
+            edges.push((edge.clone(), mapped.clone()));
+        }
+        for vertex in source.vertices.keys() {
+            image(vertex)?;
+        }
+        mappings.extend(edges);
+        let is_identity =
+            Rc::ptr_eq(source, target) && mappings.iter().all(|(edge, mapped)| edge == mapped);
+        Ok(match is_identity {
+            true => Arrow::new_identity_with_mappings(id.to_string(), source.clone(), mappings),
+            false => Rc::new(Arrow::new(
+                id.to_string(),
+                source.clone(),
+                target.clone(),
+                mappings,
+            )),
+        })
+    }
+
+    pub fn add_homomorphism(
+        &mut self,
+        id: &str,
+        source: &Rc<DirectedGraph<Vertex>>,
+        target: &Rc<DirectedGraph<Vertex>>,
+        vertex_map: &HashMap<ObjectId, ObjectId>,
+        edge_map: &HashMap<String, String>,
+    ) -> Result<Rc<GraphHomomorphism<Vertex>>, Errors> {
+        let homomorphism = Self::homomorphism(id, source, target, vertex_map, edge_map)?;
+        Ok(self.add_morphism(homomorphism)?.clone())
+    }
+
+    pub fn vertex_image<'a>(
+        homomorphism: &'a GraphHomomorphism<Vertex>,
+        vertex: &Vertex,
+    ) -> Result<&'a Rc<Vertex>, Errors> {
+        homomorphism.map_object(vertex)
+    }
+
+    pub fn edge_image<'a>(
+        homomorphism: &'a GraphHomomorphism<Vertex>,
+        edge: &Morphism<Vertex>,
+    ) -> Result<&'a Rc<Morphism<Vertex>>, Errors> {
+        homomorphism.map_morphism(edge)
+    }
+
+    // all homomorphisms between two graphs, every vertex map with every choice of edges over it
+    pub fn homomorphisms(
+        source: &Rc<DirectedGraph<Vertex>>,
+        target: &Rc<DirectedGraph<Vertex>>,
+    ) -> Result<Vec<Rc<GraphHomomorphism<Vertex>>>, Errors> {
+        let vertices = sorted_objects(source.get_all_objects()?)
+            .into_iter()
+            .map(|vertex| vertex.category_id().clone())
+            .collect::<Vec<_>>();
+        let images = sorted_objects(target.get_all_objects()?)
+            .into_iter()
+            .map(|vertex| vertex.category_id().clone())
+            .collect::<Vec<_>>();
+        let edges = source.edges();
+        let mut result = Vec::new();
+        for choice in cartesian_product(&vec![images; vertices.len()]) {
+            let vertex_map = vertices
+                .iter()
+                .cloned()
+                .zip(choice)
+                .collect::<HashMap<_, _>>();
+            let options = edges
+                .iter()
+                .map(|edge| {
+                    let source_image = &vertex_map[edge.source_object().category_id()];
+                    let target_image = &vertex_map[edge.target_object().category_id()];
+                    target
+                        .edges()
+                        .into_iter()
+                        .filter(|candidate| {
+                            candidate.source_object().category_id() == source_image
+                                && candidate.target_object().category_id() == target_image
+                        })
+                        .map(|candidate| candidate.arrow_id().clone())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            for edge_choice in cartesian_product(&options) {
+                let edge_map = edges
+                    .iter()
+                    .map(|edge| edge.arrow_id().clone())
+                    .zip(edge_choice)
+                    .collect();
+                let id = String::generate();
+                result.push(Self::homomorphism(
+                    &id,
+                    source,
+                    target,
+                    &vertex_map,
+                    &edge_map,
+                )?);
+            }
+        }
+        Ok(result)
+    }
+
+    // U(F): U(C) -> U(D), the action of a functor F: C -> D on objects and morphisms
+    pub fn underlying_homomorphism(
+        functor: &Functor<BaseCategory<Vertex>, BaseCategory<Vertex>>,
+        source: &Rc<DirectedGraph<Vertex>>,
+        target: &Rc<DirectedGraph<Vertex>>,
+    ) -> Result<Rc<GraphHomomorphism<Vertex>>, Errors>
+    where
+        Vertex: DynClone,
+    {
+        let category = functor.source_object();
+        let mut vertex_map = HashMap::new();
+        let mut edge_map = HashMap::new();
+        for object in category.get_all_objects()? {
+            let image = functor.map_object(object)?;
+            vertex_map.insert(object.category_id().clone(), image.category_id().clone());
+            for morphism in category.get_hom_set_x(object)? {
+                let image = functor.map_morphism(morphism)?;
+                edge_map.insert(morphism.arrow_id().clone(), image.arrow_id().clone());
+            }
+        }
+        Self::homomorphism(
+            &format!("U({})", functor.arrow_id()),
+            source,
+            target,
+            &vertex_map,
+            &edge_map,
+        )
+    }
+
+    /*
+    The functor Free(G) -> C of a homomorphism h: G -> U(C). It sends an edge to the morphism
+    h picks and a path to the composite of the morphisms along it.
+     */
+    pub fn free_transpose(
+        homomorphism: &GraphHomomorphism<Vertex>,
+        paths: &Rc<PathCategory<Vertex>>,
+        category: &Rc<BaseCategory<Vertex>>,
+    ) -> Result<Functor<PathCategory<Vertex>, BaseCategory<Vertex>>, Errors>
+    where
+        Vertex: DynClone,
+    {
+        if !Rc::ptr_eq(paths.graph(), homomorphism.source_object()) {
+            return Err(Errors::InvalidFunctor(
+                "The paths are not those of the source of the homomorphism".to_string(),
+            ));
+        }
+        let image = |morphism: &Rc<Morphism<Vertex>>| -> Result<Rc<Morphism<Vertex>>, Errors> {
+            let mapped = homomorphism.map_morphism(morphism)?;
+            match mapped.is_identity() {
+                true => Ok(category
+                    .get_identity_morphism(mapped.source_object())?
+                    .clone()),
+                false => Ok(category.morphism(mapped.arrow_id())?.clone()),
+            }
+        };
+        let mut mappings = HashMap::new();
+        for path in paths.get_all_morphisms()? {
+            let mapped = match paths.path_edges(path)?.split_first() {
+                None => image(path)?,
+                Some((first, rest)) => {
+                    let mut composite = image(first)?;
+                    for edge in rest {
+                        composite = category.compose_morphisms(&composite, &image(edge)?)?;
+                    }
+                    composite
+                }
+            };
+            mappings.insert(path.clone(), mapped);
+        }
+        Ok(Functor::new(
+            String::generate(),
+            paths.clone(),
+            category.clone(),
+            mappings,
+        ))
+    }
+}
+
+impl<Vertex: CategoryTrait + Hash + Eq + Clone> CategoryTrait for Graph<Vertex> {
+    type Object = DirectedGraph<Vertex>;
+
+    type Morphism = GraphHomomorphism<Vertex>;
+
+    fn new() -> Self {
+        Self::new_with_id(ObjectId::generate())
+    }
+
+    fn new_with_id(id: &ObjectId) -> Self {
+        Graph::new_with_id(id.clone())
+    }
+
+    fn category_id(&self) -> &ObjectId {
+        &self.id
+    }
+
+    fn update_category_id(&mut self, new_id: ObjectId) {
+        self.id = new_id;
+    }
+
+    fn add_object(&mut self, object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        let id = object.category_id().clone();
+        if self.objects.contains_key(&id) {
+            return Err(Errors::ObjectAlreadyExists);
+        }
+        let mappings = object
+            .get_all_morphisms()?
+            .into_iter()
+            .map(|morphism| (morphism.clone(), morphism.clone()))
+            .collect();
+        let identity =
+            Arrow::new_identity_with_mappings(format!("id_{}", id), object.clone(), mappings);
+        self.objects.insert(id.clone(), object);
+        self.identities.insert(id.clone(), identity.clone());
+        self.hom_sets.insert(id, Vec::new());
+        Ok(identity)
+    }
+
+    fn add_morphism(
+        &mut self,
+        morphism: Rc<Self::Morphism>,
+    ) -> Result<&Rc<Self::Morphism>, Errors> {
+        for graph in [morphism.source_object(), morphism.target_object()] {
+            match self.objects.get(graph.category_id()) {
+                Some(stored) if Rc::ptr_eq(stored, graph) => {}
+                _ => return Err(Errors::ObjectNotFound(graph.category_id().clone())),
+            }
+        }
+        let source = morphism.source_object().category_id().clone();
+        if morphism.is_identity() {
+            return self
+                .identities
+                .get(&source)
+                .ok_or(Errors::IdentityMorphismNotFound);
+        }
+        let hom_set = self.hom_sets.entry(source).or_default();
+        if hom_set
+            .iter()
+            .any(|stored| stored.arrow_id() == morphism.arrow_id())
+        {
+            return Err(Errors::MorphismAlreadyExists);
+        }
+        hom_set.push(morphism);
+        Ok(hom_set.last().expect("just pushed"))
+    }
+
+    fn get_identity_morphism(&self, object: &Self::Object) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.identities
+            .get(object.category_id())
+            .ok_or(Errors::IdentityMorphismNotFound)
+    }
+
+    fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
+        self.objects
+            .get(object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))
+    }
+
+    fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
+        Ok(self.objects.values().collect())
+    }
+
+    fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .identities
+            .values()
+            .chain(self.hom_sets.values().flatten())
+            .collect())
+    }
+
+    fn get_hom_set_x(
+        &self,
+        source_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .get_object_morphisms(source_object)?
+            .into_iter()
+            .collect())
+    }
+
+    fn get_object_morphisms(
+        &self,
+        object: &Self::Object,
+    ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
+        let homomorphisms = self
+            .hom_sets
+            .get(object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))?;
+        let mut result = vec![self.get_identity_morphism(object)?];
+        result.extend(homomorphisms.iter());
+        Ok(result)
+    }
+
+    // composes the vertex and edge maps, preferring a stored homomorphism with the same maps
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        if !Rc::ptr_eq(first.target_object(), second.source_object()) {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        if first.is_identity() {
+            return Ok(second.clone());
+        }
+        if second.is_identity() {
+            return Ok(first.clone());
+        }
+        let mappings = first
+            .arrow_mappings()
+            .iter()
+            .map(|(element, image)| Ok((element.clone(), second.map_morphism(image)?.clone())))
+            .collect::<Result<HashMap<_, _>, Errors>>()?;
+        let source = first.source_object();
+        let target = second.target_object();
+        if Rc::ptr_eq(source, target) && mappings.iter().all(|(edge, mapped)| edge == mapped) {
+            return Ok(self.get_identity_morphism(source)?.clone());
+        }
+        let stored = self.get_hom_set(source, target).ok().and_then(|hom_set| {
+            hom_set
+                .into_iter()
+                .find(|stored| stored.arrow_mappings() == &mappings)
+        });
+        if let Some(stored) = stored {
+            return Ok(stored.clone());
+        }
+        Ok(Rc::new(Arrow::new(
+            format!("{}∘{}", second.arrow_id(), first.arrow_id()),
+            source.clone(),
+            target.clone(),
+            mappings,
+        )))
+    }
+}
+
+// every functor between two finite categories, an object map with a choice of morphisms over it
+fn functors<Source, Target>(
+    source: &Rc<Source>,
+    target: &Rc<Target>,
+) -> Result<Vec<Functor<Source, Target>>, Errors>
+where
+    Source: CategoryTrait,
+    Target: CategoryTrait,
+{
+    let objects = sorted_objects(source.get_all_objects()?);
+    let images = sorted_objects(target.get_all_objects()?);
+    let mut morphisms = Vec::new();
+    for object in &objects {
+        morphisms.extend(sorted_morphisms(source.get_hom_set_x(object)?));
+    }
+    let mut result = Vec::new();
+    for choice in cartesian_product(&vec![images; objects.len()]) {
+        let object_map = objects
+            .iter()
+            .map(|object| object.category_id())
+            .zip(choice)
+            .collect::<HashMap<_, _>>();
+        let mut options = Vec::new();
+        for morphism in &morphisms {
+            let source_image = object_map[morphism.source_object().category_id()];
+            let target_image = object_map[morphism.target_object().category_id()];
+            options.push(match morphism.is_identity() {
+                true => vec![target.get_identity_morphism(source_image)?],
+                false => sorted_morphisms(target.get_hom_set(source_image, target_image)?),
+            });
+        }
+        for morphism_choice in cartesian_product(&options) {
+            let mappings = morphisms
+                .iter()
+                .map(|morphism| (*morphism).clone())
+                .zip(morphism_choice.into_iter().cloned())
+                .collect();
+            let functor =
+                Functor::new(String::generate(), source.clone(), target.clone(), mappings);
+            match functor.validate_mappings() {
+                Ok(()) => result.push(functor),
+                Err(Errors::InvalidFunctor(_)) => {}
+                Err(error) => return Err(error),
+            }
+        }
+    }
+    Ok(result)
+}
+
+/*
+Free ⊣ U on a graph G and a category C: sending a homomorphism h: G -> U(C) to its transpose
+Free(G) -> C is a bijection onto the functors Free(G) -> C, restricting to the edges undoes
+it, and it is natural in G along the endomorphisms k of G, transpose(h ∘ k) = transpose(h) ∘
+Free(k), and in C along the endofunctors K of C, transpose(U(K) ∘ h) = K ∘ transpose(h).
+Free(G) is a PathCategory, so G has no cycle.
+ */
+pub fn validate_free_forgetful_adjunction<Vertex>(
+    graph: &Rc<DirectedGraph<Vertex>>,
+    category: &Rc<BaseCategory<Vertex>>,
+) -> Result<(), Errors>
+where
+    Vertex: CategoryTrait + Hash + Eq + Clone + DynClone,
+{
+    let underlying = Rc::new(DirectedGraph::underlying(category)?);
+    let paths = Rc::new(PathCategory::from_graph(graph)?);
+    let homomorphisms = Graph::homomorphisms(graph, &underlying)?;
+    let mut transposes = Vec::new();
+    for homomorphism in &homomorphisms {
+        let transpose = Graph::free_transpose(homomorphism, &paths, category)?;
+        transpose.validate_mappings().map_err(|error| {
+            Errors::InvalidAdjunction(format!("The transpose is not a functor: {:?}", error))
+        })?;
+        for edge in graph.edges() {
+            let restricted = transpose.map_morphism(edge)?;
+            if restricted.arrow_id() != homomorphism.map_morphism(edge)?.arrow_id() {
+                return Err(Errors::InvalidAdjunction(format!(
+                    "The transpose of {} does not send {} to its image",
+                    homomorphism.arrow_id(),
+                    edge.arrow_id()
+                )));
+            }
+        }
+        transposes.push(transpose);
+    }
+
+    // the transposes restrict to different homomorphisms, so they are all the functors when
+    // there are as many and each functor is one of them
+    let all_functors = functors(&paths, category)?;
+    if all_functors.len() != transposes.len() {
+        return Err(Errors::InvalidAdjunction(format!(
+            "There are {} functors Free(G) -> C but {} homomorphisms G -> U(C)",
+            all_functors.len(),
+            transposes.len()
+        )));
+    }
+    for functor in &all_functors {
+        if !transposes
+            .iter()
+            .any(|transpose| transpose.arrow_mappings() == functor.arrow_mappings())
+        {
+            return Err(Errors::InvalidAdjunction(
+                "A functor Free(G) -> C is not the transpose of a homomorphism".to_string(),
+            ));
+        }
+    }
+
+    let all_paths = sorted_morphisms(paths.get_all_morphisms()?);
+    let not_natural = |side: &str, along: &str| {
+        Errors::InvalidAdjunction(format!(
+            "The transpose is not natural in {} along {}",
+            side, along
+        ))
+    };
+    // in G: a path p goes to the path along the images of its edges under k
+    let graphs = Graph::new();
+    for endomorphism in Graph::homomorphisms(graph, graph)? {
+        let free_image = |path: &Rc<Morphism<Vertex>>| -> Result<Rc<Morphism<Vertex>>, Errors> {
+            let edges = paths.path_edges(path)?;
+            if edges.is_empty() {
+                let vertex = Graph::vertex_image(&endomorphism, path.source_object())?;
+                return Ok(paths.get_identity_morphism(vertex)?.clone());
+            }
+            let images = edges
+                .iter()
+                .map(|edge| Ok(Graph::edge_image(&endomorphism, edge)?.clone()))
+                .collect::<Result<Vec<_>, Errors>>()?;
+            Ok(paths.path(&images)?.clone())
+        };
+        for (homomorphism, transpose) in homomorphisms.iter().zip(&transposes) {
+            let composite = graphs.compose_morphisms(&endomorphism, homomorphism)?;
+            let composite = Graph::free_transpose(&composite, &paths, category)?;
+            for path in &all_paths {
+                let image = free_image(path)?;
+                if composite.map_morphism(path)? != transpose.map_morphism(&image)? {
+                    return Err(not_natural("G", endomorphism.arrow_id()));
+                }
+            }
+        }
+    }
+    // in C: U(K) ∘ h sends a vertex and an edge to the images under K of their images under h
+    for endofunctor in functors(category, category)? {
+        for (homomorphism, transpose) in homomorphisms.iter().zip(&transposes) {
+            let mut vertex_map = HashMap::new();
+            for vertex in graph.get_all_objects()? {
+                let image = endofunctor.map_object(Graph::vertex_image(homomorphism, vertex)?)?;
+                vertex_map.insert(vertex.category_id().clone(), image.category_id().clone());
+            }
+            let mut edge_map = HashMap::new();
+            for edge in graph.edges() {
+                let image = endofunctor.map_morphism(transpose.map_morphism(edge)?)?;
+                edge_map.insert(edge.arrow_id().clone(), image.arrow_id().clone());
+            }
+            let composite =
+                Graph::homomorphism("U(K)∘h", graph, &underlying, &vertex_map, &edge_map)?;
+            let composite = Graph::free_transpose(&composite, &paths, category)?;
+            for path in &all_paths {
+                if composite.map_morphism(path)?
+                    != endofunctor.map_morphism(transpose.map_morphism(path)?)?
+                {
+                    return Err(not_natural("C", endofunctor.arrow_id()));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::shapes::{self, Shape};
+
+    fn vertex_map(pairs: &[(&str, &str)]) -> HashMap<ObjectId, ObjectId> {
+        pairs
+            .iter()
+            .map(|(vertex, image)| ((*vertex).into(), (*image).into()))
+            .collect()
+    }
+
+    fn edge_map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(edge, image)| (edge.to_string(), image.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_quivers_and_homomorphisms() {
+        let path = Rc::new(
+            DirectedGraph::from_quiver("path", "f: a -> b\ng: b -> c\n\nisolated").unwrap(),
+        );
+        assert_eq!(path.get_all_objects().unwrap().len(), 4);
+        assert_eq!(path.edges().len(), 2);
+        let f = path.edge("f").unwrap().clone();
+        let g = path.edge("g").unwrap().clone();
+        // f then g is no edge of the graph, but a path of Free(path)
+        assert!(path.compose_morphisms(&f, &g).is_err());
+        let free = PathCategory::from_graph(&path).unwrap();
+        let path_fg = free.compose_morphisms(&f, &g).unwrap();
+        assert_eq!(path_fg.arrow_id(), "g∘f");
+        assert_eq!(path_fg.target_object(), g.target_object());
+        assert_eq!(
+            free.path_edges(&path_fg).unwrap(),
+            &vec![f.clone(), g.clone()]
+        );
+        assert!(
+            free.get_hom_set_x(f.source_object())
+                .unwrap()
+                .contains(&path_fg)
+        );
+        assert_eq!(free.get_all_morphisms().unwrap().len(), 7);
+
+        // a loop with two parallel edges, whose paths never end
+        let cycle = Rc::new(DirectedGraph::from_quiver("cycle", "s: x -> x\nt: x -> x").unwrap());
+        assert!(PathCategory::from_graph(&cycle).is_err());
+        let mut graphs = Graph::new();
+        graphs.add_object(path.clone()).unwrap();
+        graphs.add_object(cycle.clone()).unwrap();
+        let collapse = graphs
+            .add_homomorphism(
+                "collapse",
+                &path,
+                &cycle,
+                &vertex_map(&[("a", "x"), ("b", "x"), ("c", "x"), ("isolated", "x")]),
+                &edge_map(&[("f", "s"), ("g", "t")]),
+            )
+            .unwrap();
+        let swap = graphs
+            .add_homomorphism(
+                "swap",
+                &cycle,
+                &cycle,
+                &vertex_map(&[("x", "x")]),
+                &edge_map(&[("s", "t"), ("t", "s")]),
+            )
+            .unwrap();
+        assert_eq!(Graph::edge_image(&collapse, &g).unwrap().arrow_id(), "t");
+        let swapped = graphs.compose_morphisms(&collapse, &swap).unwrap();
+        assert_eq!(Graph::edge_image(&swapped, &f).unwrap().arrow_id(), "t");
+        assert!(
+            graphs
+                .compose_morphisms(&swap, &swap)
+                .unwrap()
+                .is_identity()
+        );
+
+        // a homomorphism must preserve endpoints
+        let reversed = Rc::new(DirectedGraph::from_quiver("reversed", "f: b -> a").unwrap());
+        let two = Rc::new(DirectedGraph::from_quiver("two", "e: p -> q").unwrap());
+        assert!(
+            Graph::homomorphism(
+                "bad",
+                &reversed,
+                &two,
+                &vertex_map(&[("a", "p"), ("b", "q")]),
+                &edge_map(&[("f", "e")]),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_paths_of_a_diamond() {
+        let diamond = Rc::new(
+            DirectedGraph::from_quiver("diamond", "f: a -> b\ng: b -> d\nh: a -> c\nk: c -> d")
+                .unwrap(),
+        );
+        let free = PathCategory::from_graph(&diamond).unwrap();
+        let a = diamond.vertex(&"a".into()).unwrap();
+        let d = diamond.vertex(&"d".into()).unwrap();
+        // g∘f and k∘h are different paths from a to d
+        let paths = free.get_hom_set(a, d).unwrap();
+        let mut ids = paths
+            .iter()
+            .map(|path| path.arrow_id().clone())
+            .collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(ids, vec!["g∘f", "k∘h"]);
+        // the composites are in the hom-sets, so the searches over a category work on it
+        let f = diamond.edge("f").unwrap();
+        let g = diamond.edge("g").unwrap();
+        assert!(paths.contains(&free.compose_morphisms(f, g).unwrap()));
+        assert!(free.is_mono(f).unwrap());
+        assert_eq!(
+            free.compose_morphisms(free.get_identity_morphism(a).unwrap(), f)
+                .unwrap(),
+            *f
+        );
+    }
+
+    #[test]
+    fn test_forgetful_functor() {
+        let pair = Rc::new(shapes::parallel_pair());
+        let arrow = Rc::new(shapes::walking_arrow());
+        let pair_graph = Rc::new(DirectedGraph::underlying(&pair).unwrap());
+        let arrow_graph = Rc::new(DirectedGraph::underlying(&arrow).unwrap());
+        // f, g and the two identities, all of them plain edges
+        assert_eq!(pair_graph.edges().len(), 4);
+        assert!(pair_graph.edges().iter().all(|edge| !edge.is_identity()));
+
+        // F: f, g ↦ 0≤1 gives U(F), which keeps identities as edges
+        let object = |category: &Rc<Shape>, id: ObjectId| {
+            category
+                .get_object(&DiscreteCategory::new_with_id(id))
+                .unwrap()
+                .clone()
+        };
+        let identity = |category: &Rc<Shape>, id: ObjectId| {
+            let object = object(category, id);
+            category.get_identity_morphism(&object).unwrap().clone()
+        };
+        let arrow_morphism = arrow.morphism("0≤1").unwrap();
+        let functor = Functor::new(
+            "F".to_string(),
+            pair.clone(),
+            arrow.clone(),
+            HashMap::from([
+                (
+                    identity(&pair, "source".into()),
+                    identity(&arrow, ObjectId::Int(0)),
+                ),
+                (
+                    identity(&pair, "target".into()),
+                    identity(&arrow, ObjectId::Int(1)),
+                ),
+                (pair.morphism("f").unwrap().clone(), arrow_morphism.clone()),
+                (pair.morphism("g").unwrap().clone(), arrow_morphism.clone()),
+            ]),
+        );
+        functor.validate_mappings().unwrap();
+        let homomorphism =
+            Graph::underlying_homomorphism(&functor, &pair_graph, &arrow_graph).unwrap();
+        assert_eq!(
+            Graph::edge_image(&homomorphism, pair_graph.edge("g").unwrap()).unwrap(),
+            arrow_morphism
+        );
+        let source_identity = identity(&pair, "source".into());
+        let identity_edge = pair_graph.edge(source_identity.arrow_id()).unwrap();
+        let mapped = Graph::edge_image(&homomorphism, identity_edge).unwrap();
+        assert!(!mapped.is_identity());
+        assert_eq!(
+            mapped.arrow_id(),
+            identity(&arrow, ObjectId::Int(0)).arrow_id()
+        );
+    }
+
+    #[test]
+    fn test_free_forgetful_adjunction() {
+        // a path a -> b -> c lands in [2] along x ≤ y ≤ z
+        let path = Rc::new(DirectedGraph::from_quiver("path", "f: a -> b\ng: b -> c").unwrap());
+        let chain = Rc::new(shapes::ordinal(2));
+        let underlying = Rc::new(DirectedGraph::underlying(&chain).unwrap());
+        assert_eq!(Graph::homomorphisms(&path, &underlying).unwrap().len(), 10);
+        // as many as the functors from its paths, and the monotone maps of [2] to itself
+        let free = Rc::new(PathCategory::from_graph(&path).unwrap());
+        assert_eq!(functors(&free, &chain).unwrap().len(), 10);
+        assert_eq!(functors(&chain, &chain).unwrap().len(), 10);
+        validate_free_forgetful_adjunction(&path, &chain).unwrap();
+
+        // an edge lands on an identity, or on either of the parallel arrows
+        let edge = Rc::new(DirectedGraph::from_quiver("edge", "e: x -> y").unwrap());
+        let pair = Rc::new(shapes::parallel_pair());
+        let underlying = Rc::new(DirectedGraph::underlying(&pair).unwrap());
+        assert_eq!(Graph::homomorphisms(&edge, &underlying).unwrap().len(), 4);
+        validate_free_forgetful_adjunction(&edge, &pair).unwrap();
+
+        // the transpose needs the composites of the category
+        let mut unknown: BaseCategory<DiscreteCategory> = vec!["A", "B", "C"].into();
+        for (id, source, target) in [("u", "A", "B"), ("v", "B", "C"), ("w", "A", "C")] {
+            let endpoint = |id: &str| {
+                unknown
+                    .get_object(&DiscreteCategory::new_with_id(id.into()))
+                    .unwrap()
+                    .clone()
+            };
+            let morphism = Arrow::new(
+                id.to_string(),
+                endpoint(source),
+                endpoint(target),
+                HashMap::new(),
+            );
+            unknown.add_morphism(Rc::new(morphism)).unwrap();
+        }
+        assert!(validate_free_forgetful_adjunction(&path, &Rc::new(unknown)).is_err());
+
+        // the paths around a cycle never end
+        let cycle = Rc::new(DirectedGraph::from_quiver("cycle", "s: x -> x").unwrap());
+        assert!(validate_free_forgetful_adjunction(&cycle, &chain).is_err());
+    }
+}
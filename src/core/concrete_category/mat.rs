@@ -0,0 +1,887 @@
+/*
+Mat_k, the category of matrices over the integers or a prime field GF(p).
+
+The object n is the basis {0, .., n-1} of k^n, kept as a DiscreteCategory with id n,
+and a morphism n -> m is an m × n matrix, so composition is matrix multiplication.
+All arithmetic is exact: integers fail on overflow and GF(p) entries are reduced mod p.
+Matrices are compared by their entries, like relations in Rel.
+
+n ⊕ m is a biproduct, with block matrices as injections and projections,
+and the Kronecker product makes Mat_k a strict monoidal category with unit 1,
+which is how string diagrams get their linear-algebra semantics.
+It is symmetric, crossing two wires is the commutation matrix e_i ⊗ e_j -> e_j ⊗ e_i.
+ */
+use crate::core::concrete_category::fin_set::{FinSet, Function};
+use crate::core::discrete_category::DiscreteCategory;
+use crate::core::errors::Errors;
+use crate::core::identifier::Identifier;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::braided_monoidal_category_trait::{
+    BraidedMonoidalCategoryTrait, SymmetricMonoidalCategoryTrait,
+};
+use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::traits::finite_limits_trait::{Cocone, Cone};
+use crate::core::traits::monoidal_category_trait::MonoidalCategoryTrait;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scalars {
+    Integers,
+    PrimeField(Prime),
+}
+
+// a prime, only built by Scalars::prime_field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Prime(i64);
+
+impl Prime {
+    pub fn value(&self) -> i64 {
+        self.0
+    }
+}
+
+impl Scalars {
+    // primes are bounded by i32::MAX, which keeps the trial division short
+    pub fn prime_field(prime: i64) -> Result<Self, Errors> {
+        if prime > i32::MAX as i64 {
+            return Err(Errors::InvalidOperation(format!(
+                "{} is larger than the largest supported prime field",
+                prime
+            )));
+        }
+        let is_prime = prime > 1
+            && (2..)
+                .take_while(|d| *d <= prime / d)
+                .all(|d| prime % d != 0);
+        if !is_prime {
+            return Err(Errors::InvalidOperation(format!(
+                "{} is not a prime",
+                prime
+            )));
+        }
+        Ok(Scalars::PrimeField(Prime(prime)))
+    }
+
+    pub fn normalize(&self, value: i64) -> i64 {
+        match self {
+            Scalars::Integers => value,
+            Scalars::PrimeField(Prime(prime)) => value.rem_euclid(*prime),
+        }
+    }
+
+    pub fn add(&self, left: i64, right: i64) -> Result<i64, Errors> {
+        match self {
+            Scalars::Integers => left.checked_add(right).ok_or_else(Self::overflow),
+            Scalars::PrimeField(Prime(prime)) => {
+                Ok((left as i128 + right as i128).rem_euclid(*prime as i128) as i64)
+            }
+        }
+    }
+
+    pub fn multiply(&self, left: i64, right: i64) -> Result<i64, Errors> {
+        match self {
+            Scalars::Integers => left.checked_mul(right).ok_or_else(Self::overflow),
+            Scalars::PrimeField(Prime(prime)) => {
+                Ok((left as i128 * right as i128).rem_euclid(*prime as i128) as i64)
+            }
+        }
+    }
+
+    // the multiplicative inverse, ±1 for the integers and a^(p-2) in GF(p)
+    pub fn inverse(&self, value: i64) -> Option<i64> {
+        match self {
+            Scalars::Integers => (value == 1 || value == -1).then_some(value),
+            Scalars::PrimeField(Prime(prime)) => {
+                let value = value.rem_euclid(*prime);
+                if value == 0 {
+                    return None;
+                }
+                let (mut result, mut base, mut exponent) = (1i128, value as i128, prime - 2);
+                while exponent > 0 {
+                    if exponent & 1 == 1 {
+                        result = result * base % *prime as i128;
+                    }
+                    base = base * base % *prime as i128;
+                    exponent >>= 1;
+                }
+                Some(result as i64)
+            }
+        }
+    }
+
+    fn overflow() -> Errors {
+        Errors::InvalidOperation("Integer overflow in matrix arithmetic".to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct Matrix {
+    id: String,
+    source: Rc<DiscreteCategory>,
+    target: Rc<DiscreteCategory>,
+    scalars: Scalars,
+    // one row per basis element of the target
+    entries: Vec<Vec<i64>>,
+    // the function on bases it comes from, empty unless built from one
+    mappings: HashMap<Rc<Function>, Rc<Function>>,
+}
+
+impl PartialEq for Matrix {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.source, &other.source)
+            && Rc::ptr_eq(&self.target, &other.target)
+            && self.scalars == other.scalars
+            && self.entries == other.entries
+    }
+}
+
+impl Eq for Matrix {}
+
+impl Hash for Matrix {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Rc::as_ptr(&self.source).hash(state);
+        Rc::as_ptr(&self.target).hash(state);
+        self.scalars.hash(state);
+        self.entries.hash(state);
+    }
+}
+
+impl Matrix {
+    pub fn from_rows(
+        id: String,
+        scalars: Scalars,
+        source: Rc<DiscreteCategory>,
+        target: Rc<DiscreteCategory>,
+        rows: Vec<Vec<i64>>,
+    ) -> Result<Self, Errors> {
+        let columns = Mat::dimension_of(&source);
+        if rows.len() != Mat::dimension_of(&target) || rows.iter().any(|row| row.len() != columns) {
+            return Err(Errors::InvalidOperation(format!(
+                "Matrix {} is not {} × {}",
+                id,
+                Mat::dimension_of(&target),
+                columns
+            )));
+        }
+        let entries = rows
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|entry| scalars.normalize(entry))
+                    .collect()
+            })
+            .collect();
+        Ok(Matrix {
+            id,
+            source,
+            target,
+            scalars,
+            entries,
+            mappings: HashMap::new(),
+        })
+    }
+
+    pub fn scalars(&self) -> Scalars {
+        self.scalars
+    }
+
+    pub fn rows(&self) -> &Vec<Vec<i64>> {
+        &self.entries
+    }
+
+    pub fn entry(&self, row: usize, column: usize) -> Option<i64> {
+        self.entries.get(row)?.get(column).copied()
+    }
+}
+
+impl ArrowTrait<DiscreteCategory, DiscreteCategory> for Matrix {
+    fn source_object(&self) -> &Rc<DiscreteCategory> {
+        &self.source
+    }
+
+    fn target_object(&self) -> &Rc<DiscreteCategory> {
+        &self.target
+    }
+
+    fn new_instance(
+        source: Rc<DiscreteCategory>,
+        target: Rc<DiscreteCategory>,
+        id: &str,
+        mappings: HashMap<Rc<Function>, Rc<Function>>,
+    ) -> Self {
+        Self::new(id.to_string(), source, target, mappings)
+    }
+
+    // the 0/1 integer matrix sending each basis vector to the one it is mapped to
+    fn new(
+        id: String,
+        source: Rc<DiscreteCategory>,
+        target: Rc<DiscreteCategory>,
+        mappings: HashMap<Rc<Function>, Rc<Function>>,
+    ) -> Self {
+        let mut entries = vec![vec![0; Mat::dimension_of(&source)]; Mat::dimension_of(&target)];
+        for (element, image) in &mappings {
+            let column = Mat::basis_index(element.source_object().category_id());
+            let row = Mat::basis_index(image.source_object().category_id());
+            if let Some(entry) = row
+                .zip(column)
+                .and_then(|(row, column)| entries.get_mut(row)?.get_mut(column))
+            {
+                *entry = 1;
+            }
+        }
+        Matrix {
+            id,
+            source,
+            target,
+            scalars: Scalars::Integers,
+            entries,
+            mappings,
+        }
+    }
+
+    fn is_identity(&self) -> bool {
+        Rc::ptr_eq(&self.source, &self.target)
+            && self.entries.iter().enumerate().all(|(row, entries)| {
+                entries
+                    .iter()
+                    .enumerate()
+                    .all(|(column, entry)| *entry == (row == column) as i64)
+            })
+    }
+
+    fn arrow_id(&self) -> &String {
+        &self.id
+    }
+
+    fn compose(
+        &self,
+        _other: &impl ArrowTrait<DiscreteCategory, DiscreteCategory>,
+    ) -> Result<Rc<impl ArrowTrait<DiscreteCategory, DiscreteCategory>>, Errors> {
+        Err::<Rc<Matrix>, _>(Errors::InvalidOperation(
+            "Matrices are composed by Mat::compose_morphisms".to_string(),
+        ))
+    }
+
+    fn arrows(&self) -> Vec<&impl ArrowTrait<DiscreteCategory, DiscreteCategory>> {
+        vec![self]
+    }
+
+    fn arrow_mappings(&self) -> &HashMap<Rc<Function>, Rc<Function>> {
+        &self.mappings
+    }
+
+    fn validate_mappings(&self) -> Result<(), Errors> {
+        let columns = Mat::dimension_of(&self.source);
+        if self.entries.len() != Mat::dimension_of(&self.target)
+            || self.entries.iter().any(|row| row.len() != columns)
+        {
+            return Err(Errors::InvalidOperation(format!(
+                "Matrix {} does not match its dimensions",
+                self.id
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Mat {
+    id: ObjectId,
+    scalars: Scalars,
+    objects: HashMap<ObjectId, Rc<DiscreteCategory>>,
+    identities: HashMap<ObjectId, Rc<Matrix>>,
+    // non-identity matrices out of each dimension
+    hom_sets: HashMap<ObjectId, Vec<Rc<Matrix>>>,
+}
+
+impl Default for Mat {
+    fn default() -> Self {
+        Self::new(Scalars::Integers)
+    }
+}
+
+impl PartialEq for Mat {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Mat {}
+
+impl Hash for Mat {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl Mat {
+    pub fn new(scalars: Scalars) -> Self {
+        Self::new_with_id(ObjectId::generate(), scalars)
+    }
+
+    pub fn new_with_id(id: ObjectId, scalars: Scalars) -> Self {
+        Mat {
+            id,
+            scalars,
+            objects: HashMap::new(),
+            identities: HashMap::new(),
+            hom_sets: HashMap::new(),
+        }
+    }
+
+    // the category with the dimensions 0..=max_dimension
+    pub fn up_to(max_dimension: usize, scalars: Scalars) -> Result<Self, Errors> {
+        let mut mat = Self::new(scalars);
+        for dimension in 0..=max_dimension {
+            mat.add_dimension(dimension)?;
+        }
+        Ok(mat)
+    }
+
+    pub fn scalars(&self) -> Scalars {
+        self.scalars
+    }
+
+    // adds the object n, returning the existing one when it is already there
+    pub fn add_dimension(&mut self, dimension: usize) -> Result<Rc<DiscreteCategory>, Errors> {
+        if let Ok(object) = self.dimension(dimension) {
+            return Ok(object.clone());
+        }
+        let basis = (0..dimension as i32).collect::<Vec<_>>();
+        let object = Rc::new(FinSet::new_set(ObjectId::Int(dimension as i32), basis)?);
+        self.add_object(object.clone())?;
+        Ok(object)
+    }
+
+    pub fn dimension(&self, dimension: usize) -> Result<&Rc<DiscreteCategory>, Errors> {
+        let id = ObjectId::Int(dimension as i32);
+        self.objects.get(&id).ok_or(Errors::ObjectNotFound(id))
+    }
+
+    pub fn dimension_of(object: &DiscreteCategory) -> usize {
+        object
+            .get_all_objects()
+            .map(|basis| basis.len())
+            .unwrap_or_default()
+    }
+
+    pub fn matrix(
+        &self,
+        id: &str,
+        source: usize,
+        target: usize,
+        rows: Vec<Vec<i64>>,
+    ) -> Result<Rc<Matrix>, Errors> {
+        Ok(Rc::new(Matrix::from_rows(
+            id.to_string(),
+            self.scalars,
+            self.dimension(source)?.clone(),
+            self.dimension(target)?.clone(),
+            rows,
+        )?))
+    }
+
+    pub fn add_matrix(
+        &mut self,
+        id: &str,
+        source: usize,
+        target: usize,
+        rows: Vec<Vec<i64>>,
+    ) -> Result<Rc<Matrix>, Errors> {
+        let matrix = self.matrix(id, source, target, rows)?;
+        Ok(self.add_morphism(matrix)?.clone())
+    }
+
+    // f ⊕ g, the block diagonal matrix n + n' -> m + m'
+    pub fn direct_sum(&self, left: &Matrix, right: &Matrix) -> Result<Rc<Matrix>, Errors> {
+        let (columns, rows) = (
+            Self::dimension_of(&left.source),
+            Self::dimension_of(&left.target),
+        );
+        let source = self.dimension(columns + Self::dimension_of(&right.source))?;
+        let target = self.dimension(rows + Self::dimension_of(&right.target))?;
+        let entries = (0..Self::dimension_of(target))
+            .map(|row| {
+                (0..Self::dimension_of(source))
+                    .map(|column| match (row < rows, column < columns) {
+                        (true, true) => left.entries[row][column],
+                        (false, false) => right.entries[row - rows][column - columns],
+                        _ => 0,
+                    })
+                    .collect()
+            })
+            .collect();
+        self.stored_or_new(Matrix::from_rows(
+            format!("{}⊕{}", left.id, right.id),
+            self.scalars,
+            source.clone(),
+            target.clone(),
+            entries,
+        )?)
+    }
+
+    // n ⊕ m with its projections as a cone and its injections as a cocone
+    pub fn biproduct(
+        &self,
+        left: usize,
+        right: usize,
+    ) -> Result<(Cone<Self>, Cocone<Self>), Errors> {
+        let sum = self.dimension(left + right)?;
+        let projection = |id: String, offset: usize, size: usize| {
+            let entries = (0..size)
+                .map(|row| {
+                    (0..left + right)
+                        .map(|column| (column == row + offset) as i64)
+                        .collect()
+                })
+                .collect();
+            let matrix = Matrix::from_rows(
+                id,
+                self.scalars,
+                sum.clone(),
+                self.dimension(size)?.clone(),
+                entries,
+            )?;
+            self.stored_or_new(matrix)
+        };
+        let injection = |id: String, offset: usize, size: usize| {
+            let entries = (0..left + right)
+                .map(|row| {
+                    (0..size)
+                        .map(|column| (row == column + offset) as i64)
+                        .collect()
+                })
+                .collect();
+            let matrix = Matrix::from_rows(
+                id,
+                self.scalars,
+                self.dimension(size)?.clone(),
+                sum.clone(),
+                entries,
+            )?;
+            self.stored_or_new(matrix)
+        };
+        let projections = vec![
+            projection(format!("p1({},{})", left, right), 0, left)?,
+            projection(format!("p2({},{})", left, right), left, right)?,
+        ];
+        let injections = vec![
+            injection(format!("i1({},{})", left, right), 0, left)?,
+            injection(format!("i2({},{})", left, right), left, right)?,
+        ];
+        Ok((
+            Cone::new(sum.clone(), projections),
+            Cocone::new(sum.clone(), injections),
+        ))
+    }
+
+    // the Kronecker product f ⊗ g: n·n' -> m·m', with (f ⊗ g)[i·m'+k][j·n'+l] = f[i][j]·g[k][l]
+    pub fn kronecker(&self, left: &Matrix, right: &Matrix) -> Result<Rc<Matrix>, Errors> {
+        let (right_columns, right_rows) = (
+            Self::dimension_of(&right.source),
+            Self::dimension_of(&right.target),
+        );
+        let source = self.dimension(Self::dimension_of(&left.source) * right_columns)?;
+        let target = self.dimension(Self::dimension_of(&left.target) * right_rows)?;
+        let entries = (0..Self::dimension_of(target))
+            .map(|row| {
+                (0..Self::dimension_of(source))
+                    .map(|column| {
+                        self.scalars.multiply(
+                            left.entries[row / right_rows][column / right_columns],
+                            right.entries[row % right_rows][column % right_columns],
+                        )
+                    })
+                    .collect::<Result<Vec<_>, Errors>>()
+            })
+            .collect::<Result<Vec<_>, Errors>>()?;
+        self.stored_or_new(Matrix::from_rows(
+            format!("{}⊗{}", left.id, right.id),
+            self.scalars,
+            source.clone(),
+            target.clone(),
+            entries,
+        )?)
+    }
+
+    // the commutation matrix n·m -> m·n sending e_i ⊗ e_j to e_j ⊗ e_i
+    pub fn swap(&self, left: usize, right: usize) -> Result<Rc<Matrix>, Errors> {
+        let object = self.dimension(left * right)?;
+        let mut entries = vec![vec![0; left * right]; left * right];
+        for i in 0..left {
+            for j in 0..right {
+                entries[j * left + i][i * right + j] = 1;
+            }
+        }
+        self.stored_or_new(Matrix::from_rows(
+            format!("σ_{},{}", left, right),
+            self.scalars,
+            object.clone(),
+            object.clone(),
+            entries,
+        )?)
+    }
+
+    fn identity_matrix(object: &Rc<DiscreteCategory>, scalars: Scalars) -> Rc<Matrix> {
+        let dimension = Self::dimension_of(object);
+        let entries = (0..dimension)
+            .map(|row| {
+                (0..dimension)
+                    .map(|column| (row == column) as i64)
+                    .collect()
+            })
+            .collect();
+        let mappings = FinSet::identity_function(object).arrow_mappings().clone();
+        Rc::new(Matrix {
+            id: format!("1_{}", dimension),
+            source: object.clone(),
+            target: object.clone(),
+            scalars,
+            entries,
+            mappings,
+        })
+    }
+
+    fn basis_index(element: &ObjectId) -> Option<usize> {
+        match element {
+            ObjectId::Int(index) => usize::try_from(*index).ok(),
+            _ => None,
+        }
+    }
+
+    // the stored matrix equal to the given one, if there is any, so results compare by pointer too
+    fn stored_or_new(&self, matrix: Matrix) -> Result<Rc<Matrix>, Errors> {
+        if matrix.is_identity() {
+            return Ok(self.get_identity_morphism(&matrix.source)?.clone());
+        }
+        let stored = self
+            .hom_sets
+            .get(matrix.source.category_id())
+            .and_then(|hom_set| hom_set.iter().find(|stored| ***stored == matrix));
+        Ok(stored.cloned().unwrap_or_else(|| Rc::new(matrix)))
+    }
+}
+
+impl CategoryTrait for Mat {
+    type Object = DiscreteCategory;
+
+    type Morphism = Matrix;
+
+    fn new() -> Self {
+        Mat::default()
+    }
+
+    fn new_with_id(id: &ObjectId) -> Self {
+        Mat::new_with_id(id.clone(), Scalars::Integers)
+    }
+
+    fn category_id(&self) -> &ObjectId {
+        &self.id
+    }
+
+    fn update_category_id(&mut self, new_id: ObjectId) {
+        self.id = new_id;
+    }
+
+    fn add_object(&mut self, object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        if self.objects.contains_key(object.category_id()) {
+            return Err(Errors::ObjectAlreadyExists);
+        }
+        if object.category_id() != &ObjectId::Int(Self::dimension_of(&object) as i32) {
+            return Err(Errors::InvalidObjectId);
+        }
+        let identity = Self::identity_matrix(&object, self.scalars);
+        self.objects
+            .insert(object.category_id().clone(), object.clone());
+        self.identities
+            .insert(object.category_id().clone(), identity.clone());
+        self.hom_sets
+            .insert(object.category_id().clone(), Vec::new());
+        Ok(identity)
+    }
+
+    // a matrix equal to one already in the category is not added twice
+    fn add_morphism(
+        &mut self,
+        morphism: Rc<Self::Morphism>,
+    ) -> Result<&Rc<Self::Morphism>, Errors> {
+        for object in [morphism.source_object(), morphism.target_object()] {
+            match self.objects.get(object.category_id()) {
+                Some(stored) if Rc::ptr_eq(stored, object) => {}
+                _ => return Err(Errors::ObjectNotFound(object.category_id().clone())),
+            }
+        }
+        if morphism.scalars != self.scalars {
+            return Err(Errors::InvalidOperation(format!(
+                "Matrix {} has entries in {:?}",
+                morphism.id, morphism.scalars
+            )));
+        }
+        morphism.validate_mappings()?;
+        let source = morphism.source_object().category_id().clone();
+        if morphism.is_identity() {
+            return self
+                .identities
+                .get(&source)
+                .ok_or(Errors::IdentityMorphismNotFound);
+        }
+        let hom_set = self.hom_sets.entry(source).or_default();
+        let index = match hom_set.iter().position(|matrix| matrix == &morphism) {
+            Some(index) => index,
+            None => {
+                hom_set.push(morphism);
+                hom_set.len() - 1
+            }
+        };
+        Ok(&hom_set[index])
+    }
+
+    fn get_identity_morphism(&self, object: &Self::Object) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.identities
+            .get(object.category_id())
+            .ok_or(Errors::IdentityMorphismNotFound)
+    }
+
+    fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
+        self.objects
+            .get(object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))
+    }
+
+    fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
+        Ok(self.objects.values().collect())
+    }
+
+    fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .identities
+            .values()
+            .chain(self.hom_sets.values().flatten())
+            .collect())
+    }
+
+    fn get_hom_set_x(
+        &self,
+        source_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .get_object_morphisms(source_object)?
+            .into_iter()
+            .collect())
+    }
+
+    fn get_object_morphisms(
+        &self,
+        object: &Self::Object,
+    ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
+        let matrices = self
+            .hom_sets
+            .get(object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))?;
+        let mut result = vec![self.get_identity_morphism(object)?];
+        result.extend(matrices.iter());
+        Ok(result)
+    }
+
+    // second ∘ first is the matrix product second · first
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        if !Rc::ptr_eq(first.target_object(), second.source_object()) {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        let entries = second
+            .entries
+            .iter()
+            .map(|row| {
+                (0..Self::dimension_of(&first.source))
+                    .map(|column| {
+                        row.iter()
+                            .zip(&first.entries)
+                            .try_fold(0, |sum, (entry, first_row)| {
+                                self.scalars
+                                    .add(sum, self.scalars.multiply(*entry, first_row[column])?)
+                            })
+                    })
+                    .collect::<Result<Vec<_>, Errors>>()
+            })
+            .collect::<Result<Vec<_>, Errors>>()?;
+        self.stored_or_new(Matrix::from_rows(
+            format!("{}·{}", second.id, first.id),
+            self.scalars,
+            first.source.clone(),
+            second.target.clone(),
+            entries,
+        )?)
+    }
+}
+
+// strict: (n·m)·l and n·(m·l) are the same object, so the structure maps are identities
+impl MonoidalCategoryTrait for Mat {
+    fn tensor_objects(
+        &self,
+        left: &Rc<Self::Object>,
+        right: &Rc<Self::Object>,
+    ) -> Result<Rc<Self::Object>, Errors> {
+        Ok(self
+            .dimension(Self::dimension_of(left) * Self::dimension_of(right))?
+            .clone())
+    }
+
+    fn tensor_morphisms(
+        &self,
+        left: &Rc<Self::Morphism>,
+        right: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        self.kronecker(left, right)
+    }
+
+    fn unit_object(&self) -> Result<Rc<Self::Object>, Errors> {
+        Ok(self.dimension(1)?.clone())
+    }
+
+    fn associator(
+        &self,
+        first: &Rc<Self::Object>,
+        second: &Rc<Self::Object>,
+        third: &Rc<Self::Object>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        let tensor = self.tensor_objects(&self.tensor_objects(first, second)?, third)?;
+        Ok(self.get_identity_morphism(&tensor)?.clone())
+    }
+
+    fn left_unitor(&self, object: &Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        Ok(self.get_identity_morphism(object)?.clone())
+    }
+
+    fn right_unitor(&self, object: &Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        Ok(self.get_identity_morphism(object)?.clone())
+    }
+}
+
+// crossing wires permutes the basis, and crossing back undoes it
+impl BraidedMonoidalCategoryTrait for Mat {
+    fn braiding(
+        &self,
+        left: &Rc<Self::Object>,
+        right: &Rc<Self::Object>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        self.swap(Self::dimension_of(left), Self::dimension_of(right))
+    }
+}
+
+impl SymmetricMonoidalCategoryTrait for Mat {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::string_diagram::{DiagramBox, MorphismTerm, StringDiagram};
+
+    #[test]
+    fn test_composition_and_biproduct() {
+        let mut integers = Mat::up_to(5, Scalars::Integers).unwrap();
+        let f = integers
+            .add_matrix("f", 2, 3, vec![vec![1, 2], vec![0, -1], vec![3, 0]])
+            .unwrap();
+        let g = integers.add_matrix("g", 3, 1, vec![vec![1, 1, 1]]).unwrap();
+        let gf = integers.compose_morphisms(&f, &g).unwrap();
+        assert_eq!(gf.rows(), &vec![vec![4, 1]]);
+        let identity = integers
+            .get_identity_morphism(integers.dimension(2).unwrap())
+            .unwrap();
+        assert_eq!(&integers.compose_morphisms(identity, &f).unwrap(), &f);
+
+        // the same matrices over GF(3) are reduced mod 3
+        let mut gf3 = Mat::up_to(4, Scalars::prime_field(3).unwrap()).unwrap();
+        let f3 = gf3
+            .add_matrix("f", 2, 3, vec![vec![1, 2], vec![0, -1], vec![3, 0]])
+            .unwrap();
+        let g3 = gf3.add_matrix("g", 3, 1, vec![vec![1, 1, 1]]).unwrap();
+        assert_eq!(f3.entry(1, 1), Some(2));
+        assert_eq!(
+            gf3.compose_morphisms(&f3, &g3).unwrap().rows(),
+            &vec![vec![1, 1]]
+        );
+        assert_eq!(Scalars::prime_field(7).unwrap().inverse(3), Some(5));
+        assert!(Scalars::prime_field(4).is_err());
+        assert!(Scalars::prime_field(2_147_483_647).is_ok());
+        assert!(Scalars::prime_field(i64::MAX).is_err());
+
+        // p_i ∘ ι_j is the identity for i = j and zero otherwise, and ι_1 p_1 + ι_2 p_2 = 1
+        let (projections, injections) = integers.biproduct(1, 2).unwrap();
+        assert_eq!(projections.apex(), integers.dimension(3).unwrap());
+        let first = integers
+            .compose_morphisms(&injections.legs()[0], &projections.legs()[0])
+            .unwrap();
+        assert!(first.is_identity());
+        let second = integers
+            .compose_morphisms(&injections.legs()[1], &projections.legs()[1])
+            .unwrap();
+        assert!(second.is_identity());
+        let mixed = integers
+            .compose_morphisms(&injections.legs()[0], &projections.legs()[1])
+            .unwrap();
+        assert_eq!(mixed.rows(), &vec![vec![0], vec![0]]);
+
+        let sum = integers.direct_sum(&g, identity).unwrap();
+        assert_eq!(sum.source_object(), integers.dimension(5).unwrap());
+        assert_eq!(
+            sum.rows(),
+            &vec![
+                vec![1, 1, 1, 0, 0],
+                vec![0, 0, 0, 1, 0],
+                vec![0, 0, 0, 0, 1]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_kronecker_tensor_evaluates_string_diagrams() {
+        let mat = Mat::up_to(4, Scalars::Integers).unwrap();
+        let f = mat.matrix("f", 2, 2, vec![vec![0, 1], vec![1, 0]]).unwrap();
+        let g = mat.matrix("g", 2, 2, vec![vec![1, 1], vec![0, 1]]).unwrap();
+        let h = mat.matrix("h", 2, 1, vec![vec![1, 2]]).unwrap();
+
+        let tensor = mat.tensor_morphisms(&f, &g).unwrap();
+        assert_eq!(
+            tensor.rows(),
+            &vec![
+                vec![0, 0, 1, 1],
+                vec![0, 0, 0, 1],
+                vec![1, 1, 0, 0],
+                vec![0, 1, 0, 0],
+            ]
+        );
+        // interchange: (f ⊗ g) then (h ⊗ h) is (h∘f) ⊗ (h∘g)
+        let sequential = mat
+            .compose_morphisms(&tensor, &mat.tensor_morphisms(&h, &h).unwrap())
+            .unwrap();
+        let parallel = mat
+            .tensor_morphisms(
+                &mat.compose_morphisms(&f, &h).unwrap(),
+                &mat.compose_morphisms(&g, &h).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(sequential, parallel);
+
+        let diagram = StringDiagram::from_term(&MorphismTerm::Sequential(vec![
+            MorphismTerm::Parallel(vec![
+                MorphismTerm::Box(Rc::new(DiagramBox::new(f.clone()))),
+                MorphismTerm::Box(Rc::new(DiagramBox::new(g.clone()))),
+            ]),
+            MorphismTerm::Parallel(vec![
+                MorphismTerm::Box(Rc::new(DiagramBox::new(h.clone()))),
+                MorphismTerm::Box(Rc::new(DiagramBox::new(h.clone()))),
+            ]),
+        ]))
+        .unwrap();
+        assert_eq!(*diagram.to_morphism(&mat).unwrap(), *parallel);
+        assert_eq!(*diagram.normal_form().to_morphism(&mat).unwrap(), *parallel);
+    }
+}
@@ -0,0 +1,861 @@
+/*
+Rel, the category of finite sets and binary relations.
+
+A relation R: A -> B is a set of pairs (a, b), composed relationally:
+a (R;S) c when a R b and b S c for some b. Relations are compared by their pairs,
+so two relations between the same sets are equal when they relate the same elements.
+Besides composition Rel is an allegory: every relation has a converse (its dagger)
+and relations between the same sets are ordered by inclusion, with intersections and unions.
+
+Functions are the relations which are maps, total and single-valued,
+and graph_functor embeds FinSet into Rel so instances of either can be compared.
+ */
+use crate::core::arrow::Functor;
+use crate::core::concrete_category::fin_set::{FinSet, Function};
+use crate::core::discrete_category::DiscreteCategory;
+use crate::core::errors::Errors;
+use crate::core::identifier::Identifier;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+#[derive(Debug)]
+pub struct Relation {
+    id: String,
+    source: Rc<DiscreteCategory>,
+    target: Rc<DiscreteCategory>,
+    pairs: HashSet<(ObjectId, ObjectId)>,
+    // the function it is the graph of, empty when the relation is not a map
+    mappings: HashMap<Rc<Function>, Rc<Function>>,
+}
+
+impl PartialEq for Relation {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.source, &other.source)
+            && Rc::ptr_eq(&self.target, &other.target)
+            && self.pairs == other.pairs
+    }
+}
+
+impl Eq for Relation {}
+
+impl Hash for Relation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Rc::as_ptr(&self.source).hash(state);
+        Rc::as_ptr(&self.target).hash(state);
+        self.sorted_pairs().hash(state);
+    }
+}
+
+impl Relation {
+    pub fn from_pairs(
+        id: String,
+        source: Rc<DiscreteCategory>,
+        target: Rc<DiscreteCategory>,
+        pairs: HashSet<(ObjectId, ObjectId)>,
+    ) -> Result<Self, Errors> {
+        for (element, image) in &pairs {
+            source.get_identity_morphism(&DiscreteCategory::new_with_id(element.clone()))?;
+            target.get_identity_morphism(&DiscreteCategory::new_with_id(image.clone()))?;
+        }
+        let mut relation = Relation {
+            id,
+            source,
+            target,
+            pairs,
+            mappings: HashMap::new(),
+        };
+        if let Some(table) = relation.as_function() {
+            relation.mappings = FinSet::function(&relation.source, &relation.target, &table)?
+                .arrow_mappings()
+                .clone();
+        }
+        Ok(relation)
+    }
+
+    // the relation between two sets given by the pairs of a function
+    pub fn graph(function: &Function) -> Result<Self, Errors> {
+        let pairs = FinSet::function_table(function)?.into_iter().collect();
+        Relation::from_pairs(
+            function.arrow_id().clone(),
+            function.source_object().clone(),
+            function.target_object().clone(),
+            pairs,
+        )
+    }
+
+    pub fn pairs(&self) -> &HashSet<(ObjectId, ObjectId)> {
+        &self.pairs
+    }
+
+    pub fn relates(&self, element: &ObjectId, image: &ObjectId) -> bool {
+        self.pairs.contains(&(element.clone(), image.clone()))
+    }
+
+    // the elements related to an element of the source
+    pub fn image(&self, element: &ObjectId) -> Vec<ObjectId> {
+        let mut images = self
+            .pairs
+            .iter()
+            .filter(|(source, _)| source == element)
+            .map(|(_, image)| image.clone())
+            .collect::<Vec<_>>();
+        images.sort_by_key(|image| image.to_string());
+        images
+    }
+
+    // the table of the function, when every element is related to exactly one image
+    pub fn as_function(&self) -> Option<HashMap<ObjectId, ObjectId>> {
+        FinSet::elements(&self.source)
+            .into_iter()
+            .map(|element| match self.image(&element).as_slice() {
+                [image] => Some((element, image.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn is_map(&self) -> bool {
+        self.as_function().is_some()
+    }
+
+    fn sorted_pairs(&self) -> Vec<String> {
+        let mut pairs = self
+            .pairs
+            .iter()
+            .map(|(element, image)| format!("{}~{}", element, image))
+            .collect::<Vec<_>>();
+        pairs.sort();
+        pairs
+    }
+}
+
+impl ArrowTrait<DiscreteCategory, DiscreteCategory> for Relation {
+    fn source_object(&self) -> &Rc<DiscreteCategory> {
+        &self.source
+    }
+
+    fn target_object(&self) -> &Rc<DiscreteCategory> {
+        &self.target
+    }
+
+    fn new_instance(
+        source: Rc<DiscreteCategory>,
+        target: Rc<DiscreteCategory>,
+        id: &str,
+        mappings: HashMap<Rc<Function>, Rc<Function>>,
+    ) -> Self {
+        Self::new(id.to_string(), source, target, mappings)
+    }
+
+    // the graph of the function given by the mappings
+    fn new(
+        id: String,
+        source: Rc<DiscreteCategory>,
+        target: Rc<DiscreteCategory>,
+        mappings: HashMap<Rc<Function>, Rc<Function>>,
+    ) -> Self {
+        let pairs = mappings
+            .iter()
+            .map(|(element, image)| {
+                (
+                    element.source_object().category_id().clone(),
+                    image.source_object().category_id().clone(),
+                )
+            })
+            .collect();
+        Relation {
+            id,
+            source,
+            target,
+            pairs,
+            mappings,
+        }
+    }
+
+    fn is_identity(&self) -> bool {
+        Rc::ptr_eq(&self.source, &self.target)
+            && self.pairs.iter().all(|(element, image)| element == image)
+            && self.pairs.len() == FinSet::elements(&self.source).len()
+    }
+
+    fn arrow_id(&self) -> &String {
+        &self.id
+    }
+
+    fn compose(
+        &self,
+        _other: &impl ArrowTrait<DiscreteCategory, DiscreteCategory>,
+    ) -> Result<Rc<impl ArrowTrait<DiscreteCategory, DiscreteCategory>>, Errors> {
+        Err::<Rc<Relation>, _>(Errors::InvalidOperation(
+            "Relations are composed by Rel::compose_morphisms".to_string(),
+        ))
+    }
+
+    fn arrows(&self) -> Vec<&impl ArrowTrait<DiscreteCategory, DiscreteCategory>> {
+        vec![self]
+    }
+
+    fn arrow_mappings(&self) -> &HashMap<Rc<Function>, Rc<Function>> {
+        &self.mappings
+    }
+
+    fn validate_mappings(&self) -> Result<(), Errors> {
+        for (element, image) in &self.pairs {
+            self.source
+                .get_identity_morphism(&DiscreteCategory::new_with_id(element.clone()))?;
+            self.target
+                .get_identity_morphism(&DiscreteCategory::new_with_id(image.clone()))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rel {
+    id: ObjectId,
+    objects: HashMap<ObjectId, Rc<DiscreteCategory>>,
+    identities: HashMap<ObjectId, Rc<Relation>>,
+    // relations out of each set, identities excluded
+    hom_sets: HashMap<ObjectId, Vec<Rc<Relation>>>,
+}
+
+impl Default for Rel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for Rel {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Rel {}
+
+impl Hash for Rel {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl Rel {
+    pub fn new() -> Self {
+        Self::new_with_id(ObjectId::generate())
+    }
+
+    pub fn new_with_id(id: ObjectId) -> Self {
+        Rel {
+            id,
+            objects: HashMap::new(),
+            identities: HashMap::new(),
+            hom_sets: HashMap::new(),
+        }
+    }
+
+    pub fn add_set<T: Into<ObjectId>>(
+        &mut self,
+        id: impl Into<ObjectId>,
+        elements: Vec<T>,
+    ) -> Result<Rc<DiscreteCategory>, Errors> {
+        let set = Rc::new(FinSet::new_set(id.into(), elements)?);
+        self.add_object(set.clone())?;
+        Ok(set)
+    }
+
+    pub fn add_relation<T: Into<ObjectId>, U: Into<ObjectId>>(
+        &mut self,
+        id: &str,
+        source: &Rc<DiscreteCategory>,
+        target: &Rc<DiscreteCategory>,
+        pairs: Vec<(T, U)>,
+    ) -> Result<Rc<Relation>, Errors> {
+        let pairs = pairs
+            .into_iter()
+            .map(|(element, image)| (element.into(), image.into()))
+            .collect();
+        let relation = Rc::new(Relation::from_pairs(
+            id.to_string(),
+            source.clone(),
+            target.clone(),
+            pairs,
+        )?);
+        Ok(self.add_morphism(relation)?.clone())
+    }
+
+    pub fn identity_relation(set: &Rc<DiscreteCategory>) -> Rc<Relation> {
+        let pairs = FinSet::elements(set)
+            .into_iter()
+            .map(|element| (element.clone(), element))
+            .collect();
+        let mappings = FinSet::identity_function(set).arrow_mappings().clone();
+        Rc::new(Relation {
+            id: format!("id_{}", set.category_id()),
+            source: set.clone(),
+            target: set.clone(),
+            pairs,
+            mappings,
+        })
+    }
+
+    // the dagger R°: B -> A of R: A -> B
+    pub fn converse(relation: &Relation) -> Result<Rc<Relation>, Errors> {
+        let pairs = relation
+            .pairs
+            .iter()
+            .map(|(element, image)| (image.clone(), element.clone()))
+            .collect();
+        Ok(Rc::new(Relation::from_pairs(
+            format!("{}°", relation.id),
+            relation.target.clone(),
+            relation.source.clone(),
+            pairs,
+        )?))
+    }
+
+    pub fn intersection(first: &Relation, second: &Relation) -> Result<Rc<Relation>, Errors> {
+        Self::validate_parallel(first, second)?;
+        let pairs = first.pairs.intersection(&second.pairs).cloned().collect();
+        Ok(Rc::new(Relation::from_pairs(
+            format!("{}∩{}", first.id, second.id),
+            first.source.clone(),
+            first.target.clone(),
+            pairs,
+        )?))
+    }
+
+    pub fn union(first: &Relation, second: &Relation) -> Result<Rc<Relation>, Errors> {
+        Self::validate_parallel(first, second)?;
+        let pairs = first.pairs.union(&second.pairs).cloned().collect();
+        Ok(Rc::new(Relation::from_pairs(
+            format!("{}∪{}", first.id, second.id),
+            first.source.clone(),
+            first.target.clone(),
+            pairs,
+        )?))
+    }
+
+    // R ≤ S when every pair of R is in S
+    pub fn is_included(first: &Relation, second: &Relation) -> Result<bool, Errors> {
+        Self::validate_parallel(first, second)?;
+        Ok(first.pairs.is_subset(&second.pairs))
+    }
+
+    // the embedding of the functions of a FinSet as their graphs, into a Rel on the same sets
+    pub fn graph_functor(fin_set: &Rc<FinSet>) -> Result<Functor<FinSet, Rel>, Errors> {
+        let mut rel = Rel::new();
+        let mut functions = Vec::new();
+        for set in fin_set.get_all_objects()? {
+            rel.add_object(set.clone())?;
+            functions.extend(fin_set.get_object_morphisms(set)?);
+        }
+        let mut mappings = HashMap::new();
+        for function in functions {
+            let relation = if function.is_identity() {
+                rel.get_identity_morphism(function.source_object())?.clone()
+            } else {
+                rel.add_morphism(Rc::new(Relation::graph(function)?))?
+                    .clone()
+            };
+            mappings.insert(function.clone(), relation);
+        }
+        Ok(Functor::new(
+            format!("Graph({})", fin_set.category_id()),
+            fin_set.clone(),
+            Rc::new(rel),
+            mappings,
+        ))
+    }
+
+    // the pairs (a, b) where a is the only element related to b
+    fn private_pairs(relation: &Relation) -> HashSet<(ObjectId, ObjectId)> {
+        relation
+            .pairs
+            .iter()
+            .filter(|(element, image)| {
+                relation
+                    .pairs
+                    .iter()
+                    .all(|(other, related)| related != image || other == element)
+            })
+            .cloned()
+            .collect()
+    }
+
+    // the relation as stored in the category when there is an equal one
+    fn stored_or_new(&self, relation: Rc<Relation>) -> Rc<Relation> {
+        self.get_hom_set(relation.source_object(), relation.target_object())
+            .ok()
+            .and_then(|hom_set| {
+                hom_set
+                    .into_iter()
+                    .find(|stored| **stored == relation)
+                    .cloned()
+            })
+            .unwrap_or(relation)
+    }
+
+    fn validate_parallel(first: &Relation, second: &Relation) -> Result<(), Errors> {
+        if !Rc::ptr_eq(&first.source, &second.source) || !Rc::ptr_eq(&first.target, &second.target)
+        {
+            return Err(Errors::InvalidOperation(format!(
+                "Relations {} and {} are not between the same sets",
+                first.id, second.id
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl CategoryTrait for Rel {
+    type Object = DiscreteCategory;
+
+    type Morphism = Relation;
+
+    fn new() -> Self {
+        Rel::new()
+    }
+
+    fn new_with_id(id: &ObjectId) -> Self {
+        Rel::new_with_id(id.clone())
+    }
+
+    fn category_id(&self) -> &ObjectId {
+        &self.id
+    }
+
+    fn update_category_id(&mut self, new_id: ObjectId) {
+        self.id = new_id;
+    }
+
+    fn add_object(&mut self, object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        if self.objects.contains_key(object.category_id()) {
+            return Err(Errors::ObjectAlreadyExists);
+        }
+        let identity = Self::identity_relation(&object);
+        self.objects
+            .insert(object.category_id().clone(), object.clone());
+        self.identities
+            .insert(object.category_id().clone(), identity.clone());
+        self.hom_sets
+            .insert(object.category_id().clone(), Vec::new());
+        Ok(identity)
+    }
+
+    // a relation equal to one already in the category is not added twice
+    fn add_morphism(
+        &mut self,
+        morphism: Rc<Self::Morphism>,
+    ) -> Result<&Rc<Self::Morphism>, Errors> {
+        for object in [morphism.source_object(), morphism.target_object()] {
+            match self.objects.get(object.category_id()) {
+                Some(stored) if Rc::ptr_eq(stored, object) => {}
+                _ => return Err(Errors::ObjectNotFound(object.category_id().clone())),
+            }
+        }
+        morphism.validate_mappings()?;
+        let source = morphism.source_object().category_id().clone();
+        if morphism.is_identity() {
+            return self
+                .identities
+                .get(&source)
+                .ok_or(Errors::IdentityMorphismNotFound);
+        }
+        let hom_set = self.hom_sets.entry(source).or_default();
+        let index = match hom_set.iter().position(|relation| relation == &morphism) {
+            Some(index) => index,
+            None => {
+                hom_set.push(morphism);
+                hom_set.len() - 1
+            }
+        };
+        Ok(&hom_set[index])
+    }
+
+    fn get_identity_morphism(&self, object: &Self::Object) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.identities
+            .get(object.category_id())
+            .ok_or(Errors::IdentityMorphismNotFound)
+    }
+
+    fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
+        self.objects
+            .get(object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))
+    }
+
+    fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
+        Ok(self.objects.values().collect())
+    }
+
+    fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .identities
+            .values()
+            .chain(self.hom_sets.values().flatten())
+            .collect())
+    }
+
+    fn get_hom_set_x(
+        &self,
+        source_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .get_object_morphisms(source_object)?
+            .into_iter()
+            .collect())
+    }
+
+    fn get_object_morphisms(
+        &self,
+        object: &Self::Object,
+    ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
+        let relations = self
+            .hom_sets
+            .get(object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))?;
+        let mut result = vec![self.get_identity_morphism(object)?];
+        result.extend(relations.iter());
+        Ok(result)
+    }
+
+    // relational composition, returning the stored relation when there is an equal one
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        if !Rc::ptr_eq(first.target_object(), second.source_object()) {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        let mut pairs = HashSet::new();
+        for (element, middle) in &first.pairs {
+            for (other, image) in &second.pairs {
+                if middle == other {
+                    pairs.insert((element.clone(), image.clone()));
+                }
+            }
+        }
+        let composite = Relation::from_pairs(
+            format!("{};{}", first.id, second.id),
+            first.source.clone(),
+            second.target.clone(),
+            pairs,
+        )?;
+        Ok(self.stored_or_new(Rc::new(composite)))
+    }
+
+    /*
+    The hom-sets only hold the relations added to the category, so the predicates
+    below are decided on the pairs instead of searching them for rivals.
+     */
+
+    // the graphs of bijections, inverted by their converse
+    fn inverse_morphism(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        if morphism.is_identity() {
+            return Ok(Some(morphism.clone()));
+        }
+        let converse = Rel::converse(morphism)?;
+        if !morphism.is_map() || !converse.is_map() {
+            return Ok(None);
+        }
+        Ok(Some(self.stored_or_new(converse)))
+    }
+
+    // R is mono when every element is the only one related to some image
+    fn is_mono(&self, morphism: &Rc<Self::Morphism>) -> Result<bool, Errors> {
+        Ok(self.is_split_mono(morphism)?.is_some())
+    }
+
+    // and epi when its converse is mono
+    fn is_epi(&self, morphism: &Rc<Self::Morphism>) -> Result<bool, Errors> {
+        Ok(self.is_split_epi(morphism)?.is_some())
+    }
+
+    // every mono splits, sending the images only its element reaches back to it
+    fn is_split_mono(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        let private = Self::private_pairs(morphism);
+        let reached = private
+            .iter()
+            .map(|(element, _)| element)
+            .collect::<HashSet<_>>();
+        if reached.len() != FinSet::elements(&morphism.source).len() {
+            return Ok(None);
+        }
+        let retraction = Relation::from_pairs(
+            format!("r_{}", morphism.id),
+            morphism.target.clone(),
+            morphism.source.clone(),
+            private
+                .into_iter()
+                .map(|(element, image)| (image, element))
+                .collect(),
+        )?;
+        Ok(Some(self.stored_or_new(Rc::new(retraction))))
+    }
+
+    // and every epi, sending each image to the elements related to it alone
+    fn is_split_epi(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        let converse = Rel::converse(morphism)?;
+        let private = Self::private_pairs(&converse);
+        let reached = private
+            .iter()
+            .map(|(image, _)| image)
+            .collect::<HashSet<_>>();
+        if reached.len() != FinSet::elements(&morphism.target).len() {
+            return Ok(None);
+        }
+        let section = Relation::from_pairs(
+            format!("s_{}", morphism.id),
+            morphism.target.clone(),
+            morphism.source.clone(),
+            private,
+        )?;
+        Ok(Some(self.stored_or_new(Rc::new(section))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relational_composition_and_dagger() {
+        let mut rel = Rel::new();
+        let people = rel.add_set("people", vec!["ann", "bob"]).unwrap();
+        let projects = rel.add_set("projects", vec!["p", "q", "r"]).unwrap();
+        let works_on = rel
+            .add_relation(
+                "works_on",
+                &people,
+                &projects,
+                vec![("ann", "p"), ("ann", "q"), ("bob", "q")],
+            )
+            .unwrap();
+
+        // people sharing a project: works_on ; works_on°
+        let members = Rel::converse(&works_on).unwrap();
+        let colleagues = rel.compose_morphisms(&works_on, &members).unwrap();
+        assert!(colleagues.relates(&"ann".into(), &"bob".into()));
+        assert!(colleagues.relates(&"bob".into(), &"bob".into()));
+        assert!(!colleagues.is_map());
+        // the dagger is an involution reversing composition
+        assert_eq!(Rel::converse(&members).unwrap(), works_on);
+        assert_eq!(Rel::converse(&colleagues).unwrap(), colleagues);
+
+        let identity = rel.get_identity_morphism(&people).unwrap().clone();
+        assert!(identity.is_identity());
+        assert_eq!(
+            rel.compose_morphisms(&identity, &works_on).unwrap(),
+            works_on
+        );
+
+        // allegory operations
+        let on_q = rel
+            .add_relation("on_q", &people, &projects, vec![("ann", "q"), ("bob", "q")])
+            .unwrap();
+        let both = Rel::intersection(&works_on, &on_q).unwrap();
+        assert!(Rel::is_included(&both, &works_on).unwrap());
+        assert!(Rel::is_included(&on_q, &works_on).unwrap());
+        assert_eq!(Rel::union(&works_on, &on_q).unwrap(), works_on);
+        assert!(Rel::is_included(&works_on, &members).is_err());
+    }
+
+    #[test]
+    fn test_modular_law() {
+        let mut rel = Rel::new();
+        let people = rel.add_set("people", vec!["ann", "bob"]).unwrap();
+        let teams = rel.add_set("teams", vec!["x", "y"]).unwrap();
+        let tasks = rel.add_set("tasks", vec!["u", "v"]).unwrap();
+        // R: both people are in team x, S: team x does u and v, T: ann does u and bob does v
+        let r = rel
+            .add_relation("R", &people, &teams, vec![("ann", "x"), ("bob", "x")])
+            .unwrap();
+        let s = rel
+            .add_relation("S", &teams, &tasks, vec![("x", "u"), ("x", "v")])
+            .unwrap();
+        let t = rel
+            .add_relation("T", &people, &tasks, vec![("ann", "u"), ("bob", "v")])
+            .unwrap();
+
+        // R;S ∩ T ≤ R;(S ∩ R°;T)
+        let left = Rel::intersection(&rel.compose_morphisms(&r, &s).unwrap(), &t).unwrap();
+        let r_t = rel
+            .compose_morphisms(&Rel::converse(&r).unwrap(), &t)
+            .unwrap();
+        let right = rel
+            .compose_morphisms(&r, &Rel::intersection(&s, &r_t).unwrap())
+            .unwrap();
+        assert_eq!(*left, *t);
+        assert!(Rel::is_included(&left, &right).unwrap());
+        // and strictly, R;(S ∩ R°;T) also relates ann to v
+        assert!(right.relates(&"ann".into(), &"v".into()));
+        assert!(!Rel::is_included(&right, &left).unwrap());
+    }
+
+    #[test]
+    fn test_converse_reverses_composition() {
+        let mut rel = Rel::new();
+        let people = rel.add_set("people", vec!["ann", "bob", "cy"]).unwrap();
+        let projects = rel.add_set("projects", vec!["p", "q"]).unwrap();
+        let rooms = rel.add_set("rooms", vec![1, 2, 3]).unwrap();
+        let works_on = rel
+            .add_relation(
+                "works_on",
+                &people,
+                &projects,
+                vec![("ann", "p"), ("bob", "p"), ("bob", "q")],
+            )
+            .unwrap();
+        let meets_in = rel
+            .add_relation("meets_in", &projects, &rooms, vec![("p", 1), ("q", 3)])
+            .unwrap();
+
+        // (R;S)° = S°;R°
+        let composite = rel.compose_morphisms(&works_on, &meets_in).unwrap();
+        let converse = Rel::converse(&composite).unwrap();
+        let reversed = rel
+            .compose_morphisms(
+                &Rel::converse(&meets_in).unwrap(),
+                &Rel::converse(&works_on).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(converse, reversed);
+        assert!(converse.relates(&3.into(), &"bob".into()));
+        assert!(!converse.relates(&3.into(), &"ann".into()));
+        // but not S°;R° in the other order, which does not even compose
+        assert!(
+            rel.compose_morphisms(
+                &Rel::converse(&works_on).unwrap(),
+                &Rel::converse(&meets_in).unwrap()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_monos_and_epis_without_rival_relations() {
+        let mut rel = Rel::new();
+        let people = rel.add_set("people", vec!["ann", "bob"]).unwrap();
+        let projects = rel.add_set("projects", vec!["p", "q", "r"]).unwrap();
+        let badges = rel.add_set("badges", vec![1, 2]).unwrap();
+        // ann alone works on p and bob alone on r, with q shared
+        let works_on = rel
+            .add_relation(
+                "works_on",
+                &people,
+                &projects,
+                vec![("ann", "p"), ("ann", "q"), ("bob", "q"), ("bob", "r")],
+            )
+            .unwrap();
+        let wears = rel
+            .add_relation("wears", &people, &badges, vec![("ann", 2), ("bob", 1)])
+            .unwrap();
+
+        // no relation out of projects is stored, yet works_on is a split mono
+        assert!(rel.is_mono(&works_on).unwrap());
+        assert!(!works_on.is_map());
+        let retraction = rel.is_split_mono(&works_on).unwrap().unwrap();
+        assert_eq!(
+            rel.compose_morphisms(&works_on, &retraction).unwrap(),
+            *rel.get_identity_morphism(&people).unwrap()
+        );
+        // it is not epi, as q is not reached by one person alone, but its converse is
+        assert!(!rel.is_epi(&works_on).unwrap());
+        assert!(rel.is_split_epi(&works_on).unwrap().is_none());
+        let converse = Rel::converse(&works_on).unwrap();
+        assert!(rel.is_epi(&converse).unwrap());
+        let section = rel.is_split_epi(&converse).unwrap().unwrap();
+        assert_eq!(
+            rel.compose_morphisms(&section, &converse).unwrap(),
+            *rel.get_identity_morphism(&people).unwrap()
+        );
+        assert_eq!(rel.inverse_morphism(&works_on).unwrap(), None);
+
+        // the graph of a bijection is inverted by its converse
+        let inverse = rel.inverse_morphism(&wears).unwrap().unwrap();
+        assert_eq!(*inverse, *Rel::converse(&wears).unwrap());
+        assert_eq!(
+            rel.compose_morphisms(&inverse, &wears).unwrap(),
+            *rel.get_identity_morphism(&badges).unwrap()
+        );
+        // and a relation whose images are all shared is not mono
+        let shared = rel
+            .add_relation(
+                "shared",
+                &people,
+                &projects,
+                vec![("ann", "q"), ("bob", "q")],
+            )
+            .unwrap();
+        assert!(!rel.is_mono(&shared).unwrap());
+        assert!(!rel.is_epi(&shared).unwrap());
+    }
+
+    #[test]
+    fn test_graph_functor_compares_instances() {
+        let mut fin_set = FinSet::new();
+        let letters = fin_set.add_set("letters", vec!["a", "b"]).unwrap();
+        let numbers = fin_set.add_set("numbers", vec![1, 2]).unwrap();
+        let f = fin_set
+            .add_function(
+                "f",
+                &letters,
+                &numbers,
+                HashMap::from([("a".into(), 2.into()), ("b".into(), 2.into())]),
+            )
+            .unwrap();
+        let fin_set = Rc::new(fin_set);
+        let graph = Rel::graph_functor(&fin_set).unwrap();
+        let rel = graph.target_object().clone();
+
+        // the same instance, one valued in FinSet and one in Rel built by hand
+        let instance = Functor::new(
+            "instance".to_string(),
+            fin_set.clone(),
+            fin_set.clone(),
+            HashMap::from([
+                (
+                    fin_set.get_identity_morphism(&letters).unwrap().clone(),
+                    fin_set.get_identity_morphism(&letters).unwrap().clone(),
+                ),
+                (f.clone(), f.clone()),
+            ]),
+        );
+        let related = Rc::new(
+            Relation::from_pairs(
+                "related".to_string(),
+                letters.clone(),
+                numbers.clone(),
+                HashSet::from([("a".into(), 2.into()), ("b".into(), 2.into())]),
+            )
+            .unwrap(),
+        );
+        assert!(related.is_map());
+        let as_relations = instance.compose_functor(&graph).unwrap();
+        assert_eq!(as_relations.map_morphism(&f).unwrap(), &related);
+        assert_eq!(
+            as_relations
+                .map_morphism(fin_set.get_identity_morphism(&letters).unwrap())
+                .unwrap(),
+            rel.get_identity_morphism(&letters).unwrap()
+        );
+        // a relation which is not a map has no function behind it
+        let converse = Rel::converse(&related).unwrap();
+        assert!(converse.as_function().is_none());
+        assert!(converse.arrow_mappings().is_empty());
+    }
+}
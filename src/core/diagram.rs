@@ -0,0 +1,442 @@
+/*
+A diagram of shape J in a category C is a functor F: J -> C placing the objects and morphisms
+of J in C (see shapes for the usual J). It is built leg by leg, naming the morphisms of J:
+
+    let mut diagram = Diagram::new(Rc::new(shapes::span()), category);
+    diagram.set_leg("l", p)?;
+    diagram.set_leg("r", q)?;
+
+Setting a leg places its source and target, objects without legs are placed with set_object.
+A morphism of J left unset which is a composite g ∘ f of J is placed at F(g) ∘ F(f), and every
+composable pair g ∘ f = h of J is a face
+
+    F(g) ∘ F(f) = F(h)
+
+which must commute in C for the diagram to be a functor.
+ */
+use crate::core::arrow::Functor;
+use crate::core::errors::Errors;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::{CategoryTrait, MorphismCommutationResult};
+use crate::core::traits::finite_limits_trait::FiniteDiagram;
+use crate::core::utils::{sorted_morphisms, sorted_objects};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+type Images<Shape, Category> =
+    HashMap<Rc<<Shape as CategoryTrait>::Morphism>, Rc<<Category as CategoryTrait>::Morphism>>;
+
+#[derive(Debug)]
+pub struct Diagram<Shape: CategoryTrait, Category: CategoryTrait> {
+    shape: Rc<Shape>,
+    category: Rc<Category>,
+    // the images of the objects and morphisms of the shape, keyed by their ids
+    objects: HashMap<ObjectId, Rc<Category::Object>>,
+    legs: HashMap<String, Rc<Category::Morphism>>,
+}
+
+// the composable morphisms of the shape with second ∘ first = composite
+#[derive(Debug)]
+pub struct Face<Shape: CategoryTrait> {
+    first: Rc<Shape::Morphism>,
+    second: Rc<Shape::Morphism>,
+    composite: Rc<Shape::Morphism>,
+}
+
+impl<Shape: CategoryTrait> Face<Shape> {
+    pub fn first(&self) -> &Rc<Shape::Morphism> {
+        &self.first
+    }
+
+    pub fn second(&self) -> &Rc<Shape::Morphism> {
+        &self.second
+    }
+
+    pub fn composite(&self) -> &Rc<Shape::Morphism> {
+        &self.composite
+    }
+}
+
+impl<Shape: CategoryTrait> Display for Face<Shape> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ∘ {} = {}",
+            self.second.arrow_id(),
+            self.first.arrow_id(),
+            self.composite.arrow_id()
+        )
+    }
+}
+
+impl<Shape: CategoryTrait, Category: CategoryTrait> Diagram<Shape, Category> {
+    pub fn new(shape: Rc<Shape>, category: Rc<Category>) -> Self {
+        Diagram {
+            shape,
+            category,
+            objects: HashMap::new(),
+            legs: HashMap::new(),
+        }
+    }
+
+    pub fn shape(&self) -> &Rc<Shape> {
+        &self.shape
+    }
+
+    pub fn category(&self) -> &Rc<Category> {
+        &self.category
+    }
+
+    pub fn object(&self, object: &ObjectId) -> Result<&Rc<Category::Object>, Errors> {
+        self.objects
+            .get(object)
+            .ok_or(Errors::ObjectNotFound(object.clone()))
+    }
+
+    pub fn leg(&self, name: &str) -> Result<&Rc<Category::Morphism>, Errors> {
+        self.legs.get(name).ok_or(Errors::MorphismNotFound)
+    }
+
+    pub fn set_object(
+        &mut self,
+        object: &ObjectId,
+        image: Rc<Category::Object>,
+    ) -> Result<(), Errors> {
+        self.shape_object(object)?;
+        self.category.get_object(&image)?;
+        self.check_placement(object, &image)?;
+        self.objects.insert(object.clone(), image);
+        Ok(())
+    }
+
+    // places the morphism of the shape with the given id, and its source and target
+    pub fn set_leg(&mut self, name: &str, morphism: Rc<Category::Morphism>) -> Result<(), Errors> {
+        let leg = self.shape_morphism(name)?.clone();
+        if !self
+            .category
+            .get_hom_set_x(morphism.source_object())?
+            .contains(&morphism)
+        {
+            return Err(Errors::InvalidFunctor(format!(
+                "Morphism {} is not in {}",
+                morphism.arrow_id(),
+                self.category.category_id()
+            )));
+        }
+        if leg.is_identity() && !morphism.is_identity() {
+            return Err(Errors::InvalidFunctor(format!(
+                "Identity {} is not placed at an identity",
+                name
+            )));
+        }
+        let source = leg.source_object().category_id();
+        let target = leg.target_object().category_id();
+        self.check_placement(source, morphism.source_object())?;
+        self.check_placement(target, morphism.target_object())?;
+        self.objects
+            .insert(source.clone(), morphism.source_object().clone());
+        self.objects
+            .insert(target.clone(), morphism.target_object().clone());
+        self.legs.insert(name.to_string(), morphism);
+        Ok(())
+    }
+
+    // every composable pair of non-identity morphisms of the shape with its composite
+    pub fn faces(&self) -> Result<Vec<Face<Shape>>, Errors> {
+        let mut faces = Vec::new();
+        for first in self.shape_morphisms()? {
+            if first.is_identity() {
+                continue;
+            }
+            for second in sorted_morphisms(self.shape.get_hom_set_x(first.target_object())?) {
+                if second.is_identity() {
+                    continue;
+                }
+                faces.push(Face {
+                    first: first.clone(),
+                    second: second.clone(),
+                    composite: self.shape.compose_morphisms(first, second)?,
+                });
+            }
+        }
+        Ok(faces)
+    }
+
+    // the faces which do not commute in the category
+    pub fn failing_faces(&self) -> Result<Vec<Face<Shape>>, Errors> {
+        let images = self.images()?;
+        let mut failing = Vec::new();
+        for face in self.faces()? {
+            let result = self.category.morphism_commute(
+                vec![&images[&face.first], &images[&face.second]],
+                vec![&images[&face.composite]],
+            )?;
+            if let MorphismCommutationResult::NonCommutative(_) = result {
+                failing.push(face);
+            }
+        }
+        Ok(failing)
+    }
+
+    pub fn functor(&self) -> Result<Functor<Shape, Category>, Errors> {
+        let failing = self.failing_faces()?;
+        if !failing.is_empty() {
+            let faces = failing
+                .iter()
+                .map(|face| face.to_string())
+                .collect::<Vec<_>>();
+            return Err(Errors::InvalidFunctor(format!(
+                "Faces {} do not commute",
+                faces.join(", ")
+            )));
+        }
+        Ok(Functor::new_with_mappings(
+            self.shape.clone(),
+            self.category.clone(),
+            self.images()?,
+        ))
+    }
+
+    pub fn validate(&self) -> Result<(), Errors> {
+        self.functor()?.validate_mappings()
+    }
+
+    // the placed objects and non-identity morphisms, the input of limits and colimits
+    pub fn finite_diagram(&self) -> Result<FiniteDiagram<Category>, Errors> {
+        let images = self.images()?;
+        let mut diagram = FiniteDiagram::new();
+        let mut indices = HashMap::new();
+        for object in sorted_objects(self.shape.get_all_objects()?) {
+            let index = diagram.add_object(self.object(object.category_id())?.clone());
+            indices.insert(object.category_id().clone(), index);
+        }
+        for morphism in self.shape_morphisms()? {
+            if morphism.is_identity() {
+                continue;
+            }
+            diagram.add_edge(
+                indices[morphism.source_object().category_id()],
+                indices[morphism.target_object().category_id()],
+                images[morphism].clone(),
+            )?;
+        }
+        Ok(diagram)
+    }
+
+    /*
+    The image of every morphism of the shape: the legs, the identities of the placed objects,
+    and the unset composites of the faces, composed from the first face they are the composite of.
+     */
+    fn images(&self) -> Result<Images<Shape, Category>, Errors> {
+        let morphisms = self.shape_morphisms()?;
+        let mut images = HashMap::new();
+        for morphism in &morphisms {
+            if let Some(leg) = self.legs.get(morphism.arrow_id()) {
+                images.insert((*morphism).clone(), leg.clone());
+            } else if morphism.is_identity() {
+                let object = self.object(morphism.source_object().category_id())?;
+                let identity = self.category.get_identity_morphism(object)?;
+                images.insert((*morphism).clone(), identity.clone());
+            }
+        }
+        let faces = self.faces()?;
+        loop {
+            let mut placed = false;
+            for face in &faces {
+                if images.contains_key(&face.composite) {
+                    continue;
+                }
+                let composite = match (images.get(&face.first), images.get(&face.second)) {
+                    (Some(first), Some(second)) => {
+                        self.category.compose_morphisms(first, second)?
+                    }
+                    _ => continue,
+                };
+                images.insert(face.composite.clone(), composite);
+                placed = true;
+            }
+            if !placed {
+                break;
+            }
+        }
+        for morphism in morphisms {
+            if !images.contains_key(morphism) {
+                return Err(Errors::InvalidFunctor(format!(
+                    "No leg for morphism {}",
+                    morphism.arrow_id()
+                )));
+            }
+        }
+        Ok(images)
+    }
+
+    fn check_placement(&self, object: &ObjectId, image: &Category::Object) -> Result<(), Errors> {
+        match self.objects.get(object) {
+            Some(placed) if **placed != *image => Err(Errors::InvalidFunctor(format!(
+                "Object {} is placed at {}, not {}",
+                object,
+                placed.category_id(),
+                image.category_id()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn shape_object(&self, object: &ObjectId) -> Result<&Rc<Shape::Object>, Errors> {
+        self.shape
+            .get_all_objects()?
+            .into_iter()
+            .find(|candidate| candidate.category_id() == object)
+            .ok_or(Errors::ObjectNotFound(object.clone()))
+    }
+
+    fn shape_morphism(&self, name: &str) -> Result<&Rc<Shape::Morphism>, Errors> {
+        self.shape_morphisms()?
+            .into_iter()
+            .find(|morphism| morphism.arrow_id() == name)
+            .ok_or(Errors::MorphismNotFound)
+    }
+
+    fn shape_morphisms(&self) -> Result<Vec<&Rc<Shape::Morphism>>, Errors> {
+        let mut morphisms = Vec::new();
+        for object in sorted_objects(self.shape.get_all_objects()?) {
+            morphisms.extend(sorted_morphisms(self.shape.get_hom_set_x(object)?));
+        }
+        Ok(morphisms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::concrete_category::fin_set::FinSet;
+    use crate::core::discrete_category::DiscreteCategory;
+    use crate::core::shapes;
+    use crate::core::traits::finite_limits_trait::FiniteLimitsTrait;
+
+    fn table(entries: &[(ObjectId, i32)]) -> HashMap<ObjectId, ObjectId> {
+        entries
+            .iter()
+            .map(|(element, image)| (element.clone(), (*image).into()))
+            .collect()
+    }
+
+    // a -f-> b -h-> d and a -g-> c -k-> d in sets, with g sending x to the given number
+    fn square(g_image: i32) -> Diagram<shapes::Shape, FinSet> {
+        let mut fin_set = FinSet::new();
+        let a = fin_set.add_set("A", vec!["x"]).unwrap();
+        let b = fin_set.add_set("B", vec![1, 2]).unwrap();
+        let d = fin_set.add_set("D", vec![1, 2]).unwrap();
+        let x = ObjectId::from("x");
+        let one = ObjectId::Int(1);
+        let two = ObjectId::Int(2);
+        let f = fin_set
+            .add_function("f", &a, &b, table(&[(x.clone(), 1)]))
+            .unwrap();
+        let g = fin_set
+            .add_function("g", &a, &b, table(&[(x, g_image)]))
+            .unwrap();
+        let swap = table(&[(one.clone(), 2), (two.clone(), 1)]);
+        let h = fin_set.add_function("h", &b, &d, swap).unwrap();
+        let same = table(&[(one, 1), (two, 2)]);
+        let k = fin_set.add_function("k", &b, &d, same).unwrap();
+
+        let mut diagram = Diagram::new(Rc::new(shapes::commutative_square()), Rc::new(fin_set));
+        for (name, leg) in [("f", f), ("g", g), ("h", h), ("k", k)] {
+            diagram.set_leg(name, leg).unwrap();
+        }
+        diagram
+    }
+
+    #[test]
+    fn test_commuting_square() {
+        // h ∘ f and k ∘ g both send x to 2
+        let diagram = square(2);
+        assert_eq!(diagram.faces().unwrap().len(), 2);
+        assert!(diagram.failing_faces().unwrap().is_empty());
+        diagram.validate().unwrap();
+
+        // the diagonal is placed at the composite
+        let functor = diagram.functor().unwrap();
+        let diagonal = diagram.shape().morphism("a→d").unwrap();
+        let image = functor.map_morphism(diagonal).unwrap();
+        assert_eq!(FinSet::apply(image, &"x".into()).unwrap(), ObjectId::Int(2));
+        assert_eq!(
+            functor.map_object(&DiscreteCategory::new_with_id("c".into())),
+            diagram.object(&"b".into())
+        );
+
+        let finite_diagram = diagram.finite_diagram().unwrap();
+        assert_eq!(finite_diagram.objects().len(), 4);
+        assert_eq!(finite_diagram.edges().len(), 5);
+    }
+
+    #[test]
+    fn test_failing_faces() {
+        // h ∘ f sends x to 2 but k ∘ g to 1
+        let diagram = square(1);
+        let failing = diagram
+            .failing_faces()
+            .unwrap()
+            .iter()
+            .map(|face| face.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(failing, vec!["k ∘ g = a→d"]);
+        assert_eq!(
+            diagram.functor().unwrap_err(),
+            Errors::InvalidFunctor("Faces k ∘ g = a→d do not commute".to_string())
+        );
+    }
+
+    #[test]
+    fn test_legs_are_checked() {
+        let mut fin_set = FinSet::new();
+        let letters = fin_set.add_set("A", vec!["a", "b"]).unwrap();
+        let numbers = fin_set.add_set("N", vec![1, 2]).unwrap();
+        let f = fin_set
+            .add_function(
+                "f",
+                &letters,
+                &numbers,
+                table(&[("a".into(), 1), ("b".into(), 2)]),
+            )
+            .unwrap();
+        let g = fin_set
+            .add_function(
+                "g",
+                &letters,
+                &numbers,
+                table(&[("a".into(), 1), ("b".into(), 1)]),
+            )
+            .unwrap();
+        let swap = fin_set
+            .add_function(
+                "swap",
+                &numbers,
+                &numbers,
+                table(&[(1.into(), 2), (2.into(), 1)]),
+            )
+            .unwrap();
+        let fin_set = Rc::new(fin_set);
+
+        let mut diagram = Diagram::new(Rc::new(shapes::parallel_pair()), fin_set.clone());
+        assert_eq!(
+            diagram.set_leg("h", f.clone()),
+            Err(Errors::MorphismNotFound)
+        );
+        diagram.set_leg("f", f).unwrap();
+        // g would place the source at N
+        assert!(diagram.set_leg("g", swap).is_err());
+        assert!(diagram.functor().is_err());
+
+        // the limit of the parallel pair is the equalizer, a with its image 1
+        diagram.set_leg("g", g).unwrap();
+        let equalizer = fin_set.limit(&diagram.finite_diagram().unwrap()).unwrap();
+        assert_eq!(
+            FinSet::elements(equalizer.apex()),
+            vec![ObjectId::from("a") + 1.into()]
+        );
+    }
+}
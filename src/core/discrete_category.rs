@@ -1,3 +1,12 @@
+/*
+A discrete category, whose only morphisms are the identities of its objects.
+
+The identities are not stored up front: each object keeps a cell which is filled with its
+identity, named "id(x)" after the object x, the first time the identity is asked for and
+returned from there on. A set with many elements made through insert_object, as FinSet does,
+never builds the identities nobody asks for. add_object has to return the identity, so it
+builds it at once.
+ */
 use crate::core::arrow::{Arrow, Functor, Morphism};
 use crate::core::dynamic_category::DynamicCategory;
 use crate::core::errors::Errors;
@@ -5,27 +14,40 @@ use crate::core::identifier::Identifier;
 use crate::core::object_id::ObjectId;
 use crate::core::traits::arrow_trait::ArrowTrait;
 use crate::core::traits::category_trait::CategoryTrait;
+use std::cell::OnceCell;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display};
 use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+type IdentityCell = OnceCell<Rc<Morphism<DiscreteCategory>>>;
+
+#[derive(Debug, Clone)]
 pub struct DiscreteCategory {
     category_id: ObjectId,
-    // TODO: Find a way of avoiding storing identity cells
-    // as we could derive them from the objects.
-    cells: Option<HashMap<ObjectId, Rc<Morphism<Self>>>>,
+    // each object with its identity, once asked for
+    objects: HashMap<ObjectId, (Rc<DiscreteCategory>, IdentityCell)>,
+}
+
+// equal ids and equal objects, whichever identities have been built
+impl PartialEq for DiscreteCategory {
+    fn eq(&self, other: &Self) -> bool {
+        self.category_id == other.category_id
+            && self.objects.len() == other.objects.len()
+            && self.objects.iter().all(|(id, (object, _))| {
+                other
+                    .objects
+                    .get(id)
+                    .is_some_and(|(other_object, _)| object == other_object)
+            })
+    }
 }
 
+impl Eq for DiscreteCategory {}
+
 impl Hash for DiscreteCategory {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.category_id.hash(state);
-        if let Some(cells) = &self.cells {
-            for key in cells.keys() {
-                key.hash(state);
-            }
-        }
     }
 }
 
@@ -37,28 +59,48 @@ impl Default for DiscreteCategory {
 
 impl DiscreteCategory {
     pub fn new() -> Self {
-        DiscreteCategory {
-            category_id: ObjectId::Str(String::generate()),
-            cells: Some(HashMap::new()),
-        }
+        Self::new_with_id(ObjectId::Str(String::generate()))
     }
 
     pub fn new_with_id(category_id: ObjectId) -> Self {
         DiscreteCategory {
             category_id,
-            cells: None,
+            objects: HashMap::new(),
         }
     }
 
     pub fn clone_with_new_id(&self) -> Self {
         Self {
             category_id: ObjectId::Str(String::generate()),
-            cells: self.cells.clone(),
+            objects: self.objects.clone(),
         }
     }
     pub fn category_id(&self) -> &ObjectId {
         &self.category_id
     }
+
+    // adds the object without building its identity
+    pub fn insert_object(&mut self, object: Rc<DiscreteCategory>) -> Result<(), Errors> {
+        if self.objects.contains_key(object.category_id()) {
+            return Err(Errors::ObjectAlreadyExists);
+        }
+        self.objects
+            .insert(object.category_id().clone(), (object, OnceCell::new()));
+        Ok(())
+    }
+
+    fn identity<'a>(
+        object: &Rc<DiscreteCategory>,
+        cell: &'a IdentityCell,
+    ) -> &'a Rc<Morphism<Self>> {
+        cell.get_or_init(|| {
+            Arrow::new_identity_with_mappings(
+                ObjectId::generated("id", [object.category_id().clone()]).to_string(),
+                object.clone(),
+                HashMap::new(),
+            )
+        })
+    }
 }
 
 impl CategoryTrait for DiscreteCategory {
@@ -86,25 +128,15 @@ impl CategoryTrait for DiscreteCategory {
     }
 
     fn add_object(&mut self, object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
-        let identity_morphism = Morphism::new_identity(object.clone());
-        if let Some(cells) = &mut self.cells {
-            if cells.contains_key(&object.category_id()) {
-                return Err(Errors::ObjectAlreadyExists);
-            }
-            cells.insert(object.category_id().clone(), identity_morphism.clone());
-        } else {
-            self.cells = Some(HashMap::new());
-            self.cells
-                .as_mut()
-                .unwrap()
-                .insert(object.category_id().clone(), identity_morphism.clone());
-        }
-        Ok(identity_morphism)
+        let id = object.category_id().clone();
+        self.insert_object(object)?;
+        let (object, cell) = &self.objects[&id];
+        Ok(Self::identity(object, cell).clone())
     }
 
     fn add_morphism(
         &mut self,
-        morphism: Rc<Morphism<Self::Object>>,
+        _morphism: Rc<Morphism<Self::Object>>,
     ) -> Result<&Rc<Morphism<Self::Object>>, Errors> {
         Err(Errors::CannotAddMorphismToDiscreteCategory)
     }
@@ -113,48 +145,29 @@ impl CategoryTrait for DiscreteCategory {
         &self,
         object: &Self::Object,
     ) -> Result<&Rc<Morphism<Self::Object>>, Errors> {
-        if let Some(cells) = &self.cells {
-            if let Some(cell) = cells.get(&object.category_id()) {
-                return Ok(cell);
-            }
-        }
-        Err(Errors::ObjectNotFound)
+        let (object, cell) = self
+            .objects
+            .get(object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))?;
+        Ok(Self::identity(object, cell))
     }
 
     fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
-        if let Some(cells) = &self.cells {
-            if let Some(cell) = cells.get(&object.category_id()) {
-                return Ok(cell.source_object());
-            }
-        }
-        Err(Errors::ObjectNotFound)
-    }
-
-    // fn get_all_objects(&self) -> Result<HashSet<&Rc<DiscreteCategory>>, Errors> {
-    //     let result = if let Some(cells) = &self.cells {
-    //         cells.values().map(|item| item.source_object()).collect()
-    //     } else {
-    //         HashSet::new()
-    //     };
-    //     Ok(result)
-    // }
-    // Rust
+        self.objects
+            .get(object.category_id())
+            .map(|(object, _)| object)
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))
+    }
+
     fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
-        let result = if let Some(cells) = &self.cells {
-            cells.values().map(|item| item.source_object()).collect()
-        } else {
-            HashSet::new()
-        };
-        Ok(result)
+        Ok(self.objects.values().map(|(object, _)| object).collect())
     }
 
     fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Morphism<Self::Object>>>, Errors> {
-        let result = if let Some(cells) = &self.cells {
-            cells.values().collect()
-        } else {
-            HashSet::new()
-        };
-        Ok(result)
+        self.objects
+            .values()
+            .map(|(object, _)| self.get_identity_morphism(object))
+            .collect()
     }
 
     fn get_hom_set_x(
@@ -173,8 +186,21 @@ impl CategoryTrait for DiscreteCategory {
         Ok(vec![self.get_identity_morphism(object)?])
     }
 
-    fn nested_level() -> usize {
-        1
+    // only identities compose, and only with themselves
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        if first.target_object() != second.source_object() {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        Ok(self.get_identity_morphism(first.source_object())?.clone())
+    }
+
+    // a set of points
+    fn nested_level() -> Option<usize> {
+        Some(1)
     }
 }
 
@@ -189,7 +215,7 @@ impl From<Vec<ObjectId>> for DiscreteCategory {
         let mut category = DiscreteCategory::new();
         for object in objects {
             let object = DiscreteCategory::new_with_id(object);
-            category.add_object(Rc::new(object)).unwrap();
+            category.insert_object(Rc::new(object)).unwrap();
         }
         category
     }
@@ -206,7 +232,7 @@ impl From<Vec<i32>> for DiscreteCategory {
         let mut category = DiscreteCategory::new();
         for object in objects {
             let object = DiscreteCategory::new_with_id(object.into());
-            category.add_object(Rc::new(object)).unwrap();
+            category.insert_object(Rc::new(object)).unwrap();
         }
         category
     }
@@ -228,7 +254,7 @@ impl From<Vec<&str>> for DiscreteCategory {
         let mut category = DiscreteCategory::new();
         for object in objects {
             let object = DiscreteCategory::new_with_id(object.into());
-            category.add_object(Rc::new(object)).unwrap();
+            category.insert_object(Rc::new(object)).unwrap();
         }
         category
     }
@@ -308,4 +334,43 @@ mod tests {
         assert!(cell.source_object().equal_to(&*object3));
         assert!(cell.target_object().equal_to(&*object3));
     }
+
+    #[test]
+    pub fn test_identities_are_built_once() {
+        let labels = DiscreteCategory::from((0..100_000).collect::<Vec<i32>>());
+        let label = DiscreteCategory::new_with_id(ObjectId::Int(42));
+        let identity = labels.get_identity_morphism(&label).unwrap().clone();
+        assert_eq!(identity.arrow_id(), "id(42)");
+        assert!(identity.is_identity());
+        // asked for again, the identity is the same one
+        assert!(Rc::ptr_eq(
+            &identity,
+            labels.get_identity_morphism(&label).unwrap()
+        ));
+        // a copy shares the identities built so far and equals the original
+        let copy = labels.clone();
+        assert!(Rc::ptr_eq(
+            &identity,
+            copy.get_identity_morphism(&label).unwrap()
+        ));
+        assert_eq!(copy, labels);
+        assert_eq!(labels.get_all_objects().unwrap().len(), 100_000);
+    }
+
+    #[test]
+    pub fn test_new_and_new_with_id_agree() {
+        for mut category in [
+            DiscreteCategory::new(),
+            DiscreteCategory::new_with_id(1.into()),
+        ] {
+            assert!(category.get_all_objects().unwrap().is_empty());
+            let object = Rc::new(DiscreteCategory::from("a"));
+            let identity = category.add_object(object.clone()).unwrap();
+            assert_eq!(category.get_identity_morphism(&object).unwrap(), &identity);
+            assert_eq!(
+                category.add_object(object).err(),
+                Some(Errors::ObjectAlreadyExists)
+            );
+        }
+    }
 }
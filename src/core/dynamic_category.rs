@@ -7,6 +7,7 @@ use crate::core::object_id::ObjectId;
 use crate::core::traits::arrow_trait::ArrowTrait;
 use crate::core::traits::category_trait::{CategorySubObjectAlias, CategoryTrait};
 use crate::core::traits::factorization_system_trait::FactorizationSystemTrait;
+use crate::core::traits::finite_limits_trait::{FiniteColimitsTrait, FiniteLimitsTrait};
 use std::any::Any;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
@@ -226,6 +227,19 @@ impl CategoryTrait for DynamicCategory {
     ) -> Result<Vec<&Rc<Morphism<Self::Object>>>, Errors> {
         self.inner_category().get_object_morphisms(object)
     }
+
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        self.inner_category().compose_morphisms(first, second)
+    }
+
+    // the depth is only known at runtime, see level()
+    fn nested_level() -> Option<usize> {
+        None
+    }
 }
 
 impl FactorizationSystemTrait for DynamicCategory {
@@ -243,6 +257,10 @@ impl FactorizationSystemTrait for DynamicCategory {
     }
 }
 
+impl FiniteLimitsTrait for DynamicCategory {}
+
+impl FiniteColimitsTrait for DynamicCategory {}
+
 impl From<ObjectId> for DynamicCategory {
     fn from(value: ObjectId) -> Self {
         DynamicCategory::new_with_id(value)
@@ -10,19 +10,16 @@ use std::fmt::Debug;
 use std::hash::Hash;
 use std::rc::Rc;
 
+// the epic and the monic factor of a morphism
+type Factors<Object> = (Rc<Morphism<Object>>, Rc<Morphism<Object>>);
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EpicMonicCategory<InnerCategory>
 where
     InnerCategory: CategoryTrait + Hash + Eq,
 {
     category: InnerCategory,
-    morphism_factors: HashMap<
-        Rc<Morphism<InnerCategory::Object>>,
-        (
-            Rc<Morphism<InnerCategory::Object>>,
-            Rc<Morphism<InnerCategory::Object>>,
-        ),
-    >,
+    morphism_factors: HashMap<Rc<Morphism<InnerCategory::Object>>, Factors<InnerCategory::Object>>,
 }
 
 impl<InnerCategory> Default for EpicMonicCategory<InnerCategory>
@@ -48,84 +45,154 @@ where
     pub fn category(&self) -> &InnerCategory {
         &self.category
     }
+}
+
+impl<InnerCategory> EpicMonicCategory<InnerCategory>
+where
+    InnerCategory: CategoryTrait<
+            Morphism = Arrow<
+                <InnerCategory as CategoryTrait>::Object,
+                <InnerCategory as CategoryTrait>::Object,
+            >,
+        > + Hash
+        + Eq
+        + Clone
+        + 'static,
+    <InnerCategory as CategoryTrait>::Object: Clone,
+{
+    /*
+    Factorizes f: A -> B through its image, f = m ∘ e, where the image of f is the
+    subcategory of B holding the objects and morphisms f hits and the composites of
+    those morphisms. e sends everything to its image and m includes the image into B.
 
+    When the image holds every object and morphism of B, the image is B itself and f
+    factors as id_B ∘ f.
+     */
     fn factorize(
         &mut self,
-        morphism: &Morphism<InnerCategory::Object>,
-    ) -> Result<(Rc<InnerCategory::Morphism>, Rc<InnerCategory::Morphism>), Errors> {
-        // we factorize to image of f and from image of f to target object
+        morphism: &Rc<Morphism<InnerCategory::Object>>,
+    ) -> Result<Factors<InnerCategory::Object>, Errors> {
         let source_object = morphism.source_object();
         let target_object = morphism.target_object();
-        let mappings = morphism.arrow_mappings();
-
-        let mut all_target_objects = target_object.get_all_objects()?.clone();
 
         let mut image_object = InnerCategory::Object::new();
-        let mut image_mapping = HashMap::new();
-        // possibility the target object is the image of source object
-        let mut target_as_image = false;
-
-        // epic can also be just the target object if all objects are in the image
-        for (source_morphism, target_morphism) in mappings {
-            if source_morphism.is_identity() {
-                if !target_morphism.is_identity() {
-                    return Err(Errors::InvalidArrowNoFunctorFound);
+        // identities first, so that images of morphisms find their endpoints
+        let mut mappings: Vec<_> = morphism.arrow_mappings().iter().collect();
+        mappings.sort_by_key(|(source_morphism, _)| !source_morphism.is_identity());
+        for (source_morphism, target_morphism) in &mappings {
+            if source_morphism.is_identity() && !target_morphism.is_identity() {
+                return Err(Errors::InvalidArrowNoFunctorFound);
+            }
+            for object in [
+                target_morphism.source_object(),
+                target_morphism.target_object(),
+            ] {
+                if image_object.get_object(object).is_err() {
+                    image_object.add_object(object.clone())?;
                 }
-                // remove it from all_target_objects if it exist
-                if target_as_image && all_target_objects.contains(&target_morphism.target_object())
-                {
-                    all_target_objects.remove(&target_morphism.target_object());
-                } else {
-                    target_as_image = false;
+            }
+            if !target_morphism.is_identity()
+                && !image_object
+                    .get_hom_set_x(target_morphism.source_object())?
+                    .contains(target_morphism)
+            {
+                image_object.add_morphism((*target_morphism).clone())?;
+            }
+        }
+        // the composites in B of the morphisms in the image, until there are no new ones
+        loop {
+            let mut composites = Vec::new();
+            for object in image_object.get_all_objects()? {
+                for first in image_object.get_hom_set_x(object)? {
+                    for second in image_object.get_hom_set_x(first.target_object())? {
+                        if first.is_identity() || second.is_identity() {
+                            continue;
+                        }
+                        let composite = target_object.compose_morphisms(first, second)?;
+                        if !image_object
+                            .get_hom_set_x(composite.source_object())?
+                            .contains(&composite)
+                            && !composites.contains(&composite)
+                        {
+                            composites.push(composite);
+                        }
+                    }
                 }
+            }
+            if composites.is_empty() {
+                break;
+            }
+            for composite in composites {
+                image_object.add_morphism(composite)?;
+            }
+        }
 
-                // if object has already been added to image_object
-                if let Ok(object) = image_object.get_object(&**target_morphism.target_object()) {
-                    let target_morphism = image_object.get_identity_morphism(&**object)?;
-                    image_mapping.insert((*source_morphism).clone(), target_morphism.clone());
-                } else {
-                    image_object.add_object(target_morphism.target_object().clone())?;
-                    image_mapping.insert((*source_morphism).clone(), (*target_morphism).clone());
-                }
-            } else {
-                panic!("to implement later")
+        // epic can also be just the target object if all objects and morphisms are in the image
+        let mut target_as_image = true;
+        for object in target_object.get_all_objects()? {
+            let in_image = image_object.get_object(object).is_ok()
+                && target_object
+                    .get_hom_set_x(object)?
+                    .into_iter()
+                    .filter(|target_morphism| !target_morphism.is_identity())
+                    .all(|target_morphism| {
+                        image_object
+                            .get_hom_set_x(object)
+                            .is_ok_and(|hom_set| hom_set.contains(target_morphism))
+                    });
+            if !in_image {
+                target_as_image = false;
+                break;
+            }
+        }
+        if target_as_image {
+            let identity = self.category.get_identity_morphism(target_object)?.clone();
+            return Ok((morphism.clone(), identity));
+        }
+
+        // identities go to the identities of the image, morphisms to themselves
+        let image_morphism = |target_morphism: &Rc<
+            <InnerCategory::Object as CategoryTrait>::Morphism,
+        >|
+         -> Result<_, Errors> {
+            match target_morphism.is_identity() {
+                true => Ok(image_object
+                    .get_identity_morphism(target_morphism.target_object())?
+                    .clone()),
+                false => Ok(target_morphism.clone()),
+            }
+        };
+        let mut epic_mapping = HashMap::new();
+        for (source_morphism, target_morphism) in &mappings {
+            epic_mapping.insert((*source_morphism).clone(), image_morphism(target_morphism)?);
+        }
+
+        let mut monic_mapping = HashMap::new();
+        for object in image_object.get_all_objects()? {
+            for image_morphism in image_object.get_hom_set_x(object)? {
+                let target_morphism = match image_morphism.is_identity() {
+                    true => target_object.get_identity_morphism(object)?.clone(),
+                    false => image_morphism.clone(),
+                };
+                monic_mapping.insert(image_morphism.clone(), target_morphism);
             }
         }
-        // let image_object = if target_as_image && !all_target_objects.is_empty() {
-        //     target_object.clone()
-        // } else {
-        //     let result = Rc::new(image_object);
-        //     self.category.add_object(result.clone())?;
-        //     result
-        // };
 
         // add the image object to the category
         let image_object = Rc::new(image_object);
         self.category.add_object(image_object.clone())?;
-        let epic_morphism = Rc::new(InnerCategory::Morphism::new_with_mappings(
+        let epic_morphism = Rc::new(Arrow::new_with_mappings(
             source_object.clone(),
             image_object.clone(),
-            image_mapping,
+            epic_mapping,
         ));
-
-        // now mapping from image to target object
-        let monic_mapping = {
-            let mut monic_mapping = HashMap::new();
-            for obj in image_object.get_all_objects()? {
-                let source_morphism = image_object.get_identity_morphism(&**obj)?;
-                let target_morphism = target_object.get_identity_morphism(&**obj)?;
-                monic_mapping.insert(source_morphism.clone(), target_morphism.clone());
-            }
-            monic_mapping
-        };
-
-        let monic_morphism = Rc::new(InnerCategory::Morphism::new_with_mappings(
-            image_object.clone(),
+        let monic_morphism = Rc::new(Arrow::new_with_mappings(
+            image_object,
             target_object.clone(),
             monic_mapping,
         ));
-        self.category.add_morphism(epic_morphism.clone());
-        self.category.add_morphism(monic_morphism.clone());
+        let epic_morphism = self.category.add_morphism(epic_morphism)?.clone();
+        let monic_morphism = self.category.add_morphism(monic_morphism)?.clone();
         Ok((epic_morphism, monic_morphism))
     }
 }
@@ -175,10 +242,8 @@ where
         morphism: Rc<Morphism<InnerCategory::Object>>,
     ) -> Result<&Rc<Morphism<InnerCategory::Object>>, Errors> {
         // here we need to factor it to epic and monic morphisms
-        let (epic, monic) = self.factorize(&morphism)?;
-        // self.hash_map.insert(morphism.id().clone(), (epic, monic));
-        self.morphism_factors
-            .insert(morphism.clone(), (epic.into(), monic.into()));
+        let factors = self.factorize(&morphism)?;
+        self.morphism_factors.insert(morphism.clone(), factors);
         self.category.add_morphism(morphism)
     }
 
@@ -207,6 +272,14 @@ where
     ) -> Result<Vec<&Rc<Morphism<InnerCategory::Object>>>, Errors> {
         self.category.get_object_morphisms(object_id)
     }
+
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        self.category.compose_morphisms(first, second)
+    }
 }
 
 impl<InnerCategory> FactorizationSystemTrait for EpicMonicCategory<InnerCategory>
@@ -247,6 +320,7 @@ impl<Object: CategoryTrait + Hash + Eq> Hash for EpicMonicCategory<Object> {
     }
 }
 
+#[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::base_category::BaseCategory;
@@ -263,4 +337,224 @@ mod tests {
             .add_object(object_ab.clone())
             .expect("Failed to add object A");
     }
+
+    fn object_with_morphisms(
+        objects: &[&Rc<DynamicCategory>],
+        morphisms: &[(&str, &Rc<DynamicCategory>, &Rc<DynamicCategory>)],
+    ) -> (Rc<DynamicCategory>, Vec<Rc<Morphism<DynamicCategory>>>) {
+        let mut category = DynamicCategory::new();
+        for object in objects {
+            category.add_object((*object).clone()).unwrap();
+        }
+        let morphisms = morphisms
+            .iter()
+            .map(|(id, source, target)| {
+                let morphism = Rc::new(Arrow::new(
+                    id.to_string(),
+                    (*source).clone(),
+                    (*target).clone(),
+                    HashMap::new(),
+                ));
+                category.add_morphism(morphism).unwrap().clone()
+            })
+            .collect();
+        (Rc::new(category), morphisms)
+    }
+
+    #[test]
+    fn test_factorize_through_image() {
+        let [x, y, p, q, r] =
+            ["x", "y", "p", "q", "r"].map(|id| Rc::new(DynamicCategory::from(id)));
+        let (a, a_morphisms) = object_with_morphisms(&[&x, &y], &[("f", &x, &y)]);
+        let (b, b_morphisms) =
+            object_with_morphisms(&[&p, &q, &r], &[("g", &p, &q), ("h", &q, &r)]);
+        let mut epic_monic_category = EpicMonicCategory::<DynamicCategory>::new();
+        epic_monic_category.add_object(a.clone()).unwrap();
+        epic_monic_category.add_object(b.clone()).unwrap();
+
+        let mappings = HashMap::from([
+            (
+                a.get_identity_morphism(&x).unwrap().clone(),
+                b.get_identity_morphism(&p).unwrap().clone(),
+            ),
+            (
+                a.get_identity_morphism(&y).unwrap().clone(),
+                b.get_identity_morphism(&q).unwrap().clone(),
+            ),
+            (a_morphisms[0].clone(), b_morphisms[0].clone()),
+        ]);
+        let functor = Rc::new(Arrow::new_with_mappings(a.clone(), b.clone(), mappings));
+        epic_monic_category.add_morphism(functor.clone()).unwrap();
+
+        let (epic, monic) = epic_monic_category.morphism_factors(&functor).unwrap();
+        let (epic, monic) = (epic.clone(), monic.clone());
+        let image = epic.target_object();
+        assert!(!Rc::ptr_eq(image, &b));
+        assert!(Rc::ptr_eq(monic.source_object(), image));
+
+        // rival arrows around the image, added without factoring them: the points
+        // p and q of the image and of B, and the collapse of the image onto q
+        let z = Rc::new(DynamicCategory::from("z"));
+        let point: Rc<DynamicCategory> = Rc::new(vec![z.clone()].into());
+        let pick = |target: &Rc<DynamicCategory>, object: &Rc<DynamicCategory>| {
+            Rc::new(Arrow::new_with_mappings(
+                point.clone(),
+                target.clone(),
+                HashMap::from([(
+                    point.get_identity_morphism(&z).unwrap().clone(),
+                    target.get_identity_morphism(object).unwrap().clone(),
+                )]),
+            ))
+        };
+        let id_q = b.get_identity_morphism(&q).unwrap();
+        let collapse = |source: &Rc<DynamicCategory>, morphisms: Vec<_>| {
+            Rc::new(Arrow::new_with_mappings(
+                source.clone(),
+                b.clone(),
+                morphisms
+                    .into_iter()
+                    .map(|morphism| (morphism, id_q.clone()))
+                    .collect(),
+            ))
+        };
+        let morphisms = |category: &DynamicCategory| {
+            let objects = category.get_all_objects().unwrap().into_iter();
+            objects
+                .flat_map(|object| category.get_hom_set_x(object).unwrap())
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let collapse_image = collapse(image, morphisms(image));
+        let inner = &mut epic_monic_category.category;
+        inner.add_object(point.clone()).unwrap();
+        for morphism in [
+            pick(image, &p),
+            pick(image, &q),
+            pick(&b, &p),
+            pick(&b, &q),
+            collapse_image.clone(),
+            collapse(&a, morphisms(&a)),
+        ] {
+            inner.add_morphism(morphism).unwrap();
+        }
+        // the points of the image stay apart in B, the collapse merges them
+        assert!(epic_monic_category.is_mono(&monic).unwrap());
+        assert!(!epic_monic_category.is_mono(&collapse_image).unwrap());
+        // monic and collapse differ on the image and so after the epic
+        assert!(epic_monic_category.is_epi(&epic).unwrap());
+        // the image holds p, q and g but not r and h
+        assert_eq!(image.get_all_objects().unwrap().len(), 2);
+        assert!(image.get_object(&r).is_err());
+        let f_image = epic.map_morphism(&a_morphisms[0]).unwrap();
+        assert!(monic.map_morphism(f_image).unwrap() == &b_morphisms[0]);
+        let id_q = image.get_identity_morphism(&q).unwrap();
+        assert!(monic.map_morphism(id_q).unwrap() == b.get_identity_morphism(&q).unwrap());
+    }
+
+    #[test]
+    fn test_image_is_closed_under_composition() {
+        // p, q, r and s with one element each, g: p -> q, h: q -> r and their composite h∘g
+        let [p, q, r, s] = ["p", "q", "r", "s"].map(|id| {
+            let mut object = DynamicCategory::new_with_id(id.into());
+            let element = DynamicCategory::new_with_id(format!("{}0", id).into());
+            object.add_object(Rc::new(element)).unwrap();
+            Rc::new(object)
+        });
+        let element = |object: &Rc<DynamicCategory>| {
+            let id = format!("{}0", object.category_id());
+            object
+                .get_identity_morphism(&DynamicCategory::new_with_id(id.into()))
+                .unwrap()
+                .clone()
+        };
+        let mut b = DynamicCategory::new();
+        for object in [&p, &q, &r, &s] {
+            b.add_object(object.clone()).unwrap();
+        }
+        let [g, h, hg] =
+            [("g", &p, &q), ("h", &q, &r), ("h∘g", &p, &r)].map(|(id, source, target)| {
+                let morphism = Arrow::new(
+                    id.to_string(),
+                    source.clone(),
+                    target.clone(),
+                    HashMap::from([(element(source), element(target))]),
+                );
+                b.add_morphism(Rc::new(morphism)).unwrap().clone()
+            });
+        let b = Rc::new(b);
+        assert_eq!(b.compose_morphisms(&g, &h).unwrap(), hg);
+
+        // f1: x -> y and f2: w -> z do not compose, but their images g and h do
+        let [x, y, w, z] = ["x", "y", "w", "z"].map(|id| Rc::new(DynamicCategory::from(id)));
+        let (a, a_morphisms) =
+            object_with_morphisms(&[&x, &y, &w, &z], &[("f1", &x, &y), ("f2", &w, &z)]);
+        let mut epic_monic_category = EpicMonicCategory::<DynamicCategory>::new();
+        epic_monic_category.add_object(a.clone()).unwrap();
+        epic_monic_category.add_object(b.clone()).unwrap();
+        let mut mappings: HashMap<_, _> = [(&x, &p), (&y, &q), (&w, &q), (&z, &r)]
+            .map(|(object, image)| {
+                (
+                    a.get_identity_morphism(object).unwrap().clone(),
+                    b.get_identity_morphism(image).unwrap().clone(),
+                )
+            })
+            .into_iter()
+            .collect();
+        mappings.insert(a_morphisms[0].clone(), g.clone());
+        mappings.insert(a_morphisms[1].clone(), h.clone());
+        let functor = Rc::new(Arrow::new_with_mappings(a.clone(), b.clone(), mappings));
+        epic_monic_category.add_morphism(functor.clone()).unwrap();
+
+        let (epic, monic) = epic_monic_category.morphism_factors(&functor).unwrap();
+        let image = epic.target_object();
+        assert!(!Rc::ptr_eq(image, &b));
+        // h∘g is in the image, which leaves s out
+        assert!(image.get_hom_set(&p, &r).unwrap().contains(&hg));
+        assert_eq!(image.compose_morphisms(&g, &h).unwrap(), hg);
+        assert!(image.get_object(&s).is_err());
+        assert_eq!(monic.map_morphism(&hg).unwrap(), &hg);
+        epic.validate_mappings().unwrap();
+        monic.validate_mappings().unwrap();
+    }
+
+    #[test]
+    fn test_factorize_epic_reuses_target() {
+        let [x, y, p, q] = ["x", "y", "p", "q"].map(|id| Rc::new(DynamicCategory::from(id)));
+        let (a, a_morphisms) = object_with_morphisms(&[&x, &y], &[("f", &x, &y)]);
+        let (b, b_morphisms) = object_with_morphisms(&[&p, &q], &[("g", &p, &q)]);
+        let mut epic_monic_category = EpicMonicCategory::<DynamicCategory>::new();
+        epic_monic_category.add_object(a.clone()).unwrap();
+        epic_monic_category.add_object(b.clone()).unwrap();
+
+        let mappings = HashMap::from([
+            (
+                a.get_identity_morphism(&x).unwrap().clone(),
+                b.get_identity_morphism(&p).unwrap().clone(),
+            ),
+            (
+                a.get_identity_morphism(&y).unwrap().clone(),
+                b.get_identity_morphism(&q).unwrap().clone(),
+            ),
+            (a_morphisms[0].clone(), b_morphisms[0].clone()),
+        ]);
+        let functor = Rc::new(Arrow::new_with_mappings(a.clone(), b.clone(), mappings));
+        epic_monic_category.add_morphism(functor.clone()).unwrap();
+
+        let (epic, monic) = epic_monic_category.morphism_factors(&functor).unwrap();
+        assert!(Rc::ptr_eq(epic, &functor));
+        assert!(monic.is_identity() && Rc::ptr_eq(monic.source_object(), &b));
+        // no image object was added
+        assert_eq!(epic_monic_category.get_all_objects().unwrap().len(), 2);
+
+        // an identity cannot go to a non-identity morphism
+        let not_a_functor = Rc::new(Arrow::new_with_mappings(
+            a.clone(),
+            b.clone(),
+            HashMap::from([(
+                a.get_identity_morphism(&x).unwrap().clone(),
+                b_morphisms[0].clone(),
+            )]),
+        ));
+        assert!(epic_monic_category.add_morphism(not_a_functor).is_err());
+    }
 }
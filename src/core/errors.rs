@@ -1,3 +1,5 @@
+use crate::core::object_id::ObjectId;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Errors {
     InvalidOperation(String),
@@ -6,7 +8,8 @@ pub enum Errors {
     OnlyIdentityMorphismDiscreteCategory,
     InvalidMorphismComposition,
     InvalidMorphismCommutation,
-    ObjectNotFound,
+    // the id of the object that could not be found
+    ObjectNotFound(ObjectId),
     InvalidObjectId,
     InvalidObjectMapping,
     InvalidCellMapping,
@@ -26,4 +29,13 @@ pub enum Errors {
     InvalidDynamicType(String),
     InvalidMorphismCoercion,
     ConversionError,
+    InvalidNaturalTransformation(String),
+    InvalidDiagram,
+    LimitNotFound,
+    ColimitNotFound,
+    InvalidMonoidalStructure(String),
+    InvalidStringDiagram(String),
+    InvalidCell(String),
+    InvalidAdjunction(String),
+    InvalidMonoid(String),
 }
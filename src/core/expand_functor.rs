@@ -1,101 +1,397 @@
-use crate::core::arrow::{Arrow, Functor, Morphism};
+/*
+Expanding a category of categories into the category of their sub-objects.
+
+Every object X of the category is a category itself, and the expansion has the objects of
+every X as its objects, sub-objects with the same id being shared. A morphism f: X -> Y
+sends each sub-object x of X to f(x), and is carried to the expansion as the morphisms
+f(x): x -> f(x), named after f and x, with g(f(x)) ∘ f(x) recorded as (g ∘ f)(x).
+
+The i-th section picks the i-th sub-object of every X, in the order of their ids, or the
+first one when X has fewer. It is a functor into the expansion when every f: X -> Y sends
+the pick of X to the pick of Y and the mappings validate, otherwise there is no section at
+index i.
+
+Example, words over the alphabet [a, b] (see MonoidCategory::truncated_free_monoid):
+prefixes [e, a] times the letters [a, b] are the words of the free monoid
+
+    e·a = a, e·b = b, a·a = aa, a·b = ab
+
+so expanding the prefixes e = [a, b] and a = [aa, ab] gives the objects a, b, aa and ab,
+one section picking the first letter (e ↦ a, a ↦ aa) and one the second (e ↦ b, a ↦ ab).
+ */
+use crate::core::arrow::{Functor, Morphism};
+use crate::core::base_category::BaseCategory;
 use crate::core::errors::Errors;
-use crate::core::identifier::Identifier;
+use crate::core::traits::arrow_trait::ArrowTrait;
 use crate::core::traits::category_trait::CategoryTrait;
-use std::borrow::Borrow;
-use std::collections::{HashMap, HashSet};
+use crate::core::utils::{sorted_morphisms, sorted_objects};
+use dyn_clone::DynClone;
+use std::collections::HashMap;
 use std::hash::Hash;
 use std::rc::Rc;
 
-pub fn expand_functor<
-    SubObject: CategoryTrait + Eq + Hash,
-    Object: CategoryTrait<Object = SubObject> + Eq + Hash + From<Rc<SubObject>>,
-    Category: CategoryTrait<Object = Object> + Eq + Hash,
->(
+// the expansion together with its sections, the i-th one picking the i-th sub-objects, None
+// when that pick is not a functor
+pub type Expansion<Category, Object> = (
+    Rc<BaseCategory<Object>>,
+    Vec<Option<Functor<Category, BaseCategory<Object>>>>,
+);
+
+pub fn expand_functor<SubObject, Object, Category>(
     category: &Rc<Category>,
-) -> Result<HashSet<Functor<Category, Category>>, Errors> {
-    //  /*
-    //  This expands each object in a category to its own object.
-    //
-    //  Example
-    //  prefix: [a, e]
-    //
-    //  alphabet: [a,b]
-    //
-    //  Applying product of prefix and alphabet:
-    //  a -> a*a
-    //  result category functor should result in same numer of objects.
-    //  source: [e, a, ab]
-    //
-    // target should have three objects so it will have two objects
-    // target: [
-    //      [a, b],
-    //      [aa, ab]
-    //      [ba, bb]
-    // ]
-    //
-    //
-    // To expand is to create a functor.
-    // treat category as initial
-    //
-    //
-    //   */
-    let mut expanded_category = Category::new();
+) -> Result<Expansion<Category, Object>, Errors>
+where
+    SubObject: CategoryTrait,
+    Object: CategoryTrait<Object = SubObject> + Eq + Hash + DynClone + Clone + From<Rc<SubObject>>,
+    Category: CategoryTrait<Object = Object, Morphism = Morphism<Object>>,
+{
+    let objects = sorted_objects(category.get_all_objects()?);
+    let mut expanded_category = BaseCategory::<Object>::new();
+    // the sub-objects of every object, as objects of the expansion
     let mut object_mappings = HashMap::new();
     let mut max_functor = 0;
-    for (index, object) in category.get_all_objects()?.into_iter().enumerate() {
-        for sub_object in object.get_all_objects()? {
-            let current_mapping = object_mappings.entry(object).or_insert_with(Vec::new);
-            // convert sub_object to Object
-            let new_object =
-                Object::try_from(sub_object.clone()).map_err(|_| Errors::ConversionError)?;
-            match expanded_category.get_object(&new_object) {
-                Ok(_) => continue, // object already exists
-                Err(Errors::ObjectNotFound) => {
-                    let new_object = Rc::new(new_object);
-                    // object does not exist, we can add it
+    for object in &objects {
+        let mut sub_objects = Vec::new();
+        for sub_object in sorted_objects(object.get_all_objects()?) {
+            let new_object = Rc::new(Object::from(sub_object.clone()));
+            let new_object = match expanded_category.get_object(&new_object) {
+                Ok(existing) => existing.clone(),
+                Err(Errors::ObjectNotFound(_)) => {
                     expanded_category.add_object(new_object.clone())?;
-                    current_mapping.push(new_object.clone());
-                    new_object // return the newly added object
+                    new_object
                 }
-                Err(e) => return Err(e), // some other error occurred
+                Err(e) => return Err(e),
             };
+            sub_objects.push(new_object);
         }
-        let current_count = object_mappings.len();
-        if current_count > max_functor {
-            max_functor = current_count;
-        }
+        max_functor = max_functor.max(sub_objects.len());
+        object_mappings.insert(object.category_id().clone(), sub_objects);
     }
 
-    let expanded_category = Rc::new(expanded_category);
-
-    let mut functor_mapping = HashMap::new();
+    // f(x): x -> f(x) for every f: X -> Y and sub-object x of X
+    let mut carried_morphisms = HashMap::new();
+    for object in &objects {
+        for morphism in sorted_morphisms(category.get_hom_set_x(object)?) {
+            if morphism.is_identity() {
+                continue;
+            }
+            for sub_object in sorted_objects(object.get_all_objects()?) {
+                let image = morphism.map_object(sub_object)?;
+                let source = expanded_category
+                    .get_object(&Object::from(sub_object.clone()))?
+                    .clone();
+                let target = expanded_category
+                    .get_object(&Object::from(image.clone()))?
+                    .clone();
+                let carried = Rc::new(Morphism::new(
+                    format!("{}({})", morphism.arrow_id(), sub_object.category_id()),
+                    source,
+                    target,
+                    HashMap::new(),
+                ));
+                expanded_category.add_morphism(carried.clone())?;
+                carried_morphisms.insert(
+                    (
+                        morphism.arrow_id().clone(),
+                        sub_object.category_id().clone(),
+                    ),
+                    carried,
+                );
+            }
+        }
+    }
 
-    for (object, sub_objects) in object_mappings {
-        let identity_morphism = category.get_identity_morphism(object)?;
-        for index in 0..max_functor {
-            let current_mapping = functor_mapping.entry(index).or_insert_with(HashMap::new);
-            let mapped_object = if sub_objects.len() <= index {
-                sub_objects.get(index).ok_or(Errors::ObjectNotFound)?
-            } else {
-                sub_objects.first().ok_or(Errors::ObjectNotFound)?
-            };
-            let mapped_object_identity = expanded_category.get_identity_morphism(mapped_object)?;
-            current_mapping.insert(identity_morphism.clone(), mapped_object_identity.clone());
+    // g(f(x)) ∘ f(x) = (g ∘ f)(x), or the identity of x when g ∘ f is an identity
+    for object in &objects {
+        for first in sorted_morphisms(category.get_hom_set_x(object)?) {
+            if first.is_identity() {
+                continue;
+            }
+            for second in sorted_morphisms(category.get_hom_set_x(first.target_object())?) {
+                if second.is_identity() {
+                    continue;
+                }
+                let composite = category.compose_morphisms(first, second)?;
+                for sub_object in sorted_objects(object.get_all_objects()?) {
+                    let carried = |morphism: &Morphism<Object>, sub_object: &SubObject| {
+                        let key = (
+                            morphism.arrow_id().clone(),
+                            sub_object.category_id().clone(),
+                        );
+                        carried_morphisms
+                            .get(&key)
+                            .map(|carried| carried.arrow_id().clone())
+                            .ok_or(Errors::MorphismNotFound)
+                    };
+                    let image = first.map_object(sub_object)?;
+                    let composite_id = if composite.is_identity() {
+                        expanded_category
+                            .get_identity_morphism(&Object::from(sub_object.clone()))?
+                            .arrow_id()
+                            .clone()
+                    } else {
+                        carried(&composite, sub_object)?
+                    };
+                    expanded_category.add_composition(
+                        &carried(first, sub_object)?,
+                        &carried(second, image)?,
+                        &composite_id,
+                    )?;
+                }
+            }
         }
     }
 
-    let mut functors = HashSet::new();
-    for (index, mapping) in functor_mapping {
+    let expanded_category = Rc::new(expanded_category);
+    let mut functors = Vec::new();
+    'sections: for index in 0..max_functor {
+        let pick = |object: &Object| -> Result<&Rc<Object>, Errors> {
+            let sub_objects: &Vec<Rc<Object>> = &object_mappings[object.category_id()];
+            sub_objects
+                .get(index)
+                .or_else(|| sub_objects.first())
+                .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))
+        };
+        let mut mappings = HashMap::new();
+        for object in &objects {
+            for morphism in sorted_morphisms(category.get_hom_set_x(object)?) {
+                let source = pick(morphism.source_object())?;
+                let mapped = if morphism.is_identity() {
+                    expanded_category.get_identity_morphism(source)?
+                } else {
+                    let key = (morphism.arrow_id().clone(), source.category_id().clone());
+                    match carried_morphisms.get(&key) {
+                        Some(carried)
+                            if carried.target_object() == pick(morphism.target_object())? =>
+                        {
+                            carried
+                        }
+                        // f does not send the pick of X to the pick of Y
+                        _ => {
+                            functors.push(None);
+                            continue 'sections;
+                        }
+                    }
+                };
+                mappings.insert(morphism.clone(), mapped.clone());
+            }
+        }
         let functor = Functor::new(
-            String::generate(),
+            format!("s{}", index),
             category.clone(),
             expanded_category.clone(),
-            mapping,
+            mappings,
+        );
+        match functor.validate_mappings() {
+            Ok(()) => functors.push(Some(functor)),
+            Err(Errors::InvalidFunctor(_)) => functors.push(None),
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok((expanded_category, functors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::base_category::BaseCategory;
+    use crate::core::discrete_category::DiscreteCategory;
+    use crate::core::monoid_category::MonoidCategory;
+    use crate::core::object_id::ObjectId;
+
+    // the prefixes e and a, each expanded to the words prefix·letter
+    fn prefixes() -> (
+        BaseCategory<DiscreteCategory>,
+        Rc<DiscreteCategory>,
+        Rc<DiscreteCategory>,
+    ) {
+        let words = MonoidCategory::truncated_free_monoid("words", &["a", "b"], 2).unwrap();
+        let mut category = BaseCategory::new_with_id("prefixes".into());
+        let mut expand = |prefix: &ObjectId| {
+            let mut object = DiscreteCategory::new_with_id(prefix.clone());
+            for letter in ["a", "b"] {
+                let word = words.multiply(prefix, &letter.into()).unwrap();
+                object
+                    .add_object(Rc::new(DiscreteCategory::new_with_id(word.clone())))
+                    .unwrap();
+            }
+            let object = Rc::new(object);
+            category.add_object(object.clone()).unwrap();
+            object
+        };
+        let empty = expand(words.unit());
+        let a = expand(&"a".into());
+        (category, empty, a)
+    }
+
+    // the map sending each sub-object of the source to the one of the target as in the table,
+    // e.g. a prefix map e -> a sending words to words
+    fn prefix_map(
+        id: &str,
+        source: &Rc<DiscreteCategory>,
+        target: &Rc<DiscreteCategory>,
+        table: &[(&str, &str)],
+    ) -> Rc<Morphism<DiscreteCategory>> {
+        let mappings = table
+            .iter()
+            .map(|(word, image)| {
+                (
+                    source
+                        .get_identity_morphism(&(*word).into())
+                        .unwrap()
+                        .clone(),
+                    target
+                        .get_identity_morphism(&(*image).into())
+                        .unwrap()
+                        .clone(),
+                )
+            })
+            .collect();
+        Rc::new(Morphism::new(
+            id.to_string(),
+            source.clone(),
+            target.clone(),
+            mappings,
+        ))
+    }
+
+    fn picks(
+        functor: &Functor<BaseCategory<DiscreteCategory>, BaseCategory<DiscreteCategory>>,
+        objects: &[&Rc<DiscreteCategory>],
+    ) -> Vec<ObjectId> {
+        objects
+            .iter()
+            .map(|object| functor.map_object(object).unwrap().category_id().clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_prefixes_times_letters() {
+        let (category, empty, a) = prefixes();
+        let (expanded, sections) = expand_functor(&Rc::new(category)).unwrap();
+
+        let mut ids = expanded
+            .get_all_objects()
+            .unwrap()
+            .into_iter()
+            .map(|object| object.category_id().to_string())
+            .collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(ids, vec!["a", "aa", "ab", "b"]);
+
+        // one section per letter
+        let sections: Vec<_> = sections.into_iter().map(Option::unwrap).collect();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(
+            picks(&sections[0], &[&empty, &a]),
+            vec!["a".into(), ObjectId::from("aa")]
+        );
+        assert_eq!(
+            picks(&sections[1], &[&empty, &a]),
+            vec!["b".into(), ObjectId::from("ab")]
         );
-        functors.insert(functor);
+        for section in &sections {
+            section.validate_mappings().unwrap();
+        }
     }
 
-    Ok(functors)
-    // todo!()
+    #[test]
+    fn test_morphisms_are_carried() {
+        let (mut category, empty, a) = prefixes();
+        // prepending a sends a to aa and b to ab
+        let prepend = prefix_map("prepend", &empty, &a, &[("a", "aa"), ("b", "ab")]);
+        category.add_morphism(prepend.clone()).unwrap();
+        let (expanded, sections) = expand_functor(&Rc::new(category)).unwrap();
+
+        let sections: Vec<_> = sections.into_iter().map(Option::unwrap).collect();
+        assert_eq!(sections.len(), 2);
+        let carried = sections[1].map_morphism(&prepend).unwrap();
+        assert_eq!(carried.arrow_id(), "prepend(b)");
+        assert_eq!(carried.source_object().category_id(), &ObjectId::from("b"));
+        assert_eq!(carried.target_object().category_id(), &ObjectId::from("ab"));
+        let b = expanded.get_object(&"b".into()).unwrap();
+        assert_eq!(expanded.get_hom_set_x(b).unwrap().len(), 2);
+        for section in &sections {
+            section.validate_mappings().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_sections_which_are_not_functors_are_none() {
+        let (mut category, empty, a) = prefixes();
+        // a ↦ ab and b ↦ aa cross the picks of both letters
+        let crossed = prefix_map("crossed", &empty, &a, &[("a", "ab"), ("b", "aa")]);
+        category.add_morphism(crossed).unwrap();
+        let (expanded, sections) = expand_functor(&Rc::new(category)).unwrap();
+        assert!(sections.iter().all(Option::is_none));
+        assert_eq!(sections.len(), 2);
+        // the morphisms are still carried
+        let a = expanded.get_object(&"a".into()).unwrap();
+        assert_eq!(expanded.get_hom_set_x(a).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_sections_keep_their_index() {
+        let (mut category, empty, a) = prefixes();
+        // both letters go to aa, which only the first section picks
+        let first = prefix_map("first", &empty, &a, &[("a", "aa"), ("b", "aa")]);
+        category.add_morphism(first.clone()).unwrap();
+        let (_, sections) = expand_functor(&Rc::new(category)).unwrap();
+
+        assert_eq!(sections.len(), 2);
+        assert!(sections[0].is_some());
+        assert!(sections[1].is_none());
+        let section = sections[0].as_ref().unwrap();
+        section.validate_mappings().unwrap();
+        assert_eq!(section.map_morphism(&first).unwrap().arrow_id(), "first(a)");
+    }
+
+    #[test]
+    fn test_composites_are_carried() {
+        let mut category = BaseCategory::new_with_id("chain".into());
+        let mut add = |id: &str, elements: [&str; 2]| {
+            let mut object = DiscreteCategory::new_with_id(id.into());
+            for element in elements {
+                object
+                    .add_object(Rc::new(DiscreteCategory::new_with_id(element.into())))
+                    .unwrap();
+            }
+            let object = Rc::new(object);
+            category.add_object(object.clone()).unwrap();
+            object
+        };
+        let x = add("X", ["x1", "x2"]);
+        let y = add("Y", ["y1", "y2"]);
+        let z = add("Z", ["z1", "z2"]);
+        // X -> Y -> Z with g ∘ f = h, and k acting as h but not a composite
+        let f = prefix_map("f", &x, &y, &[("x1", "y1"), ("x2", "y2")]);
+        let g = prefix_map("g", &y, &z, &[("y1", "z1"), ("y2", "z2")]);
+        let h = prefix_map("h", &x, &z, &[("x1", "z1"), ("x2", "z2")]);
+        let k = prefix_map("k", &x, &z, &[("x1", "z1"), ("x2", "z2")]);
+        for morphism in [&f, &g, &h, &k] {
+            category.add_morphism(morphism.clone()).unwrap();
+        }
+        category.add_composition("f", "g", "h").unwrap();
+        let (expanded, sections) = expand_functor(&Rc::new(category)).unwrap();
+
+        // g(y1) ∘ f(x1) = h(x1) in the expansion
+        let composite = expanded
+            .compose_morphisms(
+                expanded.morphism("f(x1)").unwrap(),
+                expanded.morphism("g(y1)").unwrap(),
+            )
+            .unwrap();
+        assert_eq!(composite.arrow_id(), "h(x1)");
+        // so both sections preserve the composite
+        assert_eq!(sections.len(), 2);
+        for (index, section) in sections.iter().enumerate() {
+            let section = section.as_ref().unwrap();
+            section.validate_mappings().unwrap();
+            assert_eq!(
+                section.map_morphism(&h).unwrap().arrow_id(),
+                &format!("h(x{})", index + 1)
+            );
+        }
+    }
 }
@@ -0,0 +1,1012 @@
+/*
+Verifying orthogonal factorization systems on finite categories.
+
+An orthogonal factorization system (E, M) is a pair of classes of morphisms such that
+    - E and M contain all isomorphisms and are closed under composition
+    - every morphism f factors as f = m ∘ e with e in E and m in M
+    - every e in E is orthogonal to every m in M: for each commutative square
+
+            u
+        A ----> X
+        |       |
+      e |       | m
+        v       v
+        B ----> Y
+            v
+
+      with m ∘ u = v ∘ e there is exactly one diagonal d: B -> X with d ∘ e = u and m ∘ d = v.
+
+The classes are given as predicates, so they can be declared by a property (epic, monic)
+as well as by listing their members. The checks run in the order above over the morphisms
+sorted by id, the first failure found is returned.
+ */
+use crate::core::errors::Errors;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::traits::factorization_system_trait::FactorizationSystemTrait;
+use crate::core::utils::{sorted_morphisms, sorted_objects};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FactorizationClass {
+    Left,
+    Right,
+}
+
+// membership of a morphism in E or M
+type ClassPredicate<'a, Category> = dyn Fn(&Rc<<Category as CategoryTrait>::Morphism>) -> bool + 'a;
+
+// a commutative square m ∘ top = bottom ∘ e to be filled with a diagonal
+#[derive(Debug)]
+pub struct LiftingSquare<Category: CategoryTrait> {
+    pub top: Rc<Category::Morphism>,
+    pub left: Rc<Category::Morphism>,
+    pub right: Rc<Category::Morphism>,
+    pub bottom: Rc<Category::Morphism>,
+}
+
+#[derive(Debug)]
+pub enum FactorizationSystemResult<Category: CategoryTrait> {
+    Valid,
+    IsomorphismMissing(FactorizationClass, Rc<Category::Morphism>),
+    // the composite second ∘ first left the class
+    NotClosedUnderComposition(
+        FactorizationClass,
+        Rc<Category::Morphism>,
+        Rc<Category::Morphism>,
+    ),
+    NoFactorization(Rc<Category::Morphism>),
+    // the diagonals found for the square, none or more than one
+    NoUniqueDiagonal(LiftingSquare<Category>, Vec<Rc<Category::Morphism>>),
+    // the square has no diagonal at all
+    NoDiagonal(LiftingSquare<Category>),
+    // the morphism has the lifting property of the class without belonging to it
+    NotLiftingClass(FactorizationClass, Rc<Category::Morphism>),
+}
+
+impl<Category: CategoryTrait> FactorizationSystemResult<Category> {
+    pub fn is_valid(&self) -> bool {
+        matches!(self, FactorizationSystemResult::Valid)
+    }
+}
+
+// all morphisms of the category ordered by source object and id
+pub fn all_morphisms<Category: CategoryTrait>(
+    category: &Category,
+) -> Result<Vec<Rc<Category::Morphism>>, Errors> {
+    let mut morphisms = Vec::new();
+    for object in sorted_objects(category.get_all_objects()?) {
+        morphisms.extend(
+            sorted_morphisms(category.get_hom_set_x(object)?)
+                .into_iter()
+                .cloned(),
+        );
+    }
+    Ok(morphisms)
+}
+
+// the diagonals d: B -> X of the square with d ∘ left = top and right ∘ d = bottom, by id
+fn diagonals<'a, Category: CategoryTrait>(
+    category: &'a Category,
+    square: &'a LiftingSquare<Category>,
+) -> Result<impl Iterator<Item = Result<Rc<Category::Morphism>, Errors>> + 'a, Errors> {
+    let candidates =
+        category.get_hom_set(square.left.target_object(), square.right.source_object())?;
+    let fills = move |diagonal: &Rc<Category::Morphism>| -> Result<bool, Errors> {
+        Ok(
+            category.compose_morphisms(&square.left, diagonal)? == square.top
+                && category.compose_morphisms(diagonal, &square.right)? == square.bottom,
+        )
+    };
+    Ok(sorted_morphisms(candidates)
+        .into_iter()
+        .filter_map(move |diagonal| match fills(diagonal) {
+            Ok(true) => Some(Ok(diagonal.clone())),
+            Ok(false) => None,
+            Err(error) => Some(Err(error)),
+        }))
+}
+
+// all diagonals of the square
+pub fn diagonal_fillers<Category: CategoryTrait>(
+    category: &Category,
+    square: &LiftingSquare<Category>,
+) -> Result<Vec<Rc<Category::Morphism>>, Errors> {
+    diagonals(category, square)?.collect()
+}
+
+// the commutative squares from left to right
+pub fn lifting_squares<Category: CategoryTrait>(
+    category: &Category,
+    left: &Rc<Category::Morphism>,
+    right: &Rc<Category::Morphism>,
+) -> Result<Vec<LiftingSquare<Category>>, Errors> {
+    let mut squares = Vec::new();
+    let tops = category.get_hom_set(left.source_object(), right.source_object())?;
+    let bottoms = category.get_hom_set(left.target_object(), right.target_object())?;
+    let bottoms = sorted_morphisms(bottoms);
+    for top in sorted_morphisms(tops) {
+        let upper_path = category.compose_morphisms(top, right)?;
+        for bottom in &bottoms {
+            if category.compose_morphisms(left, bottom)? == upper_path {
+                squares.push(LiftingSquare {
+                    top: top.clone(),
+                    left: left.clone(),
+                    right: right.clone(),
+                    bottom: (*bottom).clone(),
+                });
+            }
+        }
+    }
+    Ok(squares)
+}
+
+// the first morphism whose factors are missing, outside the classes or do not compose to it
+fn first_unfactored<Category, Factors>(
+    category: &Category,
+    morphisms: &[Rc<Category::Morphism>],
+    in_left: &ClassPredicate<Category>,
+    in_right: &ClassPredicate<Category>,
+    factors: Factors,
+) -> Result<Option<Rc<Category::Morphism>>, Errors>
+where
+    Category: CategoryTrait,
+    Factors:
+        Fn(&Rc<Category::Morphism>) -> Option<(Rc<Category::Morphism>, Rc<Category::Morphism>)>,
+{
+    for morphism in morphisms {
+        let factorizes = match factors(morphism) {
+            Some((left, right)) => {
+                in_left(&left)
+                    && in_right(&right)
+                    && left.target_object() == right.source_object()
+                    && left.source_object() == morphism.source_object()
+                    && right.target_object() == morphism.target_object()
+                    && category.compose_morphisms(&left, &right)? == *morphism
+            }
+            None => false,
+        };
+        if !factorizes {
+            return Ok(Some(morphism.clone()));
+        }
+    }
+    Ok(None)
+}
+
+// a diagonal of the square, None when the search over all candidates found none
+pub fn find_lifting<Category: CategoryTrait>(
+    category: &Category,
+    square: &LiftingSquare<Category>,
+) -> Result<Option<Rc<Category::Morphism>>, Errors> {
+    diagonals(category, square)?.next().transpose()
+}
+
+// the first square from left to right without a diagonal
+pub fn first_square_without_lifting<Category: CategoryTrait>(
+    category: &Category,
+    left: &Rc<Category::Morphism>,
+    right: &Rc<Category::Morphism>,
+) -> Result<Option<LiftingSquare<Category>>, Errors> {
+    for square in lifting_squares(category, left, right)? {
+        if find_lifting(category, &square)?.is_none() {
+            return Ok(Some(square));
+        }
+    }
+    Ok(None)
+}
+
+pub fn verify_orthogonal_factorization_system<Category, Factors>(
+    category: &Category,
+    in_left: impl Fn(&Rc<Category::Morphism>) -> bool,
+    in_right: impl Fn(&Rc<Category::Morphism>) -> bool,
+    factors: Factors,
+) -> Result<FactorizationSystemResult<Category>, Errors>
+where
+    Category: CategoryTrait,
+    Factors:
+        Fn(&Rc<Category::Morphism>) -> Option<(Rc<Category::Morphism>, Rc<Category::Morphism>)>,
+{
+    let morphisms = all_morphisms(category)?;
+    let classes: [(FactorizationClass, &ClassPredicate<Category>); 2] = [
+        (FactorizationClass::Left, &in_left),
+        (FactorizationClass::Right, &in_right),
+    ];
+
+    for (class, contains) in classes {
+        for morphism in &morphisms {
+            if !contains(morphism) && category.inverse_morphism(morphism)?.is_some() {
+                return Ok(FactorizationSystemResult::IsomorphismMissing(
+                    class,
+                    morphism.clone(),
+                ));
+            }
+        }
+    }
+
+    for (class, contains) in classes {
+        let members: Vec<_> = morphisms
+            .iter()
+            .filter(|morphism| contains(morphism))
+            .collect();
+        for first in &members {
+            for second in &members {
+                if first.target_object() != second.source_object() {
+                    continue;
+                }
+                if !contains(&category.compose_morphisms(first, second)?) {
+                    return Ok(FactorizationSystemResult::NotClosedUnderComposition(
+                        class,
+                        (*first).clone(),
+                        (*second).clone(),
+                    ));
+                }
+            }
+        }
+    }
+
+    if let Some(morphism) = first_unfactored(category, &morphisms, &in_left, &in_right, factors)? {
+        return Ok(FactorizationSystemResult::NoFactorization(morphism));
+    }
+
+    for left in morphisms.iter().filter(|morphism| in_left(morphism)) {
+        for right in morphisms.iter().filter(|morphism| in_right(morphism)) {
+            for square in lifting_squares(category, left, right)? {
+                let fillers = diagonal_fillers(category, &square)?;
+                if fillers.len() != 1 {
+                    return Ok(FactorizationSystemResult::NoUniqueDiagonal(square, fillers));
+                }
+            }
+        }
+    }
+    Ok(FactorizationSystemResult::Valid)
+}
+
+/*
+A weak factorization system (L, R) asks for diagonals to exist but not to be unique.
+Instead of isomorphisms and composition the classes are checked to be each other's
+lifting classes: L holds exactly the morphisms lifting against all of R and R exactly
+those L lifts against.
+ */
+pub fn verify_weak_factorization_system<Category, Factors>(
+    category: &Category,
+    in_left: impl Fn(&Rc<Category::Morphism>) -> bool,
+    in_right: impl Fn(&Rc<Category::Morphism>) -> bool,
+    factors: Factors,
+) -> Result<FactorizationSystemResult<Category>, Errors>
+where
+    Category: CategoryTrait,
+    Factors:
+        Fn(&Rc<Category::Morphism>) -> Option<(Rc<Category::Morphism>, Rc<Category::Morphism>)>,
+{
+    let morphisms = all_morphisms(category)?;
+    if let Some(morphism) = first_unfactored(category, &morphisms, &in_left, &in_right, factors)? {
+        return Ok(FactorizationSystemResult::NoFactorization(morphism));
+    }
+
+    let left_class: Vec<_> = morphisms
+        .iter()
+        .filter(|morphism| in_left(morphism))
+        .collect();
+    let right_class: Vec<_> = morphisms
+        .iter()
+        .filter(|morphism| in_right(morphism))
+        .collect();
+    for left in &left_class {
+        for right in &right_class {
+            if let Some(square) = first_square_without_lifting(category, left, right)? {
+                return Ok(FactorizationSystemResult::NoDiagonal(square));
+            }
+        }
+    }
+
+    for morphism in &morphisms {
+        if !in_left(morphism) && lifts_against(category, morphism, &right_class, true)? {
+            return Ok(FactorizationSystemResult::NotLiftingClass(
+                FactorizationClass::Left,
+                morphism.clone(),
+            ));
+        }
+        if !in_right(morphism) && lifts_against(category, morphism, &left_class, false)? {
+            return Ok(FactorizationSystemResult::NotLiftingClass(
+                FactorizationClass::Right,
+                morphism.clone(),
+            ));
+        }
+    }
+    Ok(FactorizationSystemResult::Valid)
+}
+
+// whether the morphism lifts against all others, on the left or on the right side of the squares
+fn lifts_against<Category: CategoryTrait>(
+    category: &Category,
+    morphism: &Rc<Category::Morphism>,
+    others: &[&Rc<Category::Morphism>],
+    on_left: bool,
+) -> Result<bool, Errors> {
+    for other in others {
+        let (left, right) = match on_left {
+            true => (morphism, *other),
+            false => (*other, morphism),
+        };
+        if first_square_without_lifting(category, left, right)?.is_some() {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrivialFactorization {
+    // f = f ∘ id
+    IsoAll,
+    // f = id ∘ f
+    AllIso,
+}
+
+// a category with one of the trivial factorization systems (iso, all) or (all, iso)
+#[derive(Debug)]
+pub struct TrivialFactorizationCategory<Inner: CategoryTrait> {
+    category: Inner,
+    kind: TrivialFactorization,
+    isomorphisms: HashSet<Rc<Inner::Morphism>>,
+    morphism_factors: HashMap<Rc<Inner::Morphism>, Factors<Inner>>,
+}
+
+type Factors<Category> = (
+    Rc<<Category as CategoryTrait>::Morphism>,
+    Rc<<Category as CategoryTrait>::Morphism>,
+);
+
+impl<Inner: CategoryTrait + Clone> Clone for TrivialFactorizationCategory<Inner> {
+    fn clone(&self) -> Self {
+        TrivialFactorizationCategory {
+            category: self.category.clone(),
+            kind: self.kind,
+            isomorphisms: self.isomorphisms.clone(),
+            morphism_factors: self.morphism_factors.clone(),
+        }
+    }
+}
+
+impl<Inner: CategoryTrait + Clone> TrivialFactorizationCategory<Inner> {
+    pub fn from_category(category: Inner, kind: TrivialFactorization) -> Result<Self, Errors> {
+        let mut result = TrivialFactorizationCategory {
+            category,
+            kind,
+            isomorphisms: HashSet::new(),
+            morphism_factors: HashMap::new(),
+        };
+        for morphism in all_morphisms(&result.category)? {
+            result.factorize(morphism)?;
+        }
+        Ok(result)
+    }
+
+    pub fn category(&self) -> &Inner {
+        &self.category
+    }
+
+    pub fn kind(&self) -> TrivialFactorization {
+        self.kind
+    }
+
+    pub fn in_left(&self, morphism: &Rc<Inner::Morphism>) -> bool {
+        match self.kind {
+            TrivialFactorization::IsoAll => self.isomorphisms.contains(morphism),
+            TrivialFactorization::AllIso => true,
+        }
+    }
+
+    pub fn in_right(&self, morphism: &Rc<Inner::Morphism>) -> bool {
+        match self.kind {
+            TrivialFactorization::IsoAll => true,
+            TrivialFactorization::AllIso => self.isomorphisms.contains(morphism),
+        }
+    }
+
+    // a morphism becomes invertible together with its inverse, which may be added before it
+    fn factorize(&mut self, morphism: Rc<Inner::Morphism>) -> Result<(), Errors> {
+        if let Some(inverse) = self.category.inverse_morphism(&morphism)? {
+            self.isomorphisms.insert(inverse);
+            self.isomorphisms.insert(morphism.clone());
+        }
+        let factors = match self.kind {
+            TrivialFactorization::IsoAll => (
+                self.category
+                    .get_identity_morphism(morphism.source_object())?
+                    .clone(),
+                morphism.clone(),
+            ),
+            TrivialFactorization::AllIso => (
+                morphism.clone(),
+                self.category
+                    .get_identity_morphism(morphism.target_object())?
+                    .clone(),
+            ),
+        };
+        self.morphism_factors.insert(morphism, factors);
+        Ok(())
+    }
+}
+
+impl<Inner: CategoryTrait + Clone> CategoryTrait for TrivialFactorizationCategory<Inner> {
+    type Object = Inner::Object;
+
+    type Morphism = Inner::Morphism;
+
+    fn new() -> Self {
+        TrivialFactorizationCategory::from_category(Inner::new(), TrivialFactorization::IsoAll)
+            .expect("the identities of a category factor it")
+    }
+
+    fn new_with_id(id: &ObjectId) -> Self {
+        TrivialFactorizationCategory::from_category(
+            Inner::new_with_id(id),
+            TrivialFactorization::IsoAll,
+        )
+        .expect("the identities of a category factor it")
+    }
+
+    fn category_id(&self) -> &ObjectId {
+        self.category.category_id()
+    }
+
+    fn update_category_id(&mut self, new_id: ObjectId) {
+        self.category.update_category_id(new_id);
+    }
+
+    fn add_object(&mut self, object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        let identity = self.category.add_object(object)?;
+        self.factorize(identity.clone())?;
+        Ok(identity)
+    }
+
+    fn add_morphism(
+        &mut self,
+        morphism: Rc<Self::Morphism>,
+    ) -> Result<&Rc<Self::Morphism>, Errors> {
+        let morphism = self.category.add_morphism(morphism)?.clone();
+        self.factorize(morphism.clone())?;
+        self.morphism_factors
+            .get_key_value(&morphism)
+            .map(|(morphism, _)| morphism)
+            .ok_or(Errors::MorphismNotFound)
+    }
+
+    fn get_identity_morphism(&self, object: &Self::Object) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.category.get_identity_morphism(object)
+    }
+
+    fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
+        self.category.get_object(object)
+    }
+
+    fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
+        self.category.get_all_objects()
+    }
+
+    fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        self.category.get_all_morphisms()
+    }
+
+    fn get_hom_set_x(
+        &self,
+        source_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        self.category.get_hom_set_x(source_object)
+    }
+
+    fn get_object_morphisms(
+        &self,
+        object: &Self::Object,
+    ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
+        self.category.get_object_morphisms(object)
+    }
+
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        self.category.compose_morphisms(first, second)
+    }
+
+    fn inverse_morphism(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        self.category.inverse_morphism(morphism)
+    }
+}
+
+impl<Inner: CategoryTrait + Clone> FactorizationSystemTrait
+    for TrivialFactorizationCategory<Inner>
+{
+    fn morphism_factors(&self, morphism: &Self::Morphism) -> Result<&Factors<Inner>, Errors> {
+        self.morphism_factors
+            .get(morphism)
+            .ok_or(Errors::InvalidFactorization)
+    }
+}
+
+/*
+A category with a weak factorization system (L, R) given by listing the morphisms of L
+and R, identities belong to both. Every morphism, also those added later, is factored
+by searching an object it passes through with the first part in L and the second in R.
+ */
+#[derive(Debug)]
+pub struct WeakFactorizationCategory<Inner: CategoryTrait> {
+    category: Inner,
+    left: HashSet<Rc<Inner::Morphism>>,
+    right: HashSet<Rc<Inner::Morphism>>,
+    morphism_factors: HashMap<Rc<Inner::Morphism>, Factors<Inner>>,
+}
+
+impl<Inner: CategoryTrait + Clone> Clone for WeakFactorizationCategory<Inner> {
+    fn clone(&self) -> Self {
+        WeakFactorizationCategory {
+            category: self.category.clone(),
+            left: self.left.clone(),
+            right: self.right.clone(),
+            morphism_factors: self.morphism_factors.clone(),
+        }
+    }
+}
+
+impl<Inner: CategoryTrait + Clone> WeakFactorizationCategory<Inner> {
+    pub fn from_category(
+        category: Inner,
+        left: Vec<Rc<Inner::Morphism>>,
+        right: Vec<Rc<Inner::Morphism>>,
+    ) -> Result<Self, Errors> {
+        let mut result = WeakFactorizationCategory {
+            category,
+            left: left.into_iter().collect(),
+            right: right.into_iter().collect(),
+            morphism_factors: HashMap::new(),
+        };
+        for morphism in all_morphisms(&result.category)? {
+            result.factorize(morphism)?;
+        }
+        Ok(result)
+    }
+
+    pub fn category(&self) -> &Inner {
+        &self.category
+    }
+
+    pub fn in_left(&self, morphism: &Rc<Inner::Morphism>) -> bool {
+        morphism.is_identity() || self.left.contains(morphism)
+    }
+
+    pub fn in_right(&self, morphism: &Rc<Inner::Morphism>) -> bool {
+        morphism.is_identity() || self.right.contains(morphism)
+    }
+
+    pub fn find_lifting(
+        &self,
+        square: &LiftingSquare<Inner>,
+    ) -> Result<Option<Rc<Inner::Morphism>>, Errors> {
+        find_lifting(&self.category, square)
+    }
+
+    pub fn verify(&self) -> Result<FactorizationSystemResult<Inner>, Errors> {
+        verify_weak_factorization_system(
+            &self.category,
+            |morphism| self.in_left(morphism),
+            |morphism| self.in_right(morphism),
+            |morphism| self.morphism_factors.get(morphism).cloned(),
+        )
+    }
+
+    fn factorize(&mut self, morphism: Rc<Inner::Morphism>) -> Result<(), Errors> {
+        let factors = self.find_factors(&morphism)?;
+        self.morphism_factors.insert(morphism, factors);
+        Ok(())
+    }
+
+    // the first factorization of the morphism through L and R, a pair of the category composing
+    // to it or else the morphism itself with an identity, so it may not have been added yet
+    fn find_factors(&self, morphism: &Rc<Inner::Morphism>) -> Result<Factors<Inner>, Errors> {
+        for object in sorted_objects(self.category.get_all_objects()?) {
+            let lefts = self
+                .category
+                .get_hom_set(morphism.source_object(), object)?;
+            for left in sorted_morphisms(lefts)
+                .into_iter()
+                .filter(|left| self.in_left(left))
+            {
+                let rights = self
+                    .category
+                    .get_hom_set(object, morphism.target_object())?;
+                for right in sorted_morphisms(rights) {
+                    if self.in_right(right) && self.composes_to(left, right, morphism)? {
+                        return Ok((left.clone(), right.clone()));
+                    }
+                }
+            }
+        }
+        if self.in_right(morphism) {
+            let identity = self
+                .category
+                .get_identity_morphism(morphism.source_object())?;
+            return Ok((identity.clone(), morphism.clone()));
+        }
+        if self.in_left(morphism) {
+            let identity = self
+                .category
+                .get_identity_morphism(morphism.target_object())?;
+            return Ok((morphism.clone(), identity.clone()));
+        }
+        Err(Errors::InvalidFactorization)
+    }
+
+    // whether right ∘ left is the morphism, recognized by its endpoints and action when the
+    // category does not hold it yet
+    fn composes_to(
+        &self,
+        left: &Rc<Inner::Morphism>,
+        right: &Rc<Inner::Morphism>,
+        morphism: &Rc<Inner::Morphism>,
+    ) -> Result<bool, Errors> {
+        let composite = match self.category.compose_morphisms(left, right) {
+            Ok(composite) => composite,
+            Err(Errors::InvalidMorphismComposition) => return Ok(false),
+            Err(e) => return Err(e),
+        };
+        Ok(composite == *morphism
+            || (!morphism.arrow_mappings().is_empty()
+                && Rc::ptr_eq(composite.source_object(), morphism.source_object())
+                && Rc::ptr_eq(composite.target_object(), morphism.target_object())
+                && composite.arrow_mappings() == morphism.arrow_mappings()))
+    }
+}
+
+impl<Inner: CategoryTrait + Clone> CategoryTrait for WeakFactorizationCategory<Inner> {
+    type Object = Inner::Object;
+
+    type Morphism = Inner::Morphism;
+
+    fn new() -> Self {
+        WeakFactorizationCategory::from_category(Inner::new(), Vec::new(), Vec::new())
+            .expect("the identities of a category factor it")
+    }
+
+    fn new_with_id(id: &ObjectId) -> Self {
+        WeakFactorizationCategory::from_category(Inner::new_with_id(id), Vec::new(), Vec::new())
+            .expect("the identities of a category factor it")
+    }
+
+    fn category_id(&self) -> &ObjectId {
+        self.category.category_id()
+    }
+
+    fn update_category_id(&mut self, new_id: ObjectId) {
+        self.category.update_category_id(new_id);
+    }
+
+    fn add_object(&mut self, object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        let identity = self.category.add_object(object)?;
+        self.factorize(identity.clone())?;
+        Ok(identity)
+    }
+
+    // the morphism is factored first and only added when it factors through L and R
+    fn add_morphism(
+        &mut self,
+        morphism: Rc<Self::Morphism>,
+    ) -> Result<&Rc<Self::Morphism>, Errors> {
+        let factors = self.find_factors(&morphism)?;
+        let morphism = self.category.add_morphism(morphism)?.clone();
+        self.morphism_factors.insert(morphism.clone(), factors);
+        self.morphism_factors
+            .get_key_value(&morphism)
+            .map(|(morphism, _)| morphism)
+            .ok_or(Errors::MorphismNotFound)
+    }
+
+    fn get_identity_morphism(&self, object: &Self::Object) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.category.get_identity_morphism(object)
+    }
+
+    fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
+        self.category.get_object(object)
+    }
+
+    fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
+        self.category.get_all_objects()
+    }
+
+    fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        self.category.get_all_morphisms()
+    }
+
+    fn get_hom_set_x(
+        &self,
+        source_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        self.category.get_hom_set_x(source_object)
+    }
+
+    fn get_object_morphisms(
+        &self,
+        object: &Self::Object,
+    ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
+        self.category.get_object_morphisms(object)
+    }
+
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        self.category.compose_morphisms(first, second)
+    }
+
+    fn inverse_morphism(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        self.category.inverse_morphism(morphism)
+    }
+}
+
+impl<Inner: CategoryTrait + Clone> FactorizationSystemTrait for WeakFactorizationCategory<Inner> {
+    fn morphism_factors(&self, morphism: &Self::Morphism) -> Result<&Factors<Inner>, Errors> {
+        self.morphism_factors
+            .get(morphism)
+            .ok_or(Errors::InvalidFactorization)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::concrete_category::fin_set::FinSet;
+    use crate::core::preorder_category::PreorderCategory;
+    use std::collections::HashMap;
+
+    type PreorderMorphism = <PreorderCategory as CategoryTrait>::Morphism;
+
+    fn chain() -> PreorderCategory {
+        PreorderCategory::from_relation(vec![0, 1, 2], vec![(0, 1), (1, 2)]).unwrap()
+    }
+
+    fn morphism(category: &PreorderCategory, lower: i32, upper: i32) -> Rc<PreorderMorphism> {
+        category
+            .morphism(&ObjectId::Int(lower), &ObjectId::Int(upper))
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn test_reflective_factorization_on_chain() {
+        // the closure 0 ↦ 1, 1 ↦ 1, 2 ↦ 2 gives E = {0≤1} and M = {1≤2}, up to identities
+        let chain = chain();
+        let factors: HashMap<_, _> = [
+            ((0, 1), (0, 1, 1)),
+            ((0, 2), (0, 1, 2)),
+            ((1, 2), (1, 1, 2)),
+            ((0, 0), (0, 0, 0)),
+            ((1, 1), (1, 1, 1)),
+            ((2, 2), (2, 2, 2)),
+        ]
+        .into_iter()
+        .map(|((lower, upper), (source, image, target))| {
+            (
+                morphism(&chain, lower, upper),
+                (
+                    morphism(&chain, source, image),
+                    morphism(&chain, image, target),
+                ),
+            )
+        })
+        .collect();
+        let left = morphism(&chain, 0, 1);
+        let right = morphism(&chain, 1, 2);
+        let result = verify_orthogonal_factorization_system(
+            &chain,
+            |morphism| morphism.is_identity() || *morphism == left,
+            |morphism| morphism.is_identity() || *morphism == right,
+            |morphism| factors.get(morphism).cloned(),
+        )
+        .unwrap();
+        assert!(result.is_valid());
+    }
+
+    #[test]
+    fn test_first_failure_is_reported() {
+        let chain = chain();
+        let identity_factors = |morphism: &Rc<PreorderMorphism>| {
+            let identity = chain.get_identity_morphism(morphism.target_object()).ok()?;
+            Some((morphism.clone(), identity.clone()))
+        };
+
+        // identities alone do not factor 0≤1
+        let result = verify_orthogonal_factorization_system(
+            &chain,
+            |morphism| morphism.is_identity(),
+            |morphism| morphism.is_identity(),
+            identity_factors,
+        )
+        .unwrap();
+        match result {
+            FactorizationSystemResult::NoFactorization(morphism) => {
+                assert_eq!(morphism.arrow_id(), "0≤1")
+            }
+            _ => panic!("expected a morphism without factorization"),
+        }
+
+        // with everything on both sides 0≤1 is not orthogonal to itself
+        let result =
+            verify_orthogonal_factorization_system(&chain, |_| true, |_| true, identity_factors)
+                .unwrap();
+        match result {
+            FactorizationSystemResult::NoUniqueDiagonal(square, fillers) => {
+                assert_eq!(square.left.arrow_id(), "0≤1");
+                assert_eq!(square.right.arrow_id(), "0≤1");
+                assert!(fillers.is_empty());
+            }
+            _ => panic!("expected a square without diagonal"),
+        }
+
+        // a weak system only asks for a diagonal, and there is none
+        let result =
+            verify_weak_factorization_system(&chain, |_| true, |_| true, identity_factors).unwrap();
+        match result {
+            FactorizationSystemResult::NoDiagonal(square) => {
+                assert_eq!(square.left.arrow_id(), "0≤1");
+                assert_eq!(square.right.arrow_id(), "0≤1");
+            }
+            _ => panic!("expected a square without diagonal"),
+        }
+    }
+
+    #[test]
+    fn test_trivial_factorization_systems() {
+        for kind in [TrivialFactorization::IsoAll, TrivialFactorization::AllIso] {
+            let system = TrivialFactorizationCategory::from_category(chain(), kind).unwrap();
+            let result = system
+                .verify_orthogonal_factorization(
+                    |morphism| system.in_left(morphism),
+                    |morphism| system.in_right(morphism),
+                )
+                .unwrap();
+            assert!(result.is_valid());
+
+            let zero_two = morphism(system.category(), 0, 2);
+            let (left, right) = system.morphism_factors(&zero_two).unwrap();
+            match kind {
+                TrivialFactorization::IsoAll => assert!(left.is_identity() && *right == zero_two),
+                TrivialFactorization::AllIso => assert!(*left == zero_two && right.is_identity()),
+            }
+            // only the identities are invertible in the chain
+            let identity = system.category().get_identity_morphism(&0.into()).unwrap();
+            assert!(system.in_left(identity) && system.in_right(identity));
+            assert_eq!(
+                (system.in_left(&zero_two), system.in_right(&zero_two)),
+                (
+                    kind == TrivialFactorization::AllIso,
+                    kind == TrivialFactorization::IsoAll
+                )
+            );
+        }
+    }
+
+    // the sets 1 and 2 with every function between them but the swap of 2
+    fn sets_without_swap() -> (
+        FinSet,
+        HashMap<&'static str, Rc<<FinSet as CategoryTrait>::Morphism>>,
+    ) {
+        let mut fin_set = FinSet::new();
+        let one = fin_set.add_set("1", vec![0]).unwrap();
+        let two = fin_set.add_set("2", vec![0, 1]).unwrap();
+        let functions = [
+            ("i0", &one, &two, vec![(0, 0)]),
+            ("i1", &one, &two, vec![(0, 1)]),
+            ("t", &two, &one, vec![(0, 0), (1, 0)]),
+            ("c0", &two, &two, vec![(0, 0), (1, 0)]),
+            ("c1", &two, &two, vec![(0, 1), (1, 1)]),
+        ];
+        let mut named = HashMap::new();
+        for (id, source, target, table) in functions {
+            let table = table
+                .into_iter()
+                .map(|(element, image)| (ObjectId::Int(element), ObjectId::Int(image)))
+                .collect();
+            named.insert(id, fin_set.add_function(id, source, target, table).unwrap());
+        }
+        named.insert("id_2", fin_set.get_identity_morphism(&two).unwrap().clone());
+        (fin_set, named)
+    }
+
+    #[test]
+    fn test_weak_factorization_and_lifting_search() {
+        let (fin_set, named) = sets_without_swap();
+        // two diagonals, id_2 and c0, fill the square from i0 to t
+        let square = LiftingSquare {
+            top: named["i0"].clone(),
+            left: named["i0"].clone(),
+            right: named["t"].clone(),
+            bottom: named["t"].clone(),
+        };
+        assert_eq!(diagonal_fillers(&fin_set, &square).unwrap().len(), 2);
+        assert!(find_lifting(&fin_set, &square).unwrap().is_some());
+        // i0 does not lift against itself, the only map back is t and i0 ∘ t = c0 ≠ id_2
+        let square = LiftingSquare {
+            top: fin_set
+                .get_identity_morphism(named["i0"].source_object())
+                .unwrap()
+                .clone(),
+            left: named["i0"].clone(),
+            right: named["i0"].clone(),
+            bottom: named["id_2"].clone(),
+        };
+        assert!(find_lifting(&fin_set, &square).unwrap().is_none());
+
+        let system = WeakFactorizationCategory::from_category(
+            fin_set.clone(),
+            vec![named["t"].clone()],
+            vec![named["i0"].clone(), named["i1"].clone()],
+        )
+        .unwrap();
+        let (left, right) = system.morphism_factors(&named["c0"]).unwrap();
+        assert_eq!((left, right), (&named["t"], &named["i0"]));
+        assert!(system.verify().unwrap().is_valid());
+
+        // the swap of 2 factors through neither, and is not left behind in the category
+        let mut system = system;
+        let two = named["id_2"].source_object().clone();
+        let swap = fin_set
+            .clone()
+            .add_function(
+                "s",
+                &two,
+                &two,
+                [(0, 1), (1, 0)]
+                    .into_iter()
+                    .map(|(element, image)| (ObjectId::Int(element), ObjectId::Int(image)))
+                    .collect(),
+            )
+            .unwrap();
+        for _ in 0..2 {
+            assert_eq!(
+                system.add_morphism(swap.clone()).err(),
+                Some(Errors::InvalidFactorization)
+            );
+        }
+        assert!(
+            !system
+                .category()
+                .get_hom_set(&two, &two)
+                .unwrap()
+                .contains(&swap)
+        );
+        // a new constant is factored by its action before it is added
+        let constant = FinSet::function(
+            &two,
+            &two,
+            &[(0, 0), (1, 0)]
+                .into_iter()
+                .map(|(element, image)| (ObjectId::Int(element), ObjectId::Int(image)))
+                .collect(),
+        )
+        .unwrap();
+        assert_ne!(constant, named["c0"]);
+        let constant = system.add_morphism(constant).unwrap().clone();
+        assert_eq!(
+            system.morphism_factors(&constant).unwrap(),
+            &(named["t"].clone(), named["i0"].clone())
+        );
+
+        // the constants do not pass through the injections followed by t
+        let failed = WeakFactorizationCategory::from_category(
+            fin_set,
+            vec![named["i0"].clone(), named["i1"].clone()],
+            vec![named["t"].clone()],
+        );
+        assert_eq!(failed.err(), Some(Errors::InvalidFactorization));
+    }
+}
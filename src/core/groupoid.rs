@@ -0,0 +1,432 @@
+/*
+Groupoids, categories where every morphism is an isomorphism.
+
+Groupoid::core keeps all objects of a category and only its isomorphisms, the maximal
+subgroupoid, while Groupoid::from_groupoid checks that a category already is one.
+Morphisms are composed in the ambient category.
+
+A finite groupoid is determined up to equivalence by its connected components and the
+automorphism group of one object in each, and its cardinality is Σ 1/|Aut(x)| over
+the components, e.g. 1/|G| for a group G seen as a one-object category.
+ */
+use crate::core::errors::Errors;
+use crate::core::identifier::Identifier;
+use crate::core::monoid_category::MonoidCategory;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::utils::{sorted_morphisms, sorted_objects};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Display, Formatter};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i64,
+    denominator: i64,
+}
+
+impl Rational {
+    // the reduced fraction, None for a zero denominator or when it does not fit in i64
+    pub fn new(numerator: i64, denominator: i64) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator.unsigned_abs(), denominator.unsigned_abs());
+        let divisor = i64::try_from(divisor).ok()? * denominator.signum();
+        Some(Rational {
+            numerator: numerator.checked_div(divisor)?,
+            denominator: denominator.checked_div(divisor)?,
+        })
+    }
+
+    // the sum, None when it overflows
+    pub fn checked_add(self, other: Rational) -> Option<Rational> {
+        Rational::new(
+            self.numerator
+                .checked_mul(other.denominator)?
+                .checked_add(other.numerator.checked_mul(self.denominator)?)?,
+            self.denominator.checked_mul(other.denominator)?,
+        )
+    }
+
+    pub fn numerator(&self) -> i64 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i64 {
+        self.denominator
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            _ => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+fn gcd(left: u64, right: u64) -> u64 {
+    match right {
+        0 => left,
+        _ => gcd(right, left % right),
+    }
+}
+
+pub struct ConnectedComponent<Category: CategoryTrait> {
+    objects: Vec<Rc<Category::Object>>,
+    // the automorphisms of the first object, identity included
+    automorphisms: Vec<Rc<Category::Morphism>>,
+}
+
+impl<Category: CategoryTrait> ConnectedComponent<Category> {
+    pub fn objects(&self) -> &Vec<Rc<Category::Object>> {
+        &self.objects
+    }
+
+    pub fn base(&self) -> &Rc<Category::Object> {
+        &self.objects[0]
+    }
+
+    pub fn automorphisms(&self) -> &Vec<Rc<Category::Morphism>> {
+        &self.automorphisms
+    }
+}
+
+#[derive(Debug)]
+pub struct Groupoid<Category: CategoryTrait> {
+    id: ObjectId,
+    category: Rc<Category>,
+    objects: HashMap<ObjectId, Rc<Category::Object>>,
+    // isomorphisms out of each object, identity included
+    hom_sets: HashMap<ObjectId, Vec<Rc<Category::Morphism>>>,
+}
+
+impl<Category: CategoryTrait> Clone for Groupoid<Category> {
+    fn clone(&self) -> Self {
+        Groupoid {
+            id: self.id.clone(),
+            category: self.category.clone(),
+            objects: self.objects.clone(),
+            hom_sets: self.hom_sets.clone(),
+        }
+    }
+}
+
+impl<Category: CategoryTrait> Groupoid<Category> {
+    // the maximal subgroupoid: every object and only the isomorphisms
+    pub fn core(category: &Rc<Category>) -> Result<Self, Errors> {
+        Self::build(category, false)
+    }
+
+    // the category itself, failing on the first morphism which is not invertible
+    pub fn from_groupoid(category: &Rc<Category>) -> Result<Self, Errors> {
+        Self::build(category, true)
+    }
+
+    pub fn category(&self) -> &Rc<Category> {
+        &self.category
+    }
+
+    pub fn connected_components(&self) -> Result<Vec<ConnectedComponent<Category>>, Errors> {
+        let mut seen = HashSet::new();
+        let mut components = Vec::new();
+        for object in sorted_objects(self.get_all_objects()?) {
+            if seen.contains(object.category_id()) {
+                continue;
+            }
+            let mut objects = Vec::new();
+            let mut pending = vec![object.clone()];
+            seen.insert(object.category_id().clone());
+            while let Some(current) = pending.pop() {
+                for morphism in &self.hom_sets[current.category_id()] {
+                    let target = morphism.target_object();
+                    if seen.insert(target.category_id().clone()) {
+                        pending.push(target.clone());
+                    }
+                }
+                objects.push(current);
+            }
+            objects.sort_by_key(|object| object.category_id().to_string());
+            let automorphisms = sorted_morphisms(self.get_hom_set(&objects[0], &objects[0])?)
+                .into_iter()
+                .cloned()
+                .collect();
+            components.push(ConnectedComponent {
+                objects,
+                automorphisms,
+            });
+        }
+        Ok(components)
+    }
+
+    // Aut(x) as a group whose elements are the arrow ids, with a·b = a ∘ b
+    pub fn automorphism_group(&self, object: &Category::Object) -> Result<MonoidCategory, Errors> {
+        let automorphisms = sorted_morphisms(self.get_hom_set(object, object)?);
+        let ids = automorphisms
+            .iter()
+            .map(|morphism| morphism.arrow_id().clone())
+            .collect::<Vec<_>>();
+        let mut table = Vec::new();
+        for left in &automorphisms {
+            let mut row = Vec::new();
+            for right in &automorphisms {
+                let composite = self.compose_morphisms(right, left)?;
+                let product = automorphisms
+                    .iter()
+                    .find(|morphism| ***morphism == composite)
+                    .ok_or(Errors::MorphismNotFound)?;
+                row.push(product.arrow_id().clone());
+            }
+            table.push(row);
+        }
+        MonoidCategory::from_table(format!("Aut({})", object.category_id()), ids, table)?
+            .into_group()
+    }
+
+    // Σ 1/|Aut(x)| over one object x of each connected component
+    pub fn cardinality(&self) -> Result<Rational, Errors> {
+        let overflow = || Errors::InvalidOperation("the cardinality overflows i64".to_string());
+        let mut sum = Rational::new(0, 1).ok_or_else(overflow)?;
+        for component in self.connected_components()? {
+            let order = i64::try_from(component.automorphisms.len()).map_err(|_| overflow())?;
+            let term = Rational::new(1, order).ok_or_else(overflow)?;
+            sum = sum.checked_add(term).ok_or_else(overflow)?;
+        }
+        Ok(sum)
+    }
+
+    fn build(category: &Rc<Category>, require_invertible: bool) -> Result<Self, Errors> {
+        let mut objects = HashMap::new();
+        let mut hom_sets = HashMap::new();
+        for object in category.get_all_objects()? {
+            let mut isomorphisms = Vec::new();
+            for morphism in sorted_morphisms(category.get_hom_set_x(object)?) {
+                if category.inverse_morphism(morphism)?.is_some() {
+                    isomorphisms.push(morphism.clone());
+                } else if require_invertible {
+                    return Err(Errors::InvalidOperation(format!(
+                        "Morphism {} is not invertible",
+                        morphism.arrow_id()
+                    )));
+                }
+            }
+            objects.insert(object.category_id().clone(), object.clone());
+            hom_sets.insert(object.category_id().clone(), isomorphisms);
+        }
+        Ok(Groupoid {
+            id: ObjectId::generated("core", [category.category_id().clone()]),
+            category: category.clone(),
+            objects,
+            hom_sets,
+        })
+    }
+}
+
+impl<Category: CategoryTrait> CategoryTrait for Groupoid<Category> {
+    type Object = Category::Object;
+
+    type Morphism = Category::Morphism;
+
+    fn new() -> Self {
+        Self::new_with_id(&ObjectId::generate())
+    }
+
+    fn new_with_id(id: &ObjectId) -> Self {
+        Groupoid {
+            id: id.clone(),
+            category: Rc::new(Category::new()),
+            objects: HashMap::new(),
+            hom_sets: HashMap::new(),
+        }
+    }
+
+    fn category_id(&self) -> &ObjectId {
+        &self.id
+    }
+
+    fn update_category_id(&mut self, new_id: ObjectId) {
+        self.id = new_id;
+    }
+
+    fn add_object(&mut self, _object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        Err(Errors::InvalidOperation(
+            "A groupoid is built from its category".to_string(),
+        ))
+    }
+
+    fn add_morphism(
+        &mut self,
+        _morphism: Rc<Self::Morphism>,
+    ) -> Result<&Rc<Self::Morphism>, Errors> {
+        Err(Errors::InvalidOperation(
+            "A groupoid is built from its category".to_string(),
+        ))
+    }
+
+    fn get_identity_morphism(&self, object: &Self::Object) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.category.get_identity_morphism(object)
+    }
+
+    fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
+        self.objects
+            .get(object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))
+    }
+
+    fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
+        Ok(self.objects.values().collect())
+    }
+
+    fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self.hom_sets.values().flatten().collect())
+    }
+
+    fn get_hom_set_x(
+        &self,
+        source_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .hom_sets
+            .get(source_object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(source_object.category_id().clone()))?
+            .iter()
+            .collect())
+    }
+
+    fn get_object_morphisms(
+        &self,
+        object: &Self::Object,
+    ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .hom_sets
+            .get(object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))?
+            .iter()
+            .collect())
+    }
+
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        self.category.compose_morphisms(first, second)
+    }
+
+    fn inverse_morphism(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        self.category.inverse_morphism(morphism)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::concrete_category::fin_set::FinSet;
+
+    #[test]
+    fn test_core_of_fin_set() {
+        let mut fin_set = FinSet::new();
+        let two = fin_set.add_set("X", vec![1, 2]).unwrap();
+        let letters = fin_set.add_set("Y", vec!["a", "b"]).unwrap();
+        let point = fin_set.add_set("Z", vec!["z"]).unwrap();
+        let table = |pairs: &[(ObjectId, ObjectId)]| pairs.iter().cloned().collect();
+        let (one, two_id, a, b, z) = (
+            ObjectId::from(1),
+            ObjectId::from(2),
+            ObjectId::from("a"),
+            ObjectId::from("b"),
+            ObjectId::from("z"),
+        );
+        fin_set
+            .add_function(
+                "f",
+                &two,
+                &letters,
+                table(&[(one.clone(), a.clone()), (two_id.clone(), b.clone())]),
+            )
+            .unwrap();
+        fin_set
+            .add_function(
+                "f⁻¹",
+                &letters,
+                &two,
+                table(&[(a.clone(), one.clone()), (b.clone(), two_id.clone())]),
+            )
+            .unwrap();
+        fin_set
+            .add_function(
+                "swap",
+                &two,
+                &two,
+                table(&[(one.clone(), two_id.clone()), (two_id.clone(), one.clone())]),
+            )
+            .unwrap();
+        fin_set
+            .add_function("!", &two, &point, table(&[(one, z.clone()), (two_id, z)]))
+            .unwrap();
+        let fin_set = Rc::new(fin_set);
+
+        let core = Groupoid::core(&fin_set).unwrap();
+        assert_eq!(core.get_all_objects().unwrap().len(), 3);
+        // identities, f, f⁻¹ and swap, but not the map to the point
+        assert_eq!(core.get_all_morphisms().unwrap().len(), 6);
+        assert!(Groupoid::from_groupoid(&fin_set).is_err());
+
+        let components = core.connected_components().unwrap();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].objects().len(), 2);
+        assert_eq!(components[0].base().category_id(), &ObjectId::from("X"));
+        assert_eq!(components[0].automorphisms().len(), 2);
+        assert_eq!(core.cardinality().unwrap(), Rational::new(3, 2).unwrap());
+
+        let aut = core.automorphism_group(&two).unwrap();
+        assert!(aut.is_group());
+        assert_eq!(aut.elements().len(), 2);
+    }
+
+    #[test]
+    fn test_groups_as_groupoids() {
+        let z3 = Rc::new(
+            MonoidCategory::group_from_fn("Z3", vec![0, 1, 2], |a, b| (a + b) % 3).unwrap(),
+        );
+        let groupoid = Groupoid::from_groupoid(&z3).unwrap();
+        assert_eq!(groupoid.connected_components().unwrap().len(), 1);
+        assert_eq!(groupoid.cardinality().unwrap().to_string(), "1/3");
+
+        // the core of ({0, 1}, ·) is the trivial group on 1
+        let multiplication = Rc::new(
+            MonoidCategory::from_table("B", vec![0, 1], vec![vec![0, 0], vec![0, 1]]).unwrap(),
+        );
+        assert!(Groupoid::from_groupoid(&multiplication).is_err());
+        let core = Groupoid::core(&multiplication).unwrap();
+        assert_eq!(core.cardinality().unwrap(), Rational::new(1, 1).unwrap());
+    }
+
+    #[test]
+    fn test_rational_arithmetic() {
+        let [half, third, sixth] =
+            [2, 3, 6].map(|denominator| Rational::new(1, denominator).unwrap());
+        let sum = half
+            .checked_add(third)
+            .and_then(|sum| sum.checked_add(sixth));
+        assert_eq!(sum.unwrap().to_string(), "1");
+        assert_eq!(Rational::new(2, -4).unwrap().to_string(), "-1/2");
+        assert_eq!(Rational::new(1, 0), None);
+        // i64::MIN / -1 does not fit, nor do twice i64::MAX or a denominator past i64::MAX
+        assert_eq!(Rational::new(i64::MIN, -1), None);
+        let max = Rational::new(i64::MAX, 1).unwrap();
+        assert_eq!(max.checked_add(max), None);
+        assert_eq!(
+            Rational::new(1, i64::MAX)
+                .unwrap()
+                .checked_add(Rational::new(1, i64::MAX - 1).unwrap()),
+            None
+        );
+    }
+}
@@ -0,0 +1,514 @@
+/*
+An indiscrete (codiscrete) category, the dual of a DiscreteCategory: there is exactly one
+morphism between every ordered pair of objects, named after the pair, e.g. "(a,b)".
+The morphisms, and the row of morphisms out of an object, are made the first time they are
+asked for, so adding n objects does not build n² arrows up front, and composition always
+lands on the one morphism of its hom-set.
+
+Between finite sets and categories there are the functors
+    - Disc, a set as the discrete category on its elements. A set of FinSet already is
+      that DiscreteCategory and a function the functor between them
+    - Ob, the set of objects of a category
+    - Codisc, a set as the indiscrete category on its elements
+with Disc ⊣ Ob ⊣ Codisc: functors Disc(S) -> C are the functions S -> Ob(C), and the
+functions Ob(C) -> T are the functors C -> Codisc(T).
+ */
+use crate::core::arrow::{Arrow, Functor, Morphism};
+use crate::core::concrete_category::fin_set::{FinSet, Function};
+use crate::core::discrete_category::DiscreteCategory;
+use crate::core::errors::Errors;
+use crate::core::identifier::Identifier;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+type Row = Vec<OnceCell<Rc<Morphism<DiscreteCategory>>>>;
+
+#[derive(Debug, Clone)]
+pub struct IndiscreteCategory {
+    id: ObjectId,
+    objects: Vec<Rc<DiscreteCategory>>,
+    indices: HashMap<ObjectId, usize>,
+    // morphisms[i][j] is the one morphism from object i to object j, once asked for
+    morphisms: Vec<OnceCell<Row>>,
+}
+
+impl Default for IndiscreteCategory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for IndiscreteCategory {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for IndiscreteCategory {}
+
+impl Hash for IndiscreteCategory {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl IndiscreteCategory {
+    pub fn new() -> Self {
+        Self::new_with_id(ObjectId::generate())
+    }
+
+    pub fn new_with_id(id: ObjectId) -> Self {
+        IndiscreteCategory {
+            id,
+            objects: Vec::new(),
+            indices: HashMap::new(),
+            morphisms: Vec::new(),
+        }
+    }
+
+    pub fn from_elements<T: Into<ObjectId>>(
+        id: impl Into<ObjectId>,
+        elements: Vec<T>,
+    ) -> Result<Self, Errors> {
+        let mut category = Self::new_with_id(id.into());
+        for element in elements {
+            category.add_element(element)?;
+        }
+        Ok(category)
+    }
+
+    pub fn add_element(&mut self, id: impl Into<ObjectId>) -> Result<Rc<DiscreteCategory>, Errors> {
+        let element = Rc::new(DiscreteCategory::new_with_id(id.into()));
+        self.add_object(element.clone())?;
+        Ok(element)
+    }
+
+    pub fn element(&self, id: &ObjectId) -> Result<&Rc<DiscreteCategory>, Errors> {
+        Ok(&self.objects[self.index(id)?])
+    }
+
+    // the one morphism source -> target
+    pub fn morphism(
+        &self,
+        source: &ObjectId,
+        target: &ObjectId,
+    ) -> Result<&Rc<Morphism<DiscreteCategory>>, Errors> {
+        let (source, target) = (self.index(source)?, self.index(target)?);
+        let row = self.morphisms[source]
+            .get_or_init(|| (0..self.objects.len()).map(|_| OnceCell::new()).collect());
+        Ok(row[target].get_or_init(|| {
+            let (source, target) = (&self.objects[source], &self.objects[target]);
+            let id =
+                ObjectId::product([source.category_id().clone(), target.category_id().clone()]);
+            if source == target {
+                return Arrow::new_identity_with_mappings(
+                    id.to_string(),
+                    source.clone(),
+                    HashMap::new(),
+                );
+            }
+            Rc::new(Arrow::new(
+                id.to_string(),
+                source.clone(),
+                target.clone(),
+                HashMap::new(),
+            ))
+        }))
+    }
+
+    fn index(&self, id: &ObjectId) -> Result<usize, Errors> {
+        self.indices
+            .get(id)
+            .copied()
+            .ok_or_else(|| Errors::ObjectNotFound(id.clone()))
+    }
+}
+
+impl CategoryTrait for IndiscreteCategory {
+    type Object = DiscreteCategory;
+
+    type Morphism = Morphism<DiscreteCategory>;
+
+    fn new() -> Self {
+        IndiscreteCategory::new()
+    }
+
+    fn new_with_id(id: &ObjectId) -> Self {
+        IndiscreteCategory::new_with_id(id.clone())
+    }
+
+    fn category_id(&self) -> &ObjectId {
+        &self.id
+    }
+
+    fn update_category_id(&mut self, new_id: ObjectId) {
+        self.id = new_id;
+    }
+
+    fn add_object(&mut self, object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        let id = object.category_id().clone();
+        if self.indices.contains_key(&id) {
+            return Err(Errors::ObjectAlreadyExists);
+        }
+        self.indices.insert(id.clone(), self.objects.len());
+        self.objects.push(object);
+        for row in self.morphisms.iter_mut().filter_map(OnceCell::get_mut) {
+            row.push(OnceCell::new());
+        }
+        self.morphisms.push(OnceCell::new());
+        Ok(self.morphism(&id, &id)?.clone())
+    }
+
+    // every hom-set already has its morphism, which is returned
+    fn add_morphism(
+        &mut self,
+        morphism: Rc<Self::Morphism>,
+    ) -> Result<&Rc<Self::Morphism>, Errors> {
+        for object in [morphism.source_object(), morphism.target_object()] {
+            if self.element(object.category_id())? != object {
+                return Err(Errors::ObjectNotFound(object.category_id().clone()));
+            }
+        }
+        self.morphism(
+            morphism.source_object().category_id(),
+            morphism.target_object().category_id(),
+        )
+    }
+
+    fn get_identity_morphism(&self, object: &Self::Object) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.morphism(object.category_id(), object.category_id())
+    }
+
+    fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
+        self.element(object.category_id())
+    }
+
+    fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
+        Ok(self.objects.iter().collect())
+    }
+
+    fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        let mut morphisms = HashSet::new();
+        for object in &self.objects {
+            morphisms.extend(self.get_hom_set_x(object)?);
+        }
+        Ok(morphisms)
+    }
+
+    fn get_hom_set_x(
+        &self,
+        source_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        self.objects
+            .iter()
+            .map(|target| self.morphism(source_object.category_id(), target.category_id()))
+            .collect()
+    }
+
+    fn get_hom_set(
+        &self,
+        source_object: &Self::Object,
+        target_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(HashSet::from([self.morphism(
+            source_object.category_id(),
+            target_object.category_id(),
+        )?]))
+    }
+
+    fn get_object_morphisms(
+        &self,
+        object: &Self::Object,
+    ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
+        let mut morphisms = self.get_hom_set_x(object)?.into_iter().collect::<Vec<_>>();
+        morphisms.sort_by_key(|morphism| morphism.arrow_id().clone());
+        Ok(morphisms)
+    }
+
+    // the composite is the one morphism from the first source to the second target
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        if first.target_object() != second.source_object() {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        Ok(self
+            .morphism(
+                first.source_object().category_id(),
+                second.target_object().category_id(),
+            )?
+            .clone())
+    }
+}
+
+// Ob(C), the set of objects of the category
+pub fn ob<Category>(category: &Category) -> Result<Rc<DiscreteCategory>, Errors>
+where
+    Category: CategoryTrait<Object = DiscreteCategory>,
+{
+    let objects = category
+        .get_all_objects()?
+        .into_iter()
+        .map(|object| object.category_id().clone())
+        .collect();
+    Ok(Rc::new(FinSet::new_set(
+        ObjectId::generated("Ob", [category.category_id().clone()]),
+        objects,
+    )?))
+}
+
+// Ob(F): Ob(C) -> Ob(D), the action of the functor on objects
+pub fn ob_function<Source, Target>(
+    functor: &Functor<Source, Target>,
+    source: &Rc<DiscreteCategory>,
+    target: &Rc<DiscreteCategory>,
+) -> Result<Rc<Function>, Errors>
+where
+    Source: CategoryTrait<Object = DiscreteCategory>,
+    Target: CategoryTrait<Object = DiscreteCategory>,
+{
+    FinSet::function(source, target, &object_table(functor)?)
+}
+
+// Codisc(S), the indiscrete category on the elements of the set
+pub fn codisc(set: &DiscreteCategory) -> Result<IndiscreteCategory, Errors> {
+    IndiscreteCategory::from_elements(
+        ObjectId::generated("Codisc", [set.category_id().clone()]),
+        FinSet::elements(set),
+    )
+}
+
+// Codisc(f): Codisc(S) -> Codisc(T), sending a -> b to f(a) -> f(b)
+pub fn codisc_function(
+    function: &Function,
+    source: &Rc<IndiscreteCategory>,
+    target: &Rc<IndiscreteCategory>,
+) -> Result<Functor<IndiscreteCategory, IndiscreteCategory>, Errors> {
+    ob_codisc_transpose(source, target, &FinSet::function_table(function)?)
+}
+
+// the functor Disc(S) -> C of a function S -> Ob(C)
+pub fn disc_ob_transpose<Category>(
+    set: &Rc<DiscreteCategory>,
+    category: &Rc<Category>,
+    table: &HashMap<ObjectId, ObjectId>,
+) -> Result<Functor<DiscreteCategory, Category>, Errors>
+where
+    Category: CategoryTrait<Object = DiscreteCategory>,
+{
+    let mut mappings = HashMap::new();
+    for element in FinSet::elements(set) {
+        let image = image(table, &element)?.clone();
+        let identity = set.get_identity_morphism(&DiscreteCategory::new_with_id(element))?;
+        let mapped = category.get_identity_morphism(&DiscreteCategory::new_with_id(image))?;
+        mappings.insert(identity.clone(), mapped.clone());
+    }
+    Ok(Functor::new(
+        String::generate(),
+        set.clone(),
+        category.clone(),
+        mappings,
+    ))
+}
+
+// the functor C -> Codisc(T) of a function Ob(C) -> T
+pub fn ob_codisc_transpose<Category>(
+    category: &Rc<Category>,
+    codiscrete: &Rc<IndiscreteCategory>,
+    table: &HashMap<ObjectId, ObjectId>,
+) -> Result<Functor<Category, IndiscreteCategory>, Errors>
+where
+    Category: CategoryTrait<Object = DiscreteCategory>,
+{
+    let mut mappings = HashMap::new();
+    for object in category.get_all_objects()? {
+        for morphism in category.get_hom_set_x(object)? {
+            let source = image(table, morphism.source_object().category_id())?;
+            let target = image(table, morphism.target_object().category_id())?;
+            mappings.insert(
+                morphism.clone(),
+                codiscrete.morphism(source, target)?.clone(),
+            );
+        }
+    }
+    Ok(Functor::new(
+        String::generate(),
+        category.clone(),
+        codiscrete.clone(),
+        mappings,
+    ))
+}
+
+// Disc ⊣ Ob: every function S -> Ob(C) is a functor Disc(S) -> C with that action on objects
+pub fn validate_disc_ob_adjunction<Category>(
+    set: &Rc<DiscreteCategory>,
+    category: &Rc<Category>,
+) -> Result<(), Errors>
+where
+    Category: CategoryTrait<Object = DiscreteCategory>,
+{
+    let objects = ob(&**category)?;
+    for table in FinSet::all_tables(set, &objects) {
+        let functor = disc_ob_transpose(set, category, &table)?;
+        check_transpose(&functor, &table)?;
+    }
+    Ok(())
+}
+
+// Ob ⊣ Codisc: every function Ob(C) -> T is a functor C -> Codisc(T) with that action on objects
+pub fn validate_ob_codisc_adjunction<Category>(
+    category: &Rc<Category>,
+    set: &Rc<DiscreteCategory>,
+) -> Result<(), Errors>
+where
+    Category: CategoryTrait<Object = DiscreteCategory>,
+{
+    let objects = ob(&**category)?;
+    let codiscrete = Rc::new(codisc(set)?);
+    for table in FinSet::all_tables(&objects, set) {
+        let functor = ob_codisc_transpose(category, &codiscrete, &table)?;
+        check_transpose(&functor, &table)?;
+    }
+    Ok(())
+}
+
+// the transpose is a functor and gives back the function it came from
+fn check_transpose<Source, Target>(
+    functor: &Functor<Source, Target>,
+    table: &HashMap<ObjectId, ObjectId>,
+) -> Result<(), Errors>
+where
+    Source: CategoryTrait<Object = DiscreteCategory>,
+    Target: CategoryTrait<Object = DiscreteCategory>,
+{
+    functor.validate_mappings().map_err(|error| {
+        Errors::InvalidAdjunction(format!("The transpose is not a functor: {:?}", error))
+    })?;
+    if &object_table(functor)? != table {
+        return Err(Errors::InvalidAdjunction(format!(
+            "The transpose {} does not act on objects as its function",
+            functor.arrow_id()
+        )));
+    }
+    Ok(())
+}
+
+fn object_table<Source, Target>(
+    functor: &Functor<Source, Target>,
+) -> Result<HashMap<ObjectId, ObjectId>, Errors>
+where
+    Source: CategoryTrait<Object = DiscreteCategory>,
+    Target: CategoryTrait<Object = DiscreteCategory>,
+{
+    functor
+        .source_object()
+        .get_all_objects()?
+        .into_iter()
+        .map(|object| {
+            let image = functor.map_object(object)?;
+            Ok((object.category_id().clone(), image.category_id().clone()))
+        })
+        .collect()
+}
+
+fn image<'a>(
+    table: &'a HashMap<ObjectId, ObjectId>,
+    element: &ObjectId,
+) -> Result<&'a ObjectId, Errors> {
+    table
+        .get(element)
+        .ok_or_else(|| Errors::InvalidFunctor(format!("No image for element {}", element)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::preorder_category::PreorderCategory;
+
+    #[test]
+    fn test_one_morphism_per_pair() {
+        let category = IndiscreteCategory::from_elements("abc", vec!["a", "b", "c"]).unwrap();
+        let a = category.element(&"a".into()).unwrap().clone();
+        let c = category.element(&"c".into()).unwrap().clone();
+        let a_c = category.get_hom_set(&a, &c).unwrap();
+        assert_eq!(a_c.len(), 1);
+        let a_c = (*a_c.iter().next().unwrap()).clone();
+        assert_eq!(a_c.arrow_id(), "(a,c)");
+        // a -> c -> a is the identity, so every morphism is an isomorphism
+        let c_a = category.morphism(&"c".into(), &"a".into()).unwrap().clone();
+        assert!(
+            category
+                .compose_morphisms(&a_c, &c_a)
+                .unwrap()
+                .is_identity()
+        );
+        assert_eq!(category.inverse_morphism(&a_c).unwrap(), Some(c_a.clone()));
+        // asked for again, the morphism is the same one
+        assert!(Rc::ptr_eq(
+            &c_a,
+            category.morphism(&"c".into(), &"a".into()).unwrap()
+        ));
+        assert_eq!(category.get_all_morphisms().unwrap().len(), 9);
+    }
+
+    #[test]
+    fn test_many_objects() {
+        let mut category = IndiscreteCategory::from_elements("many", (0..2000).collect()).unwrap();
+        let first = category.morphism(&0.into(), &1999.into()).unwrap().clone();
+        let second = category.morphism(&1999.into(), &7.into()).unwrap().clone();
+        let composite = category.compose_morphisms(&first, &second).unwrap();
+        assert_eq!(composite.arrow_id(), "(0,7)");
+        assert!(category.add_element(3).is_err());
+        // the rows already made reach the objects added later
+        category.add_element(2000).unwrap();
+        let last = category.morphism(&0.into(), &2000.into()).unwrap();
+        assert_eq!(last.arrow_id(), "(0,2000)");
+    }
+
+    #[test]
+    fn test_discrete_ob_codiscrete_adjunctions() {
+        let mut fin_set = FinSet::new();
+        let set = fin_set.add_set("S", vec!["x", "y"]).unwrap();
+        let preorder = Rc::new(
+            PreorderCategory::from_relation(vec!["a", "b", "c"], vec![("a", "b")]).unwrap(),
+        );
+        validate_disc_ob_adjunction(&set, &preorder).unwrap();
+        validate_ob_codisc_adjunction(&preorder, &set).unwrap();
+
+        // a ≤ b may go anywhere in Codisc(S), as b ≤ a may not in the preorder
+        let table = HashMap::from([
+            ("a".into(), "y".into()),
+            ("b".into(), "x".into()),
+            ("c".into(), "x".into()),
+        ]);
+        let codiscrete = Rc::new(codisc(&set).unwrap());
+        let functor = ob_codisc_transpose(&preorder, &codiscrete, &table).unwrap();
+        let a_b = preorder.morphism(&"a".into(), &"b".into()).unwrap();
+        assert_eq!(functor.map_morphism(a_b).unwrap().arrow_id(), "(y,x)");
+        let objects = ob(&*preorder).unwrap();
+        let object_function = ob_function(&functor, &objects, &set).unwrap();
+        assert_eq!(FinSet::function_table(&object_function).unwrap(), table);
+
+        // a function between sets lifts to the codiscrete categories
+        let flip = FinSet::function(
+            &set,
+            &set,
+            &HashMap::from([("x".into(), "y".into()), ("y".into(), "x".into())]),
+        )
+        .unwrap();
+        let lifted = codisc_function(&flip, &codiscrete, &codiscrete).unwrap();
+        lifted.validate_mappings().unwrap();
+        let x_y = codiscrete.morphism(&"x".into(), &"y".into()).unwrap();
+        assert_eq!(lifted.map_morphism(x_y).unwrap().arrow_id(), "(y,x)");
+
+        // a set is its own discrete category, so Disc ⊣ Ob also holds against the codiscrete one
+        validate_disc_ob_adjunction(&set, &codiscrete).unwrap();
+    }
+}
@@ -0,0 +1,495 @@
+/*
+Kan extensions of a functor F: C -> E along a functor K: C -> D,
+computed with the pointwise formulas over comma categories:
+
+    (Lan_K F)(d) = colim_{(c, f: K(c) -> d) ∈ K ↓ d} F(c)
+    (Ran_K F)(d) = lim_{(c, f: d -> K(c)) ∈ d ↓ K} F(c)
+
+The left extension comes with its unit η: F => Lan_K F ∘ K
+and the right extension with its counit ε: Ran_K F ∘ K => F.
+ */
+use crate::core::arrow::Functor;
+use crate::core::errors::Errors;
+use crate::core::identifier::Identifier;
+use crate::core::natural_transformation::NaturalTransformation;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::traits::finite_limits_trait::{
+    Cocone, Cone, FiniteColimitsTrait, FiniteDiagram, FiniteLimitsTrait,
+};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Debug)]
+pub struct KanExtension<
+    SourceCategory: CategoryTrait,
+    AlongCategory: CategoryTrait,
+    TargetCategory: CategoryTrait,
+> {
+    extension: Rc<Functor<AlongCategory, TargetCategory>>,
+    // η: F => Lan_K F ∘ K for left extensions, ε: Ran_K F ∘ K => F for right extensions
+    universal_transformation: NaturalTransformation<SourceCategory, TargetCategory>,
+}
+
+impl<SourceCategory: CategoryTrait, AlongCategory: CategoryTrait, TargetCategory: CategoryTrait>
+    KanExtension<SourceCategory, AlongCategory, TargetCategory>
+{
+    pub fn extension(&self) -> &Rc<Functor<AlongCategory, TargetCategory>> {
+        &self.extension
+    }
+
+    pub fn universal_transformation(
+        &self,
+    ) -> &NaturalTransformation<SourceCategory, TargetCategory> {
+        &self.universal_transformation
+    }
+}
+
+// an object (c, f) of a comma category
+type CommaObject<SourceCategory, AlongCategory> = (
+    Rc<<SourceCategory as CategoryTrait>::Object>,
+    Rc<<AlongCategory as CategoryTrait>::Morphism>,
+);
+
+// objects of the comma category paired with the diagram F restricted to it
+struct CommaDiagram<
+    SourceCategory: CategoryTrait,
+    AlongCategory: CategoryTrait,
+    TargetCategory: CategoryTrait,
+> {
+    labels: Vec<CommaObject<SourceCategory, AlongCategory>>,
+    diagram: FiniteDiagram<TargetCategory>,
+}
+
+impl<SourceCategory: CategoryTrait, AlongCategory: CategoryTrait, TargetCategory: CategoryTrait>
+    CommaDiagram<SourceCategory, AlongCategory, TargetCategory>
+{
+    // K ↓ d when `over` is true, d ↓ K otherwise
+    fn new(
+        functor: &Functor<SourceCategory, TargetCategory>,
+        along: &Functor<SourceCategory, AlongCategory>,
+        object: &Rc<AlongCategory::Object>,
+        over: bool,
+    ) -> Result<Self, Errors> {
+        let source_category = along.source_object();
+        let along_category = along.target_object();
+
+        let mut labels = Vec::new();
+        let mut diagram = FiniteDiagram::new();
+        for source_object in source_category.get_all_objects()? {
+            let mapped_object = along.map_object(source_object)?;
+            let hom_set = if over {
+                along_category.get_hom_set(mapped_object, object)?
+            } else {
+                along_category.get_hom_set(object, mapped_object)?
+            };
+            for morphism in hom_set {
+                labels.push((source_object.clone(), morphism.clone()));
+                diagram.add_object(functor.map_object(source_object)?.clone());
+            }
+        }
+
+        for (source_index, (source_object, source_morphism)) in labels.iter().enumerate() {
+            for morphism in source_category.get_hom_set_x(source_object)? {
+                if morphism.is_identity() {
+                    continue;
+                }
+                let mapped_morphism = along.map_morphism(morphism)?;
+                for (target_index, (target_object, target_morphism)) in labels.iter().enumerate() {
+                    if target_object != morphism.target_object() {
+                        continue;
+                    }
+                    // K(u): (c, f) -> (c', f') is a morphism of K ↓ d when f' ∘ K(u) = f
+                    // and of d ↓ K when K(u) ∘ f = f'
+                    let commutes = if over {
+                        along_category.compose_morphisms(mapped_morphism, target_morphism)?
+                            == *source_morphism
+                    } else {
+                        along_category.compose_morphisms(source_morphism, mapped_morphism)?
+                            == *target_morphism
+                    };
+                    if commutes {
+                        diagram.add_edge(
+                            source_index,
+                            target_index,
+                            functor.map_morphism(morphism)?.clone(),
+                        )?;
+                    }
+                }
+            }
+        }
+        Ok(CommaDiagram { labels, diagram })
+    }
+
+    fn index_of(
+        &self,
+        object: &SourceCategory::Object,
+        morphism: &Rc<AlongCategory::Morphism>,
+    ) -> Result<usize, Errors> {
+        self.labels
+            .iter()
+            .position(|(label_object, label_morphism)| {
+                &**label_object == object && label_morphism == morphism
+            })
+            .ok_or(Errors::InvalidDiagram)
+    }
+}
+
+pub fn left_kan_extension<SourceCategory, AlongCategory, TargetCategory>(
+    functor: &Rc<Functor<SourceCategory, TargetCategory>>,
+    along: &Rc<Functor<SourceCategory, AlongCategory>>,
+) -> Result<KanExtension<SourceCategory, AlongCategory, TargetCategory>, Errors>
+where
+    SourceCategory: CategoryTrait,
+    AlongCategory: CategoryTrait,
+    TargetCategory: FiniteColimitsTrait,
+{
+    let along_category = along.target_object();
+    let target_category = functor.target_object();
+
+    let mut colimits = HashMap::new();
+    for object in along_category.get_all_objects()? {
+        let comma = CommaDiagram::new(functor, along, object, true)?;
+        let colimit = target_category.colimit(&comma.diagram)?;
+        colimits.insert(object.category_id().clone(), (comma, colimit));
+    }
+
+    let mut mappings = HashMap::new();
+    for object in along_category.get_all_objects()? {
+        let (comma, colimit) = &colimits[object.category_id()];
+        for morphism in along_category.get_hom_set_x(object)? {
+            if morphism.is_identity() {
+                // the colimit into itself, whose apex the target need not hold as an object
+                let identity =
+                    target_category.colimit_mediating_morphism(&comma.diagram, colimit, colimit)?;
+                mappings.insert(morphism.clone(), identity);
+                continue;
+            }
+            // g: d -> d' sends (c, f) to (c, g ∘ f)
+            let (target_comma, target_colimit) = &colimits[morphism.target_object().category_id()];
+            let mut legs = Vec::new();
+            for (source_object, source_morphism) in &comma.labels {
+                let composite = along_category.compose_morphisms(source_morphism, morphism)?;
+                let index = target_comma.index_of(source_object, &composite)?;
+                legs.push(target_colimit.legs()[index].clone());
+            }
+            let cocone = Cocone::new(target_colimit.apex().clone(), legs);
+            let mediating =
+                target_category.colimit_mediating_morphism(&comma.diagram, colimit, &cocone)?;
+            mappings.insert(morphism.clone(), mediating);
+        }
+    }
+    let extension = Rc::new(Functor::new(
+        String::generate(),
+        along_category.clone(),
+        target_category.clone(),
+        mappings,
+    ));
+
+    let mut components = HashMap::new();
+    for object in functor.source_object().get_all_objects()? {
+        let mapped_object = along.map_object(object)?;
+        let (comma, colimit) = &colimits[mapped_object.category_id()];
+        let identity = along_category.get_identity_morphism(mapped_object)?;
+        let index = comma.index_of(object, identity)?;
+        components.insert(object.category_id().clone(), colimit.legs()[index].clone());
+    }
+    let composite = Rc::new(along.compose_functor(&extension)?);
+    let unit = NaturalTransformation::new(functor.clone(), composite, components);
+
+    Ok(KanExtension {
+        extension,
+        universal_transformation: unit,
+    })
+}
+
+pub fn right_kan_extension<SourceCategory, AlongCategory, TargetCategory>(
+    functor: &Rc<Functor<SourceCategory, TargetCategory>>,
+    along: &Rc<Functor<SourceCategory, AlongCategory>>,
+) -> Result<KanExtension<SourceCategory, AlongCategory, TargetCategory>, Errors>
+where
+    SourceCategory: CategoryTrait,
+    AlongCategory: CategoryTrait,
+    TargetCategory: FiniteLimitsTrait,
+{
+    let along_category = along.target_object();
+    let target_category = functor.target_object();
+
+    let mut limits = HashMap::new();
+    for object in along_category.get_all_objects()? {
+        let comma = CommaDiagram::new(functor, along, object, false)?;
+        let limit = target_category.limit(&comma.diagram)?;
+        limits.insert(object.category_id().clone(), (comma, limit));
+    }
+
+    let mut mappings = HashMap::new();
+    for object in along_category.get_all_objects()? {
+        let (comma, limit) = &limits[object.category_id()];
+        for morphism in along_category.get_hom_set_x(object)? {
+            if morphism.is_identity() {
+                // the limit into itself, whose apex the target need not hold as an object
+                let identity =
+                    target_category.limit_mediating_morphism(&comma.diagram, limit, limit)?;
+                mappings.insert(morphism.clone(), identity);
+                continue;
+            }
+            // g: d -> d' sends (c, f') of d' ↓ K to (c, f' ∘ g) of d ↓ K
+            let (target_comma, target_limit) = &limits[morphism.target_object().category_id()];
+            let mut legs = Vec::new();
+            for (target_object, target_morphism) in &target_comma.labels {
+                let composite = along_category.compose_morphisms(morphism, target_morphism)?;
+                let index = comma.index_of(target_object, &composite)?;
+                legs.push(limit.legs()[index].clone());
+            }
+            let cone = Cone::new(limit.apex().clone(), legs);
+            let mediating = target_category.limit_mediating_morphism(
+                &target_comma.diagram,
+                target_limit,
+                &cone,
+            )?;
+            mappings.insert(morphism.clone(), mediating);
+        }
+    }
+    let extension = Rc::new(Functor::new(
+        String::generate(),
+        along_category.clone(),
+        target_category.clone(),
+        mappings,
+    ));
+
+    let mut components = HashMap::new();
+    for object in functor.source_object().get_all_objects()? {
+        let mapped_object = along.map_object(object)?;
+        let (comma, limit) = &limits[mapped_object.category_id()];
+        let identity = along_category.get_identity_morphism(mapped_object)?;
+        let index = comma.index_of(object, identity)?;
+        components.insert(object.category_id().clone(), limit.legs()[index].clone());
+    }
+    let composite = Rc::new(along.compose_functor(&extension)?);
+    let counit = NaturalTransformation::new(composite, functor.clone(), components);
+
+    Ok(KanExtension {
+        extension,
+        universal_transformation: counit,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::arrow::Morphism;
+    use crate::core::base_category::BaseCategory;
+    use crate::core::concrete_category::fin_set::FinSet;
+    use crate::core::discrete_category::DiscreteCategory;
+    use std::collections::HashSet;
+
+    type Category = BaseCategory<DiscreteCategory>;
+
+    // 0 -> 1
+    fn walking_arrow() -> (Rc<Category>, Rc<DiscreteCategory>, Rc<DiscreteCategory>) {
+        let mut category = Category::new();
+        let zero = Rc::new(DiscreteCategory::new_with_id("0".into()));
+        let one = Rc::new(DiscreteCategory::new_with_id("1".into()));
+        category.add_object(zero.clone()).unwrap();
+        category.add_object(one.clone()).unwrap();
+        category
+            .add_morphism(Rc::new(Morphism::new(
+                "f".to_string(),
+                zero.clone(),
+                one.clone(),
+                HashMap::new(),
+            )))
+            .unwrap();
+        (Rc::new(category), zero, one)
+    }
+
+    fn point_functor(
+        point: &Rc<Category>,
+        star: &DiscreteCategory,
+        target: &Rc<Category>,
+        object: &DiscreteCategory,
+    ) -> Rc<Functor<Category, Category>> {
+        let mappings = HashMap::from([(
+            point.get_identity_morphism(star).unwrap().clone(),
+            target.get_identity_morphism(object).unwrap().clone(),
+        )]);
+        Rc::new(Functor::new(
+            String::generate(),
+            point.clone(),
+            target.clone(),
+            mappings,
+        ))
+    }
+
+    #[test]
+    fn test_kan_extensions_along_point() {
+        let star = Rc::new(DiscreteCategory::new_with_id("*".into()));
+        let point: Rc<Category> = Rc::new(vec!["*"].into());
+        let (along_category, zero, one) = walking_arrow();
+        let (target_category, target_zero, target_one) = walking_arrow();
+
+        // K picks 0 and F picks the initial object of the target
+        let along = point_functor(&point, &star, &along_category, &zero);
+        let functor = point_functor(&point, &star, &target_category, &target_zero);
+
+        let left = left_kan_extension(&functor, &along).unwrap();
+        let extension = left.extension();
+        assert_eq!(extension.map_object(&zero).unwrap(), &target_zero);
+        assert_eq!(extension.map_object(&one).unwrap(), &target_zero);
+        left.universal_transformation().validate().unwrap();
+
+        // Ran_K F(1) is the limit of an empty diagram, i.e. the terminal object
+        let right = right_kan_extension(&functor, &along).unwrap();
+        let extension = right.extension();
+        assert_eq!(extension.map_object(&zero).unwrap(), &target_zero);
+        assert_eq!(extension.map_object(&one).unwrap(), &target_one);
+        let f = along_category.get_hom_set(&zero, &one).unwrap();
+        let mapped = extension.map_morphism(f.iter().next().unwrap()).unwrap();
+        assert!(!mapped.is_identity());
+        right.universal_transformation().validate().unwrap();
+    }
+
+    // the functor from a discrete category picking a set for every object
+    fn sets_functor(
+        source: &Rc<Category>,
+        fin_set: &Rc<FinSet>,
+        sets: &[(&str, &Rc<DiscreteCategory>)],
+    ) -> Rc<Functor<Category, FinSet>> {
+        let mappings = sets
+            .iter()
+            .map(|(object, set)| {
+                (
+                    source
+                        .get_identity_morphism(&DiscreteCategory::new_with_id((*object).into()))
+                        .unwrap()
+                        .clone(),
+                    fin_set.get_identity_morphism(set).unwrap().clone(),
+                )
+            })
+            .collect();
+        Rc::new(Functor::new(
+            String::generate(),
+            source.clone(),
+            fin_set.clone(),
+            mappings,
+        ))
+    }
+
+    #[test]
+    fn test_kan_extensions_into_sets() {
+        let mut fin_set = FinSet::new();
+        let letters = fin_set.add_set("A", vec!["a", "b", "c"]).unwrap();
+        let numbers = fin_set.add_set("N", vec![1, 2]).unwrap();
+        let fin_set = Rc::new(fin_set);
+
+        // along the identity of a point both extensions give back a copy of A
+        let point: Rc<Category> = Rc::new(vec!["*"].into());
+        let star = DiscreteCategory::new_with_id("*".into());
+        let identity = Rc::new(Functor::new(
+            String::generate(),
+            point.clone(),
+            point.clone(),
+            HashMap::from([(
+                point.get_identity_morphism(&star).unwrap().clone(),
+                point.get_identity_morphism(&star).unwrap().clone(),
+            )]),
+        ));
+        let functor = sets_functor(&point, &fin_set, &[("*", &letters)]);
+        let left = left_kan_extension(&functor, &identity).unwrap();
+        left.extension().validate_mappings().unwrap();
+        left.universal_transformation().validate().unwrap();
+        let unit = left.universal_transformation().component(&star).unwrap();
+        // the unit is a bijection from A onto the copy
+        let table = FinSet::function_table(unit).unwrap();
+        let images = table.values().collect::<HashSet<_>>();
+        assert_eq!(table.len(), 3);
+        assert_eq!(images.len(), 3);
+        assert_eq!(FinSet::elements(unit.target_object()).len(), 3);
+        let right = right_kan_extension(&functor, &identity).unwrap();
+        right.extension().validate_mappings().unwrap();
+        right.universal_transformation().validate().unwrap();
+        let mapped = right.extension().map_object(&star).unwrap();
+        assert_eq!(FinSet::elements(mapped).len(), 3);
+
+        // along x, y ↦ * the extensions of x ↦ A, y ↦ N are A + N and A × N
+        let pair: Rc<Category> = Rc::new(vec!["x", "y"].into());
+        let terminal = point.get_identity_morphism(&star).unwrap().clone();
+        let along = Rc::new(Functor::new(
+            String::generate(),
+            pair.clone(),
+            point.clone(),
+            ["x", "y"]
+                .into_iter()
+                .map(|object| {
+                    let object = DiscreteCategory::new_with_id(object.into());
+                    let identity = pair.get_identity_morphism(&object).unwrap();
+                    (identity.clone(), terminal.clone())
+                })
+                .collect(),
+        ));
+        let functor = sets_functor(&pair, &fin_set, &[("x", &letters), ("y", &numbers)]);
+        let left = left_kan_extension(&functor, &along).unwrap();
+        let coproduct = left.extension().map_object(&star).unwrap();
+        assert_eq!(FinSet::elements(coproduct).len(), 5);
+        // the identity of * goes to the identity of A + N, which is built but not added
+        let identity = left.extension().map_morphism(&terminal).unwrap();
+        assert!(identity.is_identity());
+        assert!(Rc::ptr_eq(identity.source_object(), coproduct));
+        assert!(fin_set.get_identity_morphism(coproduct).is_err());
+        left.universal_transformation().validate().unwrap();
+        let right = right_kan_extension(&functor, &along).unwrap();
+        let product = right.extension().map_object(&star).unwrap();
+        assert_eq!(FinSet::elements(product).len(), 6);
+        right.universal_transformation().validate().unwrap();
+    }
+
+    #[test]
+    fn test_left_kan_extension_requires_colimits() {
+        // a discrete target with two objects has no coproduct of them
+        let point: Rc<Category> = Rc::new(vec!["x", "y"].into());
+        let target: Rc<Category> = Rc::new(vec!["a", "b"].into());
+        let terminal: Rc<Category> = Rc::new(vec!["*"].into());
+        let x = DiscreteCategory::new_with_id("x".into());
+        let y = DiscreteCategory::new_with_id("y".into());
+        let star = DiscreteCategory::new_with_id("*".into());
+        let mappings = HashMap::from([
+            (
+                point.get_identity_morphism(&x).unwrap().clone(),
+                target
+                    .get_identity_morphism(&DiscreteCategory::new_with_id("a".into()))
+                    .unwrap()
+                    .clone(),
+            ),
+            (
+                point.get_identity_morphism(&y).unwrap().clone(),
+                target
+                    .get_identity_morphism(&DiscreteCategory::new_with_id("b".into()))
+                    .unwrap()
+                    .clone(),
+            ),
+        ]);
+        let functor = Rc::new(Functor::new(
+            String::generate(),
+            point.clone(),
+            target.clone(),
+            mappings,
+        ));
+        let identity = terminal.get_identity_morphism(&star).unwrap().clone();
+        let along = Rc::new(Functor::new(
+            String::generate(),
+            point.clone(),
+            terminal.clone(),
+            HashMap::from([
+                (
+                    point.get_identity_morphism(&x).unwrap().clone(),
+                    identity.clone(),
+                ),
+                (point.get_identity_morphism(&y).unwrap().clone(), identity),
+            ]),
+        ));
+        assert_eq!(
+            left_kan_extension(&functor, &along).err(),
+            Some(Errors::ColimitNotFound)
+        );
+    }
+}
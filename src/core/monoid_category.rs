@@ -0,0 +1,514 @@
+/*
+A monoid as a category with one object.
+
+The object is the carrier, a DiscreteCategory of the elements, and every element m is
+the morphism x -> m·x on it (the Cayley representation), named after the element.
+Composing m then n gives x -> n·(m·x), the morphism of n·m, and the unit is the identity.
+Multiplication tables are checked for closure, associativity and a two-sided unit.
+
+A monoid where every element has an inverse is a group, and its category a groupoid.
+Homomorphisms h with h(e) = e and h(a·b) = h(a)·h(b) are functors between these categories.
+ */
+use crate::core::arrow::{Functor, Morphism};
+use crate::core::concrete_category::fin_set::{FinSet, Function};
+use crate::core::discrete_category::DiscreteCategory;
+use crate::core::errors::Errors;
+use crate::core::identifier::Identifier;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+pub struct MonoidCategory {
+    carrier: Rc<DiscreteCategory>,
+    elements: Vec<ObjectId>,
+    products: HashMap<(ObjectId, ObjectId), ObjectId>,
+    unit: ObjectId,
+    morphisms: HashMap<ObjectId, Rc<Function>>,
+    // the element of each morphism, by arrow id
+    arrow_elements: HashMap<String, ObjectId>,
+    // filled in for groups
+    inverses: Option<HashMap<ObjectId, ObjectId>>,
+}
+
+impl PartialEq for MonoidCategory {
+    fn eq(&self, other: &Self) -> bool {
+        self.carrier.category_id() == other.carrier.category_id()
+    }
+}
+
+impl Eq for MonoidCategory {}
+
+impl Hash for MonoidCategory {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.carrier.category_id().hash(state);
+    }
+}
+
+impl MonoidCategory {
+    // table[i][j] is the product carrier[i]·carrier[j]
+    pub fn from_table<T: Into<ObjectId> + Clone>(
+        id: impl Into<ObjectId>,
+        carrier: Vec<T>,
+        table: Vec<Vec<T>>,
+    ) -> Result<Self, Errors> {
+        let elements = carrier.into_iter().map(Into::into).collect::<Vec<_>>();
+        if table.len() != elements.len() || table.iter().any(|row| row.len() != elements.len()) {
+            return Err(Errors::InvalidMonoid(format!(
+                "The multiplication table must be {} × {}",
+                elements.len(),
+                elements.len()
+            )));
+        }
+        let mut products = HashMap::new();
+        for (left, row) in elements.iter().zip(table) {
+            for (right, product) in elements.iter().zip(row) {
+                products.insert((left.clone(), right.clone()), product.into());
+            }
+        }
+        Self::new(id.into(), elements, products)
+    }
+
+    pub fn from_fn<T: Into<ObjectId> + Clone>(
+        id: impl Into<ObjectId>,
+        carrier: Vec<T>,
+        multiply: impl Fn(&T, &T) -> T,
+    ) -> Result<Self, Errors> {
+        let mut products = HashMap::new();
+        for left in &carrier {
+            for right in &carrier {
+                products.insert(
+                    (left.clone().into(), right.clone().into()),
+                    multiply(left, right).into(),
+                );
+            }
+        }
+        Self::new(
+            id.into(),
+            carrier.into_iter().map(Into::into).collect(),
+            products,
+        )
+    }
+
+    // words of length at most max_length over the letters, with concatenation,
+    // where every longer word collapses to the absorbing element "⊥"
+    pub fn truncated_free_monoid(
+        id: impl Into<ObjectId>,
+        letters: &[&str],
+        max_length: usize,
+    ) -> Result<Self, Errors> {
+        let mut words = vec![Vec::<&str>::new()];
+        let mut layer = words.clone();
+        for _ in 0..max_length {
+            layer = layer
+                .iter()
+                .flat_map(|word| {
+                    letters.iter().map(move |letter| {
+                        let mut word = word.clone();
+                        word.push(letter);
+                        word
+                    })
+                })
+                .collect();
+            words.extend(layer.iter().cloned());
+        }
+        let name = |word: &[&str]| match word.is_empty() {
+            true => "ε".to_string(),
+            false => word.concat(),
+        };
+        let letters_of = words
+            .iter()
+            .map(|word| (name(word), word.clone()))
+            .collect::<HashMap<_, _>>();
+        let mut carrier = words.iter().map(|word| name(word)).collect::<Vec<_>>();
+        carrier.push("⊥".to_string());
+        Self::from_fn(id, carrier, |left, right| {
+            match (letters_of.get(left), letters_of.get(right)) {
+                (Some(left), Some(right)) if left.len() + right.len() <= max_length => {
+                    name(&[left.as_slice(), right.as_slice()].concat())
+                }
+                _ => "⊥".to_string(),
+            }
+        })
+    }
+
+    // checks that every element has an inverse and records them
+    pub fn into_group(mut self) -> Result<Self, Errors> {
+        let mut inverses = HashMap::new();
+        for element in &self.elements {
+            let inverse = self
+                .elements
+                .iter()
+                .find(|other| {
+                    self.multiply(element, other).ok() == Some(&self.unit)
+                        && self.multiply(other, element).ok() == Some(&self.unit)
+                })
+                .ok_or_else(|| Errors::InvalidMonoid(format!("{} has no inverse", element)))?;
+            inverses.insert(element.clone(), inverse.clone());
+        }
+        self.inverses = Some(inverses);
+        Ok(self)
+    }
+
+    pub fn group_from_fn<T: Into<ObjectId> + Clone>(
+        id: impl Into<ObjectId>,
+        carrier: Vec<T>,
+        multiply: impl Fn(&T, &T) -> T,
+    ) -> Result<Self, Errors> {
+        Self::from_fn(id, carrier, multiply)?.into_group()
+    }
+
+    pub fn carrier(&self) -> &Rc<DiscreteCategory> {
+        &self.carrier
+    }
+
+    pub fn elements(&self) -> &Vec<ObjectId> {
+        &self.elements
+    }
+
+    pub fn unit(&self) -> &ObjectId {
+        &self.unit
+    }
+
+    pub fn multiply(&self, left: &ObjectId, right: &ObjectId) -> Result<&ObjectId, Errors> {
+        self.products
+            .get(&(left.clone(), right.clone()))
+            .ok_or_else(|| Errors::ObjectNotFound(left.clone()))
+    }
+
+    pub fn is_group(&self) -> bool {
+        self.inverses.is_some()
+    }
+
+    pub fn inverse(&self, element: &ObjectId) -> Option<&ObjectId> {
+        self.inverses.as_ref()?.get(element)
+    }
+
+    // the morphism x -> m·x of an element m
+    pub fn element_morphism(&self, element: &ObjectId) -> Result<&Rc<Function>, Errors> {
+        self.morphisms
+            .get(element)
+            .ok_or_else(|| Errors::ObjectNotFound(element.clone()))
+    }
+
+    pub fn element_of(&self, morphism: &Function) -> Result<&ObjectId, Errors> {
+        self.arrow_elements
+            .get(morphism.arrow_id())
+            .ok_or(Errors::MorphismNotFound)
+    }
+
+    // the homomorphism given by its action on elements, as a functor
+    pub fn homomorphism(
+        source: &Rc<MonoidCategory>,
+        target: &Rc<MonoidCategory>,
+        table: &HashMap<ObjectId, ObjectId>,
+    ) -> Result<Functor<MonoidCategory, MonoidCategory>, Errors> {
+        let image = |element: &ObjectId| {
+            table
+                .get(element)
+                .ok_or_else(|| Errors::InvalidFunctor(format!("No image for element {}", element)))
+        };
+        if image(&source.unit)? != &target.unit {
+            return Err(Errors::InvalidFunctor(format!(
+                "The unit {} is not mapped to the unit {}",
+                source.unit, target.unit
+            )));
+        }
+        for left in &source.elements {
+            for right in &source.elements {
+                let product = image(source.multiply(left, right)?)?;
+                if product != target.multiply(image(left)?, image(right)?)? {
+                    return Err(Errors::InvalidFunctor(format!(
+                        "h({}·{}) is not h({})·h({})",
+                        left, right, left, right
+                    )));
+                }
+            }
+        }
+        let mappings = source
+            .elements
+            .iter()
+            .map(|element| {
+                Ok((
+                    source.element_morphism(element)?.clone(),
+                    target.element_morphism(image(element)?)?.clone(),
+                ))
+            })
+            .collect::<Result<HashMap<_, _>, Errors>>()?;
+        Ok(Functor::new(
+            String::generate(),
+            source.clone(),
+            target.clone(),
+            mappings,
+        ))
+    }
+
+    fn new(
+        id: ObjectId,
+        elements: Vec<ObjectId>,
+        products: HashMap<(ObjectId, ObjectId), ObjectId>,
+    ) -> Result<Self, Errors> {
+        let carrier = Rc::new(FinSet::new_set(id, elements.clone())?);
+        let elements = FinSet::elements(&carrier);
+        let product = |left: &ObjectId, right: &ObjectId| {
+            products
+                .get(&(left.clone(), right.clone()))
+                .filter(|product| {
+                    carrier
+                        .get_object(&DiscreteCategory::new_with_id((*product).clone()))
+                        .is_ok()
+                })
+                .ok_or_else(|| {
+                    Errors::InvalidMonoid(format!("{}·{} is not in the carrier", left, right))
+                })
+        };
+        for a in &elements {
+            for b in &elements {
+                let ab = product(a, b)?;
+                for c in &elements {
+                    if product(ab, c)? != product(a, product(b, c)?)? {
+                        return Err(Errors::InvalidMonoid(format!(
+                            "({}·{})·{} is not {}·({}·{})",
+                            a, b, c, a, b, c
+                        )));
+                    }
+                }
+            }
+        }
+        let unit = elements
+            .iter()
+            .find(|unit| {
+                elements.iter().all(|element| {
+                    product(unit, element).ok() == Some(element)
+                        && product(element, unit).ok() == Some(element)
+                })
+            })
+            .cloned()
+            .ok_or_else(|| Errors::InvalidMonoid("There is no unit".to_string()))?;
+
+        let mut morphisms = HashMap::new();
+        let mut arrow_elements = HashMap::new();
+        for element in &elements {
+            let table = elements
+                .iter()
+                .map(|other| Ok((other.clone(), product(element, other)?.clone())))
+                .collect::<Result<HashMap<_, _>, Errors>>()?;
+            let mappings = FinSet::function(&carrier, &carrier, &table)?
+                .arrow_mappings()
+                .clone();
+            let morphism = if element == &unit {
+                Morphism::new_identity_with_mappings(element.to_string(), carrier.clone(), mappings)
+            } else {
+                Rc::new(Morphism::new(
+                    element.to_string(),
+                    carrier.clone(),
+                    carrier.clone(),
+                    mappings,
+                ))
+            };
+            arrow_elements.insert(morphism.arrow_id().clone(), element.clone());
+            morphisms.insert(element.clone(), morphism);
+        }
+        Ok(MonoidCategory {
+            carrier,
+            elements,
+            products,
+            unit,
+            morphisms,
+            arrow_elements,
+            inverses: None,
+        })
+    }
+}
+
+impl CategoryTrait for MonoidCategory {
+    type Object = DiscreteCategory;
+
+    type Morphism = Function;
+
+    // the trivial monoid
+    fn new() -> Self {
+        Self::new_with_id(&ObjectId::generate())
+    }
+
+    fn new_with_id(id: &ObjectId) -> Self {
+        MonoidCategory::from_table(id.clone(), vec!["e"], vec![vec!["e"]])
+            .and_then(MonoidCategory::into_group)
+            .expect("the trivial group is a monoid")
+    }
+
+    fn category_id(&self) -> &ObjectId {
+        self.carrier.category_id()
+    }
+
+    fn update_category_id(&mut self, _new_id: ObjectId) {}
+
+    fn add_object(&mut self, _object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        Err(Errors::InvalidOperation(
+            "A monoid has exactly one object".to_string(),
+        ))
+    }
+
+    fn add_morphism(
+        &mut self,
+        _morphism: Rc<Self::Morphism>,
+    ) -> Result<&Rc<Self::Morphism>, Errors> {
+        Err(Errors::InvalidOperation(
+            "The morphisms of a monoid are given by its multiplication table".to_string(),
+        ))
+    }
+
+    fn get_identity_morphism(&self, object: &Self::Object) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.get_object(object)?;
+        self.element_morphism(&self.unit)
+    }
+
+    fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
+        if object.category_id() != self.carrier.category_id() {
+            return Err(Errors::ObjectNotFound(object.category_id().clone()));
+        }
+        Ok(&self.carrier)
+    }
+
+    fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
+        Ok(HashSet::from([&self.carrier]))
+    }
+
+    fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self.morphisms.values().collect())
+    }
+
+    fn get_hom_set_x(
+        &self,
+        source_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        self.get_object(source_object)?;
+        self.get_all_morphisms()
+    }
+
+    fn get_object_morphisms(
+        &self,
+        object: &Self::Object,
+    ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
+        self.get_object(object)?;
+        self.elements
+            .iter()
+            .map(|element| self.element_morphism(element))
+            .collect()
+    }
+
+    // m then n is x -> n·(m·x), the morphism of n·m
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        let product = self.multiply(self.element_of(second)?, self.element_of(first)?)?;
+        Ok(self.element_morphism(product)?.clone())
+    }
+
+    fn inverse_morphism(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        match &self.inverses {
+            Some(inverses) => {
+                let inverse = &inverses[self.element_of(morphism)?];
+                Ok(Some(self.element_morphism(inverse)?.clone()))
+            }
+            None => {
+                let element = self.element_of(morphism)?;
+                let inverse = self.elements.iter().find(|other| {
+                    self.multiply(element, other).ok() == Some(&self.unit)
+                        && self.multiply(other, element).ok() == Some(&self.unit)
+                });
+                inverse
+                    .map(|inverse| Ok(self.element_morphism(inverse)?.clone()))
+                    .transpose()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cyclic_group(order: i32) -> MonoidCategory {
+        MonoidCategory::group_from_fn(format!("Z{}", order), (0..order).collect(), |a, b| {
+            (a + b) % order
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_monoids_and_groups() {
+        let z3 = cyclic_group(3);
+        assert_eq!(z3.unit(), &ObjectId::Int(0));
+        assert_eq!(z3.inverse(&1.into()), Some(&ObjectId::Int(2)));
+        let one = z3.element_morphism(&1.into()).unwrap().clone();
+        let two = z3.compose_morphisms(&one, &one).unwrap();
+        assert_eq!(z3.element_of(&two).unwrap(), &ObjectId::Int(2));
+        assert!(z3.compose_morphisms(&one, &two).unwrap().is_identity());
+        assert_eq!(z3.inverse_morphism(&one).unwrap().unwrap(), two);
+        // the Cayley morphism of 1 shifts the carrier
+        assert_eq!(FinSet::apply(&one, &2.into()).unwrap(), ObjectId::Int(0));
+
+        // ({0, 1}, ·) is a monoid but 0 has no inverse
+        let multiplication =
+            MonoidCategory::from_table("B", vec![0, 1], vec![vec![0, 0], vec![0, 1]]).unwrap();
+        assert_eq!(multiplication.unit(), &ObjectId::Int(1));
+        let zero = multiplication.element_morphism(&0.into()).unwrap().clone();
+        assert!(multiplication.inverse_morphism(&zero).unwrap().is_none());
+        assert!(matches!(
+            multiplication.clone().into_group(),
+            Err(Errors::InvalidMonoid(_))
+        ));
+
+        // subtraction is not associative, and left projection has no unit
+        assert!(matches!(
+            MonoidCategory::from_fn("sub", vec![0, 1, 2], |a, b| (a - b).rem_euclid(3)),
+            Err(Errors::InvalidMonoid(_))
+        ));
+        assert!(matches!(
+            MonoidCategory::from_fn("left", vec![0, 1], |a, _| *a),
+            Err(Errors::InvalidMonoid(_))
+        ));
+    }
+
+    #[test]
+    fn test_truncated_free_monoid() {
+        let words = MonoidCategory::truncated_free_monoid("words", &["a", "b"], 2).unwrap();
+        // ε, a, b, aa, ab, ba, bb and ⊥
+        assert_eq!(words.elements().len(), 8);
+        assert_eq!(words.unit(), &ObjectId::from("ε"));
+        let a = words.element_morphism(&"a".into()).unwrap().clone();
+        let b = words.element_morphism(&"b".into()).unwrap().clone();
+        // a then b acts as x -> b·(a·x), the word ba
+        let ba = words.compose_morphisms(&a, &b).unwrap();
+        assert_eq!(words.element_of(&ba).unwrap(), &ObjectId::from("ba"));
+        let too_long = words.compose_morphisms(&ba, &a).unwrap();
+        assert_eq!(words.element_of(&too_long).unwrap(), &ObjectId::from("⊥"));
+    }
+
+    #[test]
+    fn test_homomorphisms_are_functors() {
+        let z4 = Rc::new(cyclic_group(4));
+        let z2 = Rc::new(cyclic_group(2));
+        let modulo = (0..4).map(|n| (n.into(), (n % 2).into())).collect();
+        let functor = MonoidCategory::homomorphism(&z4, &z2, &modulo).unwrap();
+        let three = z4.element_morphism(&3.into()).unwrap();
+        assert_eq!(
+            functor.map_morphism(three).unwrap(),
+            z2.element_morphism(&1.into()).unwrap()
+        );
+        assert_eq!(functor.map_object(z4.carrier()).unwrap(), z2.carrier());
+
+        // n -> n/2 is not additive
+        let not_additive = (0..4).map(|n| (n.into(), (n / 2).into())).collect();
+        assert!(MonoidCategory::homomorphism(&z4, &z2, &not_additive).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+/*
+A natural transformation α: F => G between two functors F, G: C -> D
+assigns to every object c of C a morphism α_c: F(c) -> G(c) in D
+such that for every morphism f: a -> b in C the square commutes:
+
+    G(f) ∘ α_a = α_b ∘ F(f)
+ */
+use crate::core::arrow::Functor;
+use crate::core::errors::Errors;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+#[derive(Debug)]
+pub struct NaturalTransformation<SourceCategory: CategoryTrait, TargetCategory: CategoryTrait> {
+    source_functor: Rc<Functor<SourceCategory, TargetCategory>>,
+    target_functor: Rc<Functor<SourceCategory, TargetCategory>>,
+    // components keyed by the id of the object of the source category
+    components: HashMap<ObjectId, Rc<TargetCategory::Morphism>>,
+}
+
+impl<SourceCategory: CategoryTrait, TargetCategory: CategoryTrait>
+    NaturalTransformation<SourceCategory, TargetCategory>
+{
+    pub fn new(
+        source_functor: Rc<Functor<SourceCategory, TargetCategory>>,
+        target_functor: Rc<Functor<SourceCategory, TargetCategory>>,
+        components: HashMap<ObjectId, Rc<TargetCategory::Morphism>>,
+    ) -> Self {
+        NaturalTransformation {
+            source_functor,
+            target_functor,
+            components,
+        }
+    }
+
+    pub fn source_functor(&self) -> &Rc<Functor<SourceCategory, TargetCategory>> {
+        &self.source_functor
+    }
+
+    pub fn target_functor(&self) -> &Rc<Functor<SourceCategory, TargetCategory>> {
+        &self.target_functor
+    }
+
+    pub fn components(&self) -> &HashMap<ObjectId, Rc<TargetCategory::Morphism>> {
+        &self.components
+    }
+
+    pub fn component(
+        &self,
+        object: &SourceCategory::Object,
+    ) -> Result<&Rc<TargetCategory::Morphism>, Errors> {
+        self.components
+            .get(object.category_id())
+            .ok_or(Errors::InvalidNaturalTransformation(format!(
+                "No component for object {}",
+                object.category_id()
+            )))
+    }
+
+    pub fn validate(&self) -> Result<(), Errors> {
+        let source_category = self.source_functor.source_object();
+        let target_category = self.source_functor.target_object();
+        if source_category.category_id() != self.target_functor.source_object().category_id()
+            || target_category.category_id() != self.target_functor.target_object().category_id()
+        {
+            return Err(Errors::InvalidNaturalTransformation(
+                "Functors are not parallel".to_string(),
+            ));
+        }
+
+        for object in source_category.get_all_objects()? {
+            let component = self.component(object)?;
+            if component.source_object() != self.source_functor.map_object(object)?
+                || component.target_object() != self.target_functor.map_object(object)?
+            {
+                return Err(Errors::InvalidNaturalTransformation(format!(
+                    "Component at {} has the wrong boundary",
+                    object.category_id()
+                )));
+            }
+        }
+
+        for object in source_category.get_all_objects()? {
+            for morphism in source_category.get_hom_set_x(object)? {
+                // G(f) ∘ α_a
+                let first_path = target_category.compose_morphisms(
+                    self.component(morphism.source_object())?,
+                    self.target_functor.map_morphism(morphism)?,
+                )?;
+                // α_b ∘ F(f)
+                let second_path = target_category.compose_morphisms(
+                    self.source_functor.map_morphism(morphism)?,
+                    self.component(morphism.target_object())?,
+                )?;
+                if first_path != second_path {
+                    return Err(Errors::InvalidNaturalTransformation(format!(
+                        "Naturality square does not commute at {}",
+                        object.category_id()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
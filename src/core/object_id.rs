@@ -1,36 +1,148 @@
+/*
+Ids of objects and elements. Besides the plain Int, Str and Bool ids there are structured
+ids for the things built out of other ids, so that different constructions never share an id:
+    - Product: a tuple of ids, written (a,b) and (a,) for a single component
+    - Coproduct: an id tagged with the index of its summand, written in1(a)
+    - Path: ids of nested categories from the root, written /a/b
+    - Generated: a named construction applied to ids, written eq(f,g)
+
+A Str which would read as something else is quoted, on its own as well as inside a
+structured id, e.g. "1", "/x" or ("a,b",c), so the Display of every id parses back to it.
+ */
+use crate::core::errors::Errors;
 use std::fmt::Display;
 use std::ops::Add;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ObjectId {
     Int(i32),
     Str(String),
     Bool(bool),
+    Product(Vec<ObjectId>),
+    // the index of the summand and the id inside it
+    Coproduct(usize, Box<ObjectId>),
+    Path(Vec<ObjectId>),
+    // the name of the construction and its arguments
+    Generated(String, Vec<ObjectId>),
 }
 
-impl Add for ObjectId {
-    type Output = Self;
+impl ObjectId {
+    pub fn product(components: impl IntoIterator<Item = ObjectId>) -> Self {
+        ObjectId::Product(components.into_iter().collect())
+    }
 
-    fn add(self, other: Self) -> Self {
-        use ObjectId::*;
-        match (self, other) {
-            (Int(a), Int(b)) => Int(a + b),
-            (Str(a), Str(b)) => Str(a + &b),
-            (Bool(a), Bool(b)) => Bool(a || b),
-            _ => panic!("Cannot add different types of ObjectId"),
+    pub fn coproduct(index: usize, id: ObjectId) -> Self {
+        ObjectId::Coproduct(index, Box::new(id))
+    }
+
+    pub fn generated(name: &str, arguments: impl IntoIterator<Item = ObjectId>) -> Self {
+        ObjectId::Generated(name.to_string(), arguments.into_iter().collect())
+    }
+
+    // the components of a Product
+    pub fn components(&self) -> Option<&[ObjectId]> {
+        match self {
+            ObjectId::Product(components) => Some(components),
+            _ => None,
+        }
+    }
+
+    // the component of a Product at the index
+    pub fn project(&self, index: usize) -> Option<&ObjectId> {
+        self.components()?.get(index)
+    }
+
+    // the summand index and the id of a Coproduct
+    pub fn injection(&self) -> Option<(usize, &ObjectId)> {
+        match self {
+            ObjectId::Coproduct(index, id) => Some((*index, id)),
+            _ => None,
+        }
+    }
+
+    pub fn segments(&self) -> Option<&[ObjectId]> {
+        match self {
+            ObjectId::Path(segments) => Some(segments),
+            _ => None,
+        }
+    }
+
+    // the name and the arguments of a Generated id
+    pub fn construction(&self) -> Option<(&str, &[ObjectId])> {
+        match self {
+            ObjectId::Generated(name, arguments) => Some((name, arguments)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn fmt_segments(
+        segments: &[ObjectId],
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        for (index, segment) in segments.iter().enumerate() {
+            if index > 0 {
+                write!(f, "/")?;
+            }
+            // a path inside a path needs parentheses to keep its slashes apart
+            if let ObjectId::Path(_) = segment {
+                write!(f, "({})", segment)?;
+            } else {
+                write!(f, "{}", segment)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn fmt_list(components: &[ObjectId], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, component) in components.iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", component)?;
         }
+        Ok(())
     }
 }
 
-impl PartialEq for ObjectId {
-    fn eq(&self, other: &Self) -> bool {
-        use ObjectId::*;
-        match (self, other) {
-            (Int(a), Int(b)) => a == b,
-            (Str(a), Str(b)) => a == b,
-            (Bool(a), Bool(b)) => a == b,
-            _ => false,
+fn is_special(character: char) -> bool {
+    matches!(character, '(' | ')' | ',' | '/' | '"' | '\\') || character.is_whitespace()
+}
+
+// a string which parses back to itself without quotes
+fn is_plain(value: &str) -> bool {
+    !value.is_empty()
+        && !value.chars().any(is_special)
+        && value.parse::<i32>().is_err()
+        && value.parse::<bool>().is_err()
+}
+
+// the names in0, in1, ... are read as coproduct injections
+fn injection_index(name: &str) -> Option<usize> {
+    let digits = name.strip_prefix("in")?;
+    if digits.is_empty() || !digits.chars().all(|digit| digit.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+fn write_quoted(value: &str, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "\"")?;
+    for character in value.chars() {
+        if matches!(character, '"' | '\\') {
+            write!(f, "\\")?;
         }
+        write!(f, "{}", character)?;
+    }
+    write!(f, "\"")
+}
+
+// the pair (self, other), so no two different pairs share an id
+impl Add for ObjectId {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        ObjectId::Product(vec![self, other])
     }
 }
 
@@ -38,12 +150,191 @@ impl Display for ObjectId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ObjectId::Int(v) => write!(f, "{}", v),
-            ObjectId::Str(v) => write!(f, "{}", v),
+            ObjectId::Str(v) if is_plain(v) => write!(f, "{}", v),
+            ObjectId::Str(v) => write_quoted(v, f),
             ObjectId::Bool(v) => write!(f, "{}", v),
+            ObjectId::Product(components) => {
+                write!(f, "(")?;
+                ObjectId::fmt_list(components, f)?;
+                if components.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            ObjectId::Coproduct(index, id) => {
+                write!(f, "in{}({})", index, id)
+            }
+            ObjectId::Path(segments) => {
+                write!(f, "/")?;
+                ObjectId::fmt_segments(segments, f)
+            }
+            ObjectId::Generated(name, arguments) => {
+                if is_plain(name) && injection_index(name).is_none() {
+                    write!(f, "{}", name)?;
+                } else {
+                    write_quoted(name, f)?;
+                }
+                write!(f, "(")?;
+                ObjectId::fmt_list(arguments, f)?;
+                write!(f, ")")
+            }
         }
     }
 }
 
+// reads the Display of any id, failing with InvalidObjectId on anything else
+impl FromStr for ObjectId {
+    type Err = Errors;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            characters: value.chars().collect(),
+            position: 0,
+        };
+        let id = parser.id()?;
+        if parser.peek().is_some() {
+            return Err(Errors::InvalidObjectId);
+        }
+        Ok(id)
+    }
+}
+
+struct Parser {
+    characters: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.characters.get(self.position).copied()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Errors> {
+        if self.peek() != Some(expected) {
+            return Err(Errors::InvalidObjectId);
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn id(&mut self) -> Result<ObjectId, Errors> {
+        if self.peek() != Some('/') {
+            return self.term();
+        }
+        self.position += 1;
+        let mut segments = Vec::new();
+        if matches!(self.peek(), None | Some(',') | Some(')')) {
+            return Ok(ObjectId::Path(segments));
+        }
+        segments.push(self.term()?);
+        while self.peek() == Some('/') {
+            self.position += 1;
+            segments.push(self.term()?);
+        }
+        Ok(ObjectId::Path(segments))
+    }
+
+    fn term(&mut self) -> Result<ObjectId, Errors> {
+        match self.peek() {
+            Some('(') => self.tuple(),
+            Some('"') => {
+                let value = self.quoted()?;
+                if self.peek() == Some('(') {
+                    return Ok(ObjectId::Generated(value, self.arguments()?));
+                }
+                Ok(ObjectId::Str(value))
+            }
+            _ => {
+                let token = self.token()?;
+                if self.peek() == Some('(') {
+                    let mut arguments = self.arguments()?;
+                    return match injection_index(&token) {
+                        Some(index) if arguments.len() == 1 => {
+                            Ok(ObjectId::coproduct(index, arguments.remove(0)))
+                        }
+                        Some(_) => Err(Errors::InvalidObjectId),
+                        None => Ok(ObjectId::Generated(token, arguments)),
+                    };
+                }
+                if let Ok(value) = token.parse::<i32>() {
+                    Ok(ObjectId::Int(value))
+                } else if let Ok(value) = token.parse::<bool>() {
+                    Ok(ObjectId::Bool(value))
+                } else {
+                    Ok(ObjectId::Str(token))
+                }
+            }
+        }
+    }
+
+    // (), (a,), (a,b) or the parenthesized id (a)
+    fn tuple(&mut self) -> Result<ObjectId, Errors> {
+        self.expect('(')?;
+        if self.peek() == Some(')') {
+            self.position += 1;
+            return Ok(ObjectId::Product(Vec::new()));
+        }
+        let first = self.id()?;
+        if self.peek() == Some(')') {
+            self.position += 1;
+            return Ok(first);
+        }
+        let mut components = vec![first];
+        self.expect(',')?;
+        while self.peek() != Some(')') {
+            components.push(self.id()?);
+            if self.peek() != Some(')') {
+                self.expect(',')?;
+            }
+        }
+        self.position += 1;
+        Ok(ObjectId::Product(components))
+    }
+
+    fn arguments(&mut self) -> Result<Vec<ObjectId>, Errors> {
+        self.expect('(')?;
+        let mut arguments = Vec::new();
+        while self.peek() != Some(')') {
+            if !arguments.is_empty() {
+                self.expect(',')?;
+            }
+            arguments.push(self.id()?);
+        }
+        self.position += 1;
+        Ok(arguments)
+    }
+
+    fn quoted(&mut self) -> Result<String, Errors> {
+        self.expect('"')?;
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(Errors::InvalidObjectId),
+                Some('"') => break,
+                Some('\\') => {
+                    self.position += 1;
+                    value.push(self.peek().ok_or(Errors::InvalidObjectId)?);
+                }
+                Some(character) => value.push(character),
+            }
+            self.position += 1;
+        }
+        self.position += 1;
+        Ok(value)
+    }
+
+    fn token(&mut self) -> Result<String, Errors> {
+        let start = self.position;
+        while self.peek().is_some_and(|character| !is_special(character)) {
+            self.position += 1;
+        }
+        if start == self.position {
+            return Err(Errors::InvalidObjectId);
+        }
+        Ok(self.characters[start..self.position].iter().collect())
+    }
+}
+
 impl PartialEq<str> for ObjectId {
     fn eq(&self, other: &str) -> bool {
         matches!(self, ObjectId::Str(v) if v == other)
@@ -85,3 +376,80 @@ impl From<&str> for ObjectId {
         ObjectId::Str(value.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(id: &ObjectId) -> ObjectId {
+        id.to_string().parse().unwrap()
+    }
+
+    #[test]
+    fn test_products_do_not_collide() {
+        let ab_c = ObjectId::from("ab") + ObjectId::from("c");
+        let a_bc = ObjectId::from("a") + ObjectId::from("bc");
+        assert_ne!(ab_c, a_bc);
+        assert_ne!(ObjectId::Int(1) + ObjectId::Int(2), ObjectId::Int(3));
+        // mixed ids pair up instead of panicking
+        let mixed = ObjectId::Int(1) + ObjectId::from("x");
+        assert_eq!(mixed.to_string(), "(1,x)");
+        assert_eq!(mixed.project(1), Some(&ObjectId::from("x")));
+        assert_eq!(mixed.project(2), None);
+    }
+
+    #[test]
+    fn test_display_parses_back() {
+        let ids = vec![
+            ObjectId::product(vec!["a,b".into(), "c".into()]),
+            ObjectId::product(vec!["1".into(), 1.into(), ObjectId::Bool(true), "".into()]),
+            ObjectId::product(vec![ObjectId::product(vec![]), "x".into()]),
+            ObjectId::product(vec!["only".into()]),
+            ObjectId::coproduct(2, ObjectId::product(vec!["a".into(), "b".into()])),
+            ObjectId::Path(vec!["root".into(), "a/b".into(), 3.into()]),
+            ObjectId::Path(vec![
+                ObjectId::Path(vec!["a".into(), "b".into()]),
+                "c".into(),
+            ]),
+            ObjectId::Path(vec![]),
+            ObjectId::generated("eq", vec!["f".into(), "say \"hi\"".into()]),
+            ObjectId::generated("in1", vec!["x".into()]),
+            ObjectId::generated("lim", vec![ObjectId::Path(vec!["a".into()])]),
+            // strings on their own which read as other ids
+            "(a,b)".into(),
+            "1".into(),
+            "/x".into(),
+            "true".into(),
+            "".into(),
+            "eq(f)".into(),
+        ];
+        for id in &ids {
+            assert_eq!(&round_trip(id), id, "{}", id);
+        }
+        assert_eq!(ids[0].to_string(), "(\"a,b\",c)");
+        assert_eq!(ids[5].to_string(), "/root/\"a/b\"/3");
+        assert_eq!(ids[9].to_string(), "\"in1\"(x)");
+        assert_eq!(ids[11].to_string(), "\"(a,b)\"");
+        assert_eq!(ids[12].to_string(), "\"1\"");
+        assert_eq!(ids[13].to_string(), "\"/x\"");
+        assert_eq!(ObjectId::from("x").to_string(), "x");
+        assert_eq!("(only,)".parse::<ObjectId>().unwrap(), ids[3]);
+        assert_eq!("(x)".parse::<ObjectId>().unwrap(), ObjectId::from("x"));
+        assert_eq!("(a,b".parse::<ObjectId>(), Err(Errors::InvalidObjectId));
+        assert_eq!("in1(a,b)".parse::<ObjectId>(), Err(Errors::InvalidObjectId));
+    }
+
+    #[test]
+    fn test_projections() {
+        let injection = ObjectId::coproduct(1, "b".into());
+        assert_eq!(injection.to_string(), "in1(b)");
+        assert_eq!(injection.injection(), Some((1, &ObjectId::from("b"))));
+        let generated = ObjectId::generated("eq", vec!["f".into(), "g".into()]);
+        let (name, arguments) = generated.construction().unwrap();
+        assert_eq!(name, "eq");
+        assert_eq!(arguments, [ObjectId::from("f"), ObjectId::from("g")]);
+        assert_eq!(generated.components(), None);
+        let path = ObjectId::Path(vec!["a".into(), "b".into()]);
+        assert_eq!(path.segments().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,119 @@
+/*
+Address of an object or morphism inside nested categories, written as its
+ObjectId segments from the root joined by '/', e.g. "schema/users/id".
+Morphisms are addressed by the path of the category holding them followed by their arrow id.
+As an ObjectId it is the Path of its segments, written with a leading '/'.
+ */
+use crate::core::errors::Errors;
+use crate::core::object_id::ObjectId;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ObjectPath {
+    segments: Vec<ObjectId>,
+}
+
+impl ObjectPath {
+    pub fn new(segments: Vec<ObjectId>) -> Self {
+        ObjectPath { segments }
+    }
+
+    pub fn segments(&self) -> &[ObjectId] {
+        &self.segments
+    }
+
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    pub fn last(&self) -> Option<&ObjectId> {
+        self.segments.last()
+    }
+
+    // the path of the category holding this one, None for the root
+    pub fn parent(&self) -> Option<ObjectPath> {
+        if self.segments.is_empty() {
+            return None;
+        }
+        Some(ObjectPath::new(
+            self.segments[..self.segments.len() - 1].to_vec(),
+        ))
+    }
+
+    pub fn child(&self, segment: ObjectId) -> ObjectPath {
+        let mut segments = self.segments.clone();
+        segments.push(segment);
+        ObjectPath::new(segments)
+    }
+}
+
+impl Display for ObjectPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        ObjectId::fmt_segments(&self.segments, f)
+    }
+}
+
+/*
+Segments are read as ObjectIds, so structured and quoted segments round trip through Display.
+A path which does not read that way is split at every '/', its segments which read as
+integers or booleans becoming Int and Bool ids and everything else Str.
+ */
+impl FromStr for ObjectPath {
+    type Err = std::convert::Infallible;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let structured = format!("/{}", path.strip_prefix('/').unwrap_or(path));
+        if let Ok(ObjectId::Path(segments)) = structured.parse() {
+            return Ok(ObjectPath::new(segments));
+        }
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                if let Ok(value) = segment.parse::<i32>() {
+                    ObjectId::Int(value)
+                } else if let Ok(value) = segment.parse::<bool>() {
+                    ObjectId::Bool(value)
+                } else {
+                    ObjectId::Str(segment.to_string())
+                }
+            })
+            .collect();
+        Ok(ObjectPath::new(segments))
+    }
+}
+
+impl From<&str> for ObjectPath {
+    fn from(path: &str) -> Self {
+        let Ok(path) = path.parse();
+        path
+    }
+}
+
+impl From<Vec<ObjectId>> for ObjectPath {
+    fn from(segments: Vec<ObjectId>) -> Self {
+        ObjectPath::new(segments)
+    }
+}
+
+impl From<ObjectPath> for ObjectId {
+    fn from(path: ObjectPath) -> Self {
+        ObjectId::Path(path.segments)
+    }
+}
+
+impl TryFrom<ObjectId> for ObjectPath {
+    type Error = Errors;
+
+    fn try_from(id: ObjectId) -> Result<Self, Self::Error> {
+        match id {
+            ObjectId::Path(segments) => Ok(ObjectPath::new(segments)),
+            _ => Err(Errors::InvalidObjectId),
+        }
+    }
+}
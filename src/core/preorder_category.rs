@@ -0,0 +1,603 @@
+/*
+A preorder as a thin category: the objects are the elements and there is exactly one
+morphism a -> b, named "a≤b", when a ≤ b. Relations added to it are closed reflexively
+and transitively, so composition always lands on the one morphism of its hom-set.
+
+In a thin category limits are greatest lower bounds and colimits least upper bounds,
+so products are meets and coproducts are joins. Monotone maps are functors between
+preorders, and a GaloisConnection is a pair of them f ⊣ g with f(p) ≤ q iff p ≤ g(q).
+ */
+use crate::core::arrow::{Arrow, Functor, Morphism};
+use crate::core::discrete_category::DiscreteCategory;
+use crate::core::errors::Errors;
+use crate::core::identifier::Identifier;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::traits::finite_limits_trait::{
+    Cocone, Cone, FiniteColimitsTrait, FiniteDiagram, FiniteLimitsTrait,
+};
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+pub struct PreorderCategory {
+    id: ObjectId,
+    objects: HashMap<ObjectId, Rc<DiscreteCategory>>,
+    // for each element, the morphisms to the elements above it, identity included
+    hom_sets: HashMap<ObjectId, HashMap<ObjectId, Rc<Morphism<DiscreteCategory>>>>,
+}
+
+impl Default for PreorderCategory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for PreorderCategory {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for PreorderCategory {}
+
+impl Hash for PreorderCategory {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl PreorderCategory {
+    pub fn new() -> Self {
+        Self::new_with_id(ObjectId::generate())
+    }
+
+    pub fn new_with_id(id: ObjectId) -> Self {
+        PreorderCategory {
+            id,
+            objects: HashMap::new(),
+            hom_sets: HashMap::new(),
+        }
+    }
+
+    // the preorder generated by the pairs (a, b) read as a ≤ b
+    pub fn from_relation<T: Into<ObjectId> + Clone>(
+        elements: Vec<T>,
+        pairs: Vec<(T, T)>,
+    ) -> Result<Self, Errors> {
+        let mut preorder = Self::new();
+        for element in elements {
+            preorder.add_element(element)?;
+        }
+        for (lower, upper) in pairs {
+            preorder.add_relation(lower, upper)?;
+        }
+        Ok(preorder)
+    }
+
+    pub fn add_element(&mut self, id: impl Into<ObjectId>) -> Result<Rc<DiscreteCategory>, Errors> {
+        let element = Rc::new(DiscreteCategory::new_with_id(id.into()));
+        self.add_object(element.clone())?;
+        Ok(element)
+    }
+
+    pub fn element(&self, id: &ObjectId) -> Result<&Rc<DiscreteCategory>, Errors> {
+        self.objects
+            .get(id)
+            .ok_or_else(|| Errors::ObjectNotFound(id.clone()))
+    }
+
+    // adds lower ≤ upper together with everything it implies by transitivity
+    pub fn add_relation(
+        &mut self,
+        lower: impl Into<ObjectId>,
+        upper: impl Into<ObjectId>,
+    ) -> Result<Rc<Morphism<DiscreteCategory>>, Errors> {
+        let (lower, upper) = (lower.into(), upper.into());
+        let below = self.down_set(&lower)?;
+        let above = self.up_set(&upper)?;
+        for source in &below {
+            for target in &above {
+                if !self.less_or_equal(source, target) {
+                    let morphism = Rc::new(Arrow::new(
+                        format!("{}≤{}", source, target),
+                        self.element(source)?.clone(),
+                        self.element(target)?.clone(),
+                        HashMap::new(),
+                    ));
+                    self.insert(morphism);
+                }
+            }
+        }
+        self.morphism(&lower, &upper).cloned()
+    }
+
+    pub fn less_or_equal(&self, lower: &ObjectId, upper: &ObjectId) -> bool {
+        self.hom_sets
+            .get(lower)
+            .is_some_and(|hom_set| hom_set.contains_key(upper))
+    }
+
+    // the one morphism lower -> upper
+    pub fn morphism(
+        &self,
+        lower: &ObjectId,
+        upper: &ObjectId,
+    ) -> Result<&Rc<Morphism<DiscreteCategory>>, Errors> {
+        self.hom_sets
+            .get(lower)
+            .ok_or_else(|| Errors::ObjectNotFound(lower.clone()))?
+            .get(upper)
+            .ok_or(Errors::MorphismNotFound)
+    }
+
+    // the elements above an element, sorted by id
+    pub fn up_set(&self, element: &ObjectId) -> Result<Vec<ObjectId>, Errors> {
+        let mut above = self
+            .hom_sets
+            .get(element)
+            .ok_or_else(|| Errors::ObjectNotFound(element.clone()))?
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        above.sort_by_key(|element| element.to_string());
+        Ok(above)
+    }
+
+    pub fn down_set(&self, element: &ObjectId) -> Result<Vec<ObjectId>, Errors> {
+        self.element(element)?;
+        let mut below = self
+            .hom_sets
+            .iter()
+            .filter(|(_, hom_set)| hom_set.contains_key(element))
+            .map(|(lower, _)| lower.clone())
+            .collect::<Vec<_>>();
+        below.sort_by_key(|element| element.to_string());
+        Ok(below)
+    }
+
+    // a ≤ b and b ≤ a only for a = b
+    pub fn is_partial_order(&self) -> bool {
+        self.hom_sets.iter().all(|(lower, hom_set)| {
+            hom_set
+                .keys()
+                .all(|upper| upper == lower || !self.less_or_equal(upper, lower))
+        })
+    }
+
+    // the greatest lower bound of the elements, the product in the category
+    pub fn meet(&self, elements: &[ObjectId]) -> Result<Rc<DiscreteCategory>, Errors> {
+        Ok(self.product_of(elements)?.apex().clone())
+    }
+
+    // the least upper bound of the elements, the coproduct in the category
+    pub fn join(&self, elements: &[ObjectId]) -> Result<Rc<DiscreteCategory>, Errors> {
+        Ok(self.coproduct_of(elements)?.apex().clone())
+    }
+
+    // the monotone map given by its action on elements, as a functor between preorders
+    pub fn monotone_map(
+        source: &Rc<PreorderCategory>,
+        target: &Rc<PreorderCategory>,
+        table: &HashMap<ObjectId, ObjectId>,
+    ) -> Result<Functor<PreorderCategory, PreorderCategory>, Errors> {
+        let mut mappings = HashMap::new();
+        for (lower, hom_set) in &source.hom_sets {
+            for (upper, morphism) in hom_set {
+                let image = |element: &ObjectId| {
+                    table.get(element).ok_or_else(|| {
+                        Errors::InvalidFunctor(format!("No image for element {}", element))
+                    })
+                };
+                let mapped = target.morphism(image(lower)?, image(upper)?).map_err(|_| {
+                    Errors::InvalidFunctor(format!(
+                        "Map is not monotone: {} ≤ {} but {} ≰ {}",
+                        lower, upper, table[lower], table[upper]
+                    ))
+                })?;
+                mappings.insert(morphism.clone(), mapped.clone());
+            }
+        }
+        Ok(Functor::new(
+            String::generate(),
+            source.clone(),
+            target.clone(),
+            mappings,
+        ))
+    }
+
+    fn product_of(&self, elements: &[ObjectId]) -> Result<Cone<Self>, Errors> {
+        let mut diagram = FiniteDiagram::new();
+        for element in elements {
+            diagram.add_object(self.element(element)?.clone());
+        }
+        self.limit(&diagram)
+    }
+
+    fn coproduct_of(&self, elements: &[ObjectId]) -> Result<Cocone<Self>, Errors> {
+        let mut diagram = FiniteDiagram::new();
+        for element in elements {
+            diagram.add_object(self.element(element)?.clone());
+        }
+        self.colimit(&diagram)
+    }
+
+    // the bound which is above (below for lower = false) every other bound, smallest id first
+    fn best_bound(&self, bounds: Vec<ObjectId>, lower: bool) -> Option<ObjectId> {
+        bounds.iter().find_map(|candidate| {
+            bounds
+                .iter()
+                .all(|bound| match lower {
+                    true => self.less_or_equal(bound, candidate),
+                    false => self.less_or_equal(candidate, bound),
+                })
+                .then(|| candidate.clone())
+        })
+    }
+
+    fn sorted_ids(&self) -> Vec<ObjectId> {
+        let mut ids = self.objects.keys().cloned().collect::<Vec<_>>();
+        ids.sort_by_key(|id| id.to_string());
+        ids
+    }
+
+    fn insert(&mut self, morphism: Rc<Morphism<DiscreteCategory>>) {
+        self.hom_sets
+            .entry(morphism.source_object().category_id().clone())
+            .or_default()
+            .insert(morphism.target_object().category_id().clone(), morphism);
+    }
+}
+
+impl CategoryTrait for PreorderCategory {
+    type Object = DiscreteCategory;
+
+    type Morphism = Morphism<DiscreteCategory>;
+
+    fn new() -> Self {
+        PreorderCategory::new()
+    }
+
+    fn new_with_id(id: &ObjectId) -> Self {
+        PreorderCategory::new_with_id(id.clone())
+    }
+
+    fn category_id(&self) -> &ObjectId {
+        &self.id
+    }
+
+    fn update_category_id(&mut self, new_id: ObjectId) {
+        self.id = new_id;
+    }
+
+    fn add_object(&mut self, object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        let id = object.category_id().clone();
+        if self.objects.contains_key(&id) {
+            return Err(Errors::ObjectAlreadyExists);
+        }
+        let identity = Arrow::new_identity_with_mappings(
+            format!("{}≤{}", id, id),
+            object.clone(),
+            HashMap::new(),
+        );
+        self.objects.insert(id, object);
+        self.insert(identity.clone());
+        Ok(identity)
+    }
+
+    // a morphism a -> b adds a ≤ b, returning the one morphism of the hom-set
+    fn add_morphism(
+        &mut self,
+        morphism: Rc<Self::Morphism>,
+    ) -> Result<&Rc<Self::Morphism>, Errors> {
+        let source = morphism.source_object().category_id().clone();
+        let target = morphism.target_object().category_id().clone();
+        for (id, object) in [
+            (&source, morphism.source_object()),
+            (&target, morphism.target_object()),
+        ] {
+            match self.objects.get(id) {
+                Some(stored) if Rc::ptr_eq(stored, object) => {}
+                _ => return Err(Errors::ObjectNotFound(id.clone())),
+            }
+        }
+        if !self.less_or_equal(&source, &target) {
+            self.insert(morphism);
+            self.add_relation(source.clone(), target.clone())?;
+        }
+        self.morphism(&source, &target)
+    }
+
+    fn get_identity_morphism(&self, object: &Self::Object) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.morphism(object.category_id(), object.category_id())
+            .map_err(|_| Errors::IdentityMorphismNotFound)
+    }
+
+    fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
+        self.element(object.category_id())
+    }
+
+    fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
+        Ok(self.objects.values().collect())
+    }
+
+    fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .hom_sets
+            .values()
+            .flat_map(|hom_set| hom_set.values())
+            .collect())
+    }
+
+    fn get_hom_set_x(
+        &self,
+        source_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        Ok(self
+            .hom_sets
+            .get(source_object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(source_object.category_id().clone()))?
+            .values()
+            .collect())
+    }
+
+    fn get_object_morphisms(
+        &self,
+        object: &Self::Object,
+    ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
+        let mut morphisms = self.get_hom_set_x(object)?.into_iter().collect::<Vec<_>>();
+        morphisms.sort_by_key(|morphism| morphism.arrow_id().clone());
+        Ok(morphisms)
+    }
+
+    // thin: the composite is the one morphism from the first source to the second target
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        if first.target_object() != second.source_object() {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        Ok(self
+            .morphism(
+                first.source_object().category_id(),
+                second.target_object().category_id(),
+            )?
+            .clone())
+    }
+}
+
+// edges of a diagram always commute in a thin category, so only its objects matter
+impl FiniteLimitsTrait for PreorderCategory {
+    fn limit(&self, diagram: &FiniteDiagram<Self>) -> Result<Cone<Self>, Errors> {
+        let lower_bounds = self
+            .sorted_ids()
+            .into_iter()
+            .filter(|candidate| {
+                diagram
+                    .objects()
+                    .iter()
+                    .all(|object| self.less_or_equal(candidate, object.category_id()))
+            })
+            .collect();
+        let meet = self
+            .best_bound(lower_bounds, true)
+            .ok_or(Errors::LimitNotFound)?;
+        let legs = diagram
+            .objects()
+            .iter()
+            .map(|object| Ok(self.morphism(&meet, object.category_id())?.clone()))
+            .collect::<Result<Vec<_>, Errors>>()?;
+        Ok(Cone::new(self.element(&meet)?.clone(), legs))
+    }
+}
+
+impl FiniteColimitsTrait for PreorderCategory {
+    fn colimit(&self, diagram: &FiniteDiagram<Self>) -> Result<Cocone<Self>, Errors> {
+        let upper_bounds = self
+            .sorted_ids()
+            .into_iter()
+            .filter(|candidate| {
+                diagram
+                    .objects()
+                    .iter()
+                    .all(|object| self.less_or_equal(object.category_id(), candidate))
+            })
+            .collect();
+        let join = self
+            .best_bound(upper_bounds, false)
+            .ok_or(Errors::ColimitNotFound)?;
+        let legs = diagram
+            .objects()
+            .iter()
+            .map(|object| Ok(self.morphism(object.category_id(), &join)?.clone()))
+            .collect::<Result<Vec<_>, Errors>>()?;
+        Ok(Cocone::new(self.element(&join)?.clone(), legs))
+    }
+}
+
+// f ⊣ g for monotone f: P -> Q and g: Q -> P, i.e. f(p) ≤ q exactly when p ≤ g(q)
+pub struct GaloisConnection {
+    lower: Functor<PreorderCategory, PreorderCategory>,
+    upper: Functor<PreorderCategory, PreorderCategory>,
+}
+
+impl GaloisConnection {
+    pub fn new(
+        lower: Functor<PreorderCategory, PreorderCategory>,
+        upper: Functor<PreorderCategory, PreorderCategory>,
+    ) -> Result<Self, Errors> {
+        let connection = GaloisConnection { lower, upper };
+        connection.validate()?;
+        Ok(connection)
+    }
+
+    pub fn lower(&self) -> &Functor<PreorderCategory, PreorderCategory> {
+        &self.lower
+    }
+
+    pub fn upper(&self) -> &Functor<PreorderCategory, PreorderCategory> {
+        &self.upper
+    }
+
+    // g(f(p)), the closure operator on P
+    pub fn closure(&self, element: &ObjectId) -> Result<Rc<DiscreteCategory>, Errors> {
+        let image = self.apply_lower(element)?;
+        Ok(self.apply_upper(image.category_id())?.clone())
+    }
+
+    // f(g(q)), the kernel operator on Q
+    pub fn kernel(&self, element: &ObjectId) -> Result<Rc<DiscreteCategory>, Errors> {
+        let image = self.apply_upper(element)?;
+        Ok(self.apply_lower(image.category_id())?.clone())
+    }
+
+    pub fn validate(&self) -> Result<(), Errors> {
+        let (source, target) = (self.lower.source_object(), self.lower.target_object());
+        if self.upper.source_object() != target || self.upper.target_object() != source {
+            return Err(Errors::InvalidAdjunction(
+                "The maps of a Galois connection must go in opposite directions".to_string(),
+            ));
+        }
+        for p in source.sorted_ids() {
+            let lower_image = self.apply_lower(&p)?.category_id().clone();
+            for q in target.sorted_ids() {
+                let upper_image = self.apply_upper(&q)?.category_id().clone();
+                if target.less_or_equal(&lower_image, &q) != source.less_or_equal(&p, &upper_image)
+                {
+                    return Err(Errors::InvalidAdjunction(format!(
+                        "f({}) = {} ≤ {} does not match {} ≤ g({}) = {}",
+                        p, lower_image, q, p, q, upper_image
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_lower(&self, element: &ObjectId) -> Result<&Rc<DiscreteCategory>, Errors> {
+        let source = self.lower.source_object();
+        self.lower.map_object(source.element(element)?)
+    }
+
+    fn apply_upper(&self, element: &ObjectId) -> Result<&Rc<DiscreteCategory>, Errors> {
+        let source = self.upper.source_object();
+        self.upper.map_object(source.element(element)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(length: i32) -> Rc<PreorderCategory> {
+        let pairs = (1..length).map(|element| (element - 1, element)).collect();
+        Rc::new(PreorderCategory::from_relation((0..length).collect(), pairs).unwrap())
+    }
+
+    #[test]
+    fn test_closure_and_thinness() {
+        let mut preorder =
+            PreorderCategory::from_relation(vec!["a", "b", "c", "d"], vec![("a", "b"), ("b", "c")])
+                .unwrap();
+        assert!(preorder.less_or_equal(&"a".into(), &"c".into()));
+        assert!(preorder.less_or_equal(&"d".into(), &"d".into()));
+        assert!(!preorder.less_or_equal(&"c".into(), &"a".into()));
+        assert!(preorder.is_partial_order());
+
+        let a_b = preorder.morphism(&"a".into(), &"b".into()).unwrap().clone();
+        let b_c = preorder.morphism(&"b".into(), &"c".into()).unwrap().clone();
+        let a_c = preorder.compose_morphisms(&a_b, &b_c).unwrap();
+        assert_eq!(a_c.arrow_id(), "a≤c");
+        let c = preorder.element(&"c".into()).unwrap().clone();
+        let a = preorder.element(&"a".into()).unwrap().clone();
+        assert_eq!(preorder.get_hom_set(&a, &c).unwrap().len(), 1);
+
+        // closing c ≤ a makes a, b, c isomorphic and the preorder no longer a poset
+        preorder.add_relation("c", "a").unwrap();
+        assert!(preorder.less_or_equal(&"b".into(), &"a".into()));
+        assert!(!preorder.is_partial_order());
+        let c_a = preorder.morphism(&"c".into(), &"a".into()).unwrap().clone();
+        assert!(preorder.inverse_morphism(&c_a).unwrap().is_some());
+        assert_eq!(preorder.get_all_morphisms().unwrap().len(), 10);
+    }
+
+    #[test]
+    fn test_meets_and_joins() {
+        // the permission lattice none ≤ read, write ≤ admin
+        let lattice = PreorderCategory::from_relation(
+            vec!["none", "read", "write", "admin"],
+            vec![
+                ("none", "read"),
+                ("none", "write"),
+                ("read", "admin"),
+                ("write", "admin"),
+            ],
+        )
+        .unwrap();
+        let (read, write) = (ObjectId::from("read"), ObjectId::from("write"));
+        assert_eq!(
+            lattice
+                .meet(&[read.clone(), write.clone()])
+                .unwrap()
+                .category_id(),
+            &ObjectId::from("none")
+        );
+        assert_eq!(
+            lattice
+                .join(&[read.clone(), write.clone()])
+                .unwrap()
+                .category_id(),
+            &ObjectId::from("admin")
+        );
+        // the empty meet is the top element
+        assert_eq!(
+            lattice.meet(&[]).unwrap().category_id(),
+            &ObjectId::from("admin")
+        );
+
+        let product = lattice.product_of(&[read.clone(), write]).unwrap();
+        assert_eq!(product.legs()[0].arrow_id(), "none≤read");
+
+        let incomparable =
+            PreorderCategory::from_relation(vec!["x", "y"], Vec::<(&str, &str)>::new()).unwrap();
+        assert!(matches!(
+            incomparable.meet(&["x".into(), "y".into()]),
+            Err(Errors::LimitNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_galois_connection() {
+        let (five, three) = (chain(5), chain(3));
+        // ⌈p/2⌉ ⊣ 2q between 0 ≤ .. ≤ 4 and 0 ≤ 1 ≤ 2
+        let halve_up = (0..5).map(|p| (p.into(), ((p + 1) / 2).into())).collect();
+        let double = (0..3).map(|q| (q.into(), (2 * q).into())).collect();
+        let lower = PreorderCategory::monotone_map(&five, &three, &halve_up).unwrap();
+        let upper = PreorderCategory::monotone_map(&three, &five, &double).unwrap();
+        let connection = GaloisConnection::new(lower, upper).unwrap();
+        assert_eq!(
+            connection.closure(&3.into()).unwrap().category_id(),
+            &ObjectId::Int(4)
+        );
+        assert_eq!(
+            connection.kernel(&1.into()).unwrap().category_id(),
+            &ObjectId::Int(1)
+        );
+
+        // ⌊p/2⌋ is monotone but not left adjoint to doubling
+        let halve_down = (0..5).map(|p| (p.into(), (p / 2).into())).collect();
+        let lower = PreorderCategory::monotone_map(&five, &three, &halve_down).unwrap();
+        let upper = PreorderCategory::monotone_map(&three, &five, &double).unwrap();
+        assert!(matches!(
+            GaloisConnection::new(lower, upper),
+            Err(Errors::InvalidAdjunction(_))
+        ));
+
+        let reverse = (0..5).map(|p| (p.into(), ((4 - p) / 2).into())).collect();
+        assert!(PreorderCategory::monotone_map(&five, &three, &reverse).is_err());
+    }
+}
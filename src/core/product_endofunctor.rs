@@ -2,122 +2,348 @@
 Endo functor maps objects and morphisms of a category to itself.
 This is a specific case of a functor where the source and target categories are the same.
 
-For our category framework since we always have a definite set of objects and morphisms
+The product endofunctor (-) × X of a fixed object X sends
+    - an object A to the product A × X
+    - a morphism f: A -> B to f × id_X: A × X -> B × X, the mediating morphism of the
+      cone (A × X, [f ∘ π_A, π_X]) into the product B × X
 
-Endo functor will create a new category with the objects and morphisms of the original category.
-and the new objects and morphisms mapped to the new values.
-
-For instance if category A has objects {a, b} and morphisms {f: a -> b, g: b -> a},
-an endo functor F would map these to a new category B with objects {F(a), F(b)} and morphisms {F(f): F(a) -> F(b), F(g): F(b) -> F(a)}.
-This is useful for creating new categories that are derived from the original category, such as the category of sets or the category of groups.
+Products are found through FiniteLimitsTrait. The functor lives on one category, which must
+be closed under (-) × X: every A × X is one of its objects and every f × id_X one of its
+morphisms, otherwise there is no endofunctor. add_products adds the missing A × X and
+f × id_X to a category, and with_products builds the functor after adding them, which only
+succeeds when the added products have their own products in turn (as A × 1 = A for a
+terminal 1, or meets in a preorder, but not FinSet where (A × X) × X is a new set).
  */
-use crate::core::arrow::{Arrow, Functor, Morphism};
+use crate::core::arrow::Functor;
 use crate::core::errors::Errors;
-use crate::core::identifier::Identifier;
+use crate::core::object_id::ObjectId;
 use crate::core::traits::arrow_trait::ArrowTrait;
 use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::traits::finite_limits_trait::{Cone, FiniteDiagram, FiniteLimitsTrait};
+use crate::core::utils::{sorted_morphisms, sorted_objects};
 use std::collections::HashMap;
-use std::hash::Hash;
 use std::rc::Rc;
 
-pub fn apply_product<Category: CategoryTrait + Eq + Hash>(
-    category: &mut Category,
-    source_object: &Rc<Category::Object>,
+pub struct ProductEndofunctor<Category: FiniteLimitsTrait> {
+    category: Rc<Category>,
     fixed_object: Rc<Category::Object>,
-) -> Result<HashMap<Rc<Category::Morphism>, Rc<Category::Morphism>>, Errors>
-where
-    // <SourCategory as CategoryTrait>::Object: CategoryTrait<Identifier = String>,
-{
-    // we take a product of the source category and the fixed category
-    // and map the objects and morphisms of the source category to the target category
-    let mut target_object = Category::Object::new();
-
-    let fixed_objects = fixed_object.get_all_objects()?;
-
-    let mut source_object_mapping = HashMap::new();
-
-    // first map the objects from the source category to the target category
-    for source_sub_identity_morphism in source_object.get_all_identity_morphisms()? {
-        for fixed_sub_object in &fixed_objects {
-            // product object (_) * fixed_object
-            let new_value = (*source_sub_identity_morphism.source_object().clone())
-                .category_id()
-                .to_owned()
-                + (*fixed_sub_object).clone().category_id().clone();
-            let new_category = <Category::Object as CategoryTrait>::Object::new_with_id(&new_value);
-            let new_category_rc = Rc::new(new_category);
-
-            let target_sub_identity_morphism = target_object.add_object(new_category_rc.clone())?;
-            source_object_mapping
-                .entry(source_sub_identity_morphism)
-                .or_insert_with(Vec::new)
-                .push(target_sub_identity_morphism);
+    // A ↦ (A × X, [π_A, π_X])
+    products: HashMap<ObjectId, Cone<Category>>,
+}
+
+impl<Category: FiniteLimitsTrait> ProductEndofunctor<Category> {
+    // fails with LimitNotFound when some A × X does not exist, and with InvalidFunctor when
+    // the category is not closed under (-) × X
+    pub fn new(
+        category: &Rc<Category>,
+        fixed_object: &Rc<Category::Object>,
+    ) -> Result<Self, Errors> {
+        let fixed_object = category.get_object(fixed_object)?.clone();
+        let mut products = HashMap::new();
+        for object in sorted_objects(category.get_all_objects()?) {
+            let product = category.limit(&Self::diagram(object, &fixed_object))?;
+            if category.get_object(product.apex()).is_err() {
+                return Err(Errors::InvalidFunctor(format!(
+                    "{} × {} is not an object of the category",
+                    object.category_id(),
+                    fixed_object.category_id()
+                )));
+            }
+            products.insert(object.category_id().clone(), product);
+        }
+        let product = ProductEndofunctor {
+            category: category.clone(),
+            fixed_object,
+            products,
+        };
+        for object in sorted_objects(category.get_all_objects()?) {
+            for morphism in sorted_morphisms(category.get_hom_set_x(object)?) {
+                product.map_morphism(morphism)?;
+            }
         }
+        Ok(product)
     }
 
-    let source_object_mapped_product = Rc::new(target_object);
-    category.add_object(source_object_mapped_product.clone())?;
+    // adds the products and the morphisms f × id_X of the category, then builds the functor
+    pub fn with_products(
+        category: &mut Category,
+        fixed_object: &Rc<Category::Object>,
+    ) -> Result<Self, Errors>
+    where
+        Category: Clone,
+    {
+        Self::add_products(category, fixed_object)?;
+        Self::new(&Rc::new(category.clone()), fixed_object)
+    }
 
-    // should map other related object
-    let mut morphism_mapping = HashMap::new();
+    // adds A × X and f × id_X for the objects A and morphisms f of the category, returning
+    // the products by the id of A
+    pub fn add_products(
+        category: &mut Category,
+        fixed_object: &Rc<Category::Object>,
+    ) -> Result<HashMap<ObjectId, Cone<Category>>, Errors> {
+        let fixed_object = category.get_object(fixed_object)?.clone();
+        let objects = sorted_objects(category.get_all_objects()?)
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut morphisms = Vec::new();
+        for object in &objects {
+            for morphism in sorted_morphisms(category.get_hom_set_x(object)?) {
+                if !morphism.is_identity() {
+                    morphisms.push(morphism.clone());
+                }
+            }
+        }
+        let mut products = HashMap::new();
+        for object in &objects {
+            let product = category.limit(&Self::diagram(object, &fixed_object))?;
+            if category.get_object(product.apex()).is_err() {
+                category.add_object(product.apex().clone())?;
+            }
+            products.insert(object.category_id().clone(), product);
+        }
+        let morphisms = morphisms
+            .iter()
+            .map(|morphism| Self::times_fixed(category, &products, &fixed_object, morphism))
+            .collect::<Result<Vec<_>, Errors>>()?;
+        for morphism in morphisms {
+            if Self::stored(category, &morphism)?.is_none() {
+                category.add_morphism(morphism)?;
+            }
+        }
+        Ok(products)
+    }
 
-    let object_morphisms = category
-        .get_object_morphisms(&*source_object)?
-        .into_iter()
-        .map(|m| m.clone())
-        .collect::<Vec<_>>();
+    // the category, closed under (-) × X
+    pub fn category(&self) -> &Rc<Category> {
+        &self.category
+    }
+
+    pub fn fixed_object(&self) -> &Rc<Category::Object> {
+        &self.fixed_object
+    }
 
-    for morphism in object_morphisms {
+    // the product A × X with its projections
+    pub fn product(&self, object: &Category::Object) -> Result<&Cone<Category>, Errors> {
+        Self::find_product(&self.products, object)
+    }
+
+    pub fn map_object(&self, object: &Category::Object) -> Result<&Rc<Category::Object>, Errors> {
+        Ok(self.product(object)?.apex())
+    }
+
+    // f × id_X as a morphism of the category, the identity of A × X when f is an identity
+    pub fn map_morphism(
+        &self,
+        morphism: &Rc<Category::Morphism>,
+    ) -> Result<Rc<Category::Morphism>, Errors> {
         if morphism.is_identity() {
-            let identity_mapped_object =
-                category.get_identity_morphism(&*source_object_mapped_product)?;
-            morphism_mapping.insert(morphism.clone(), identity_mapped_object.clone());
-            continue;
+            let apex = self.product(morphism.source_object())?.apex();
+            return Ok(self.category.get_identity_morphism(apex)?.clone());
         }
-        let arrow_mapping = morphism.arrow_mappings();
-        let mut new_mapping = HashMap::new();
-        let mut target_object = Category::Object::new();
-        for (source_sub_morphism, target_sub_morphism) in arrow_mapping {
-            let mut mapped_objects = Vec::new();
-            for fixed_sub_object in &fixed_objects {
-                // product object (_) * fixed_object
-                let new_value = (*target_sub_morphism.source_object().clone())
-                    .category_id()
-                    .to_owned()
-                    + (*fixed_sub_object).clone().category_id().clone();
-                let new_category =
-                    <Category::Object as CategoryTrait>::Object::new_with_id(&new_value);
-                let new_category_rc = Rc::new(new_category);
-                target_object.add_object(new_category_rc.clone())?;
-                let mapped_morphism = target_object.get_identity_morphism(&*new_category_rc)?;
-                mapped_objects.push(mapped_morphism.clone());
-            }
+        let mapped =
+            Self::times_fixed(&self.category, &self.products, &self.fixed_object, morphism)?;
+        Self::stored(&self.category, &mapped)?.ok_or_else(|| {
+            Errors::InvalidFunctor(format!(
+                "{} × id is not a morphism of the category",
+                morphism.arrow_id()
+            ))
+        })
+    }
 
-            if let Some(source_mapped_morphism) = source_object_mapping.get(source_sub_morphism) {
-                for (index, source_mapped_object) in source_mapped_morphism.iter().enumerate() {
-                    let target_mapped_morphism = &mapped_objects[index];
-                    new_mapping.insert(
-                        source_mapped_object.clone().clone(),
-                        target_mapped_morphism.clone(),
-                    );
-                }
-            } else {
-                return Err(Errors::InvalidFunctor("Invalid mapping".to_string()));
+    pub fn functor(&self) -> Result<Functor<Category, Category>, Errors> {
+        let mut mappings = HashMap::new();
+        for object in sorted_objects(self.category.get_all_objects()?) {
+            for morphism in sorted_morphisms(self.category.get_hom_set_x(object)?) {
+                mappings.insert(morphism.clone(), self.map_morphism(morphism)?);
             }
         }
-        let target_object = Rc::new(target_object);
+        Ok(Functor::new(
+            format!("(-)×{}", self.fixed_object.category_id()),
+            self.category.clone(),
+            self.category.clone(),
+            mappings,
+        ))
+    }
+
+    fn find_product<'a>(
+        products: &'a HashMap<ObjectId, Cone<Category>>,
+        object: &Category::Object,
+    ) -> Result<&'a Cone<Category>, Errors> {
+        products
+            .get(object.category_id())
+            .ok_or_else(|| Errors::ObjectNotFound(object.category_id().clone()))
+    }
+
+    // f × id_X, the mediating morphism of (A × X, [f ∘ π_A, π_X]) into B × X
+    fn times_fixed(
+        category: &Category,
+        products: &HashMap<ObjectId, Cone<Category>>,
+        fixed_object: &Rc<Category::Object>,
+        morphism: &Rc<Category::Morphism>,
+    ) -> Result<Rc<Category::Morphism>, Errors> {
+        let source = Self::find_product(products, morphism.source_object())?;
+        let target = Self::find_product(products, morphism.target_object())?;
+        let cone = Cone::new(
+            source.apex().clone(),
+            vec![
+                category.compose_morphisms(&source.legs()[0], morphism)?,
+                source.legs()[1].clone(),
+            ],
+        );
+        let diagram = Self::diagram(morphism.target_object(), fixed_object);
+        category.limit_mediating_morphism(&diagram, target, &cone)
+    }
 
-        category.add_object(target_object.clone())?;
+    // the morphism itself when the category has it, otherwise one with the same endpoints and
+    // action, which parallel morphisms without mappings (e.g. in shapes) never share
+    fn stored(
+        category: &Category,
+        morphism: &Rc<Category::Morphism>,
+    ) -> Result<Option<Rc<Category::Morphism>>, Errors> {
+        let hom_set = category.get_hom_set(morphism.source_object(), morphism.target_object())?;
+        if let Some(candidate) = hom_set.iter().find(|candidate| **candidate == morphism) {
+            return Ok(Some((*candidate).clone()));
+        }
+        if morphism.arrow_mappings().is_empty() {
+            return Ok(None);
+        }
+        Ok(hom_set
+            .into_iter()
+            .find(|candidate| candidate.arrow_mappings() == morphism.arrow_mappings())
+            .cloned())
+    }
 
-        let new_morphism = Rc::new(Category::Morphism::new(
-            String::generate(),
-            source_object_mapped_product.clone(),
-            target_object.clone(),
-            new_mapping,
+    fn diagram(
+        object: &Rc<Category::Object>,
+        fixed_object: &Rc<Category::Object>,
+    ) -> FiniteDiagram<Category> {
+        let mut diagram = FiniteDiagram::new();
+        diagram.add_object(object.clone());
+        diagram.add_object(fixed_object.clone());
+        diagram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::arrow::Morphism;
+    use crate::core::base_category::BaseCategory;
+    use crate::core::concrete_category::fin_set::FinSet;
+    use crate::core::discrete_category::DiscreteCategory;
+    use crate::core::preorder_category::PreorderCategory;
+
+    #[test]
+    fn test_product_with_a_set() {
+        let mut fin_set = FinSet::new();
+        let letters = fin_set.add_set("letters", vec!["a", "b"]).unwrap();
+        let numbers = fin_set.add_set("numbers", vec![1, 2, 3]).unwrap();
+        fin_set
+            .add_function(
+                "f",
+                &letters,
+                &numbers,
+                [("a", 1), ("b", 3)]
+                    .into_iter()
+                    .map(|(letter, number)| (letter.into(), number.into()))
+                    .collect(),
+            )
+            .unwrap();
+        let fixed = fin_set.add_set("fixed", vec!["x", "y"]).unwrap();
+
+        // the products of the three sets join the category, with f × id between them
+        let products = ProductEndofunctor::add_products(&mut fin_set, &fixed).unwrap();
+        assert_eq!(fin_set.get_all_objects().unwrap().len(), 6);
+        let letters_x = products[letters.category_id()].apex();
+        let numbers_x = products[numbers.category_id()].apex();
+        assert_eq!(FinSet::elements(numbers_x).len(), 6);
+        let hom_set = fin_set.get_hom_set(letters_x, numbers_x).unwrap();
+        assert_eq!(hom_set.len(), 1);
+        let f_times_x = hom_set.into_iter().next().unwrap();
+        assert_eq!(
+            FinSet::apply(f_times_x, &"(b,y)".parse().unwrap()).unwrap(),
+            ObjectId::Int(3) + "y".into()
+        );
+
+        // but (A × X) × X is a new set again, so FinSet is not closed under (-) × X
+        assert!(matches!(
+            ProductEndofunctor::new(&Rc::new(fin_set.clone()), &fixed).err(),
+            Some(Errors::InvalidFunctor(_))
+        ));
+        assert!(matches!(
+            ProductEndofunctor::with_products(&mut fin_set, &fixed).err(),
+            Some(Errors::InvalidFunctor(_))
         ));
-        category.add_morphism(new_morphism.clone())?;
+    }
+
+    #[test]
+    fn test_product_with_parallel_morphisms() {
+        // f, g: a -> b and a terminal object 1, so a × 1 = a and f × id_1 = f
+        let mut category: BaseCategory<DiscreteCategory> = vec!["a", "b", "1"].into();
+        let object = |category: &BaseCategory<DiscreteCategory>, id: &str| {
+            category
+                .get_object(&DiscreteCategory::new_with_id(id.into()))
+                .unwrap()
+                .clone()
+        };
+        let (a, b, one) = (
+            object(&category, "a"),
+            object(&category, "b"),
+            object(&category, "1"),
+        );
+        for (id, source, target) in [
+            ("f", &a, &b),
+            ("g", &a, &b),
+            ("!a", &a, &one),
+            ("!b", &b, &one),
+        ] {
+            category
+                .add_morphism(Rc::new(Morphism::new(
+                    id.to_string(),
+                    source.clone(),
+                    target.clone(),
+                    HashMap::new(),
+                )))
+                .unwrap();
+        }
+        category.add_composition("f", "!b", "!a").unwrap();
+        category.add_composition("g", "!b", "!a").unwrap();
+        let f = category.morphism("f").unwrap().clone();
+        let g = category.morphism("g").unwrap().clone();
+
+        let product = ProductEndofunctor::with_products(&mut category, &one).unwrap();
+        assert_eq!(product.map_object(&a).unwrap(), &a);
+        // f and g have the same (empty) mappings, but stay apart
+        assert_eq!(product.map_morphism(&f).unwrap(), f);
+        assert_eq!(product.map_morphism(&g).unwrap(), g);
+        // an endofunctor, on the category closed under (-) × 1
+        let functor = product.functor().unwrap();
+        functor.validate_mappings().unwrap();
+        assert!(Rc::ptr_eq(functor.source_object(), functor.target_object()));
+        assert_eq!(product.category().get_hom_set(&a, &b).unwrap().len(), 2);
+        assert_eq!(category.get_hom_set(&a, &b).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_missing_product() {
+        // a and b have no meet, so there is no a × b
+        let preorder = PreorderCategory::from_relation(
+            vec!["a", "b", "top"],
+            vec![("a", "top"), ("b", "top")],
+        )
+        .unwrap();
+        let b = preorder.element(&"b".into()).unwrap().clone();
+        assert_eq!(
+            ProductEndofunctor::with_products(&mut preorder.clone(), &b).err(),
+            Some(Errors::LimitNotFound)
+        );
 
-        morphism_mapping.insert(morphism.clone(), new_morphism.clone());
+        // x ∧ top = x, so the preorder is closed under (-) × top as it stands
+        let top = preorder.element(&"top".into()).unwrap().clone();
+        let product = ProductEndofunctor::new(&Rc::new(preorder), &top).unwrap();
+        product.functor().unwrap().validate_mappings().unwrap();
+        assert_eq!(product.map_object(&b).unwrap(), &b);
     }
-    Ok(morphism_mapping)
 }
@@ -0,0 +1,359 @@
+/*
+The small index categories diagrams are drawn in, ready made as BaseCategories.
+
+    0                the empty category
+    1 = [0]          one object 0
+    2 = [1]          the walking arrow 0 -> 1
+    [n]              the objects 0 ≤ 1 ≤ .. ≤ n with one morphism "i≤j" for i < j
+    span             left <-l- apex -r-> right
+    cospan           left -l-> apex <-r- right
+    parallel pair    source =f,g=> target
+    square           a -f-> b -h-> d and a -g-> c -k-> d, both composites being "a→d"
+    Δ≤n              the objects [0], .., [n], sets of the elements 0..k, with every
+                     monotone map between them
+
+The arrows of [n] and the square carry no action, their composites are recorded with the
+category (see BaseCategory::add_composition).
+In Δ≤n the face δi: [k-1] -> [k] skips i, the degeneracy σi: [k+1] -> [k] hits i twice,
+and the other maps are named after their images, e.g. "(0,0,2):[2]→[2]".
+Every map, identities included, carries its action on elements, so composites are found
+by comparing actions and satisfy the simplicial identities.
+ */
+use crate::core::arrow::{Arrow, Morphism};
+use crate::core::base_category::BaseCategory;
+use crate::core::discrete_category::DiscreteCategory;
+use crate::core::errors::Errors;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::category_trait::CategoryTrait;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+pub type Shape = BaseCategory<DiscreteCategory>;
+
+pub fn zero() -> Shape {
+    BaseCategory::new_with_id("0".into())
+}
+
+pub fn one() -> Shape {
+    ordinal(0)
+}
+
+pub fn walking_arrow() -> Shape {
+    ordinal(1)
+}
+
+pub fn ordinal(n: usize) -> Shape {
+    let mut arrows = Vec::new();
+    for i in 0..=n {
+        for j in i + 1..=n {
+            arrows.push(arrow(&format!("{}≤{}", i, j), element(i), element(j)));
+        }
+    }
+    let mut category = shape(&format!("[{}]", n), (0..=n).map(element).collect(), arrows);
+    for i in 0..=n {
+        for j in i + 1..=n {
+            for k in j + 1..=n {
+                let (first, second) = (format!("{}≤{}", i, j), format!("{}≤{}", j, k));
+                // every arrow exists, so recording cannot fail
+                category
+                    .add_composition(&first, &second, &format!("{}≤{}", i, k))
+                    .unwrap();
+            }
+        }
+    }
+    category
+}
+
+pub fn span() -> Shape {
+    shape(
+        "span",
+        names(&["left", "apex", "right"]),
+        vec![
+            arrow("l", "apex".into(), "left".into()),
+            arrow("r", "apex".into(), "right".into()),
+        ],
+    )
+}
+
+pub fn cospan() -> Shape {
+    shape(
+        "cospan",
+        names(&["left", "apex", "right"]),
+        vec![
+            arrow("l", "left".into(), "apex".into()),
+            arrow("r", "right".into(), "apex".into()),
+        ],
+    )
+}
+
+pub fn parallel_pair() -> Shape {
+    shape(
+        "parallel pair",
+        names(&["source", "target"]),
+        vec![
+            arrow("f", "source".into(), "target".into()),
+            arrow("g", "source".into(), "target".into()),
+        ],
+    )
+}
+
+pub fn commutative_square() -> Shape {
+    let mut category = shape(
+        "square",
+        names(&["a", "b", "c", "d"]),
+        vec![
+            arrow("f", "a".into(), "b".into()),
+            arrow("g", "a".into(), "c".into()),
+            arrow("h", "b".into(), "d".into()),
+            arrow("k", "c".into(), "d".into()),
+            arrow("a→d", "a".into(), "d".into()),
+        ],
+    );
+    category.add_composition("f", "h", "a→d").unwrap();
+    category.add_composition("g", "k", "a→d").unwrap();
+    category
+}
+
+pub fn truncated_simplex(n: usize) -> Shape {
+    let mut category = BaseCategory::new_with_id(format!("Δ≤{}", n).into());
+    let objects = (0..=n)
+        .map(|k| {
+            let mut set = DiscreteCategory::new_with_id(simplex_id(k));
+            for i in 0..=k {
+                // the elements are distinct, so inserting cannot fail
+                set.insert_object(Rc::new(DiscreteCategory::new_with_id(element(i))))
+                    .unwrap();
+            }
+            Rc::new(set)
+        })
+        .collect::<Vec<_>>();
+    for (k, object) in objects.iter().enumerate() {
+        let identity = Arrow::new_identity_with_mappings(
+            format!("id:[{}]", k),
+            object.clone(),
+            action(object, object, &(0..=k).collect::<Vec<_>>()),
+        );
+        // the elements are new, so the object is too
+        category.add_object_with_identity(identity).unwrap();
+    }
+    for (k, source) in objects.iter().enumerate() {
+        for (m, target) in objects.iter().enumerate() {
+            for images in monotone_maps(k, m) {
+                if k == m && images.iter().enumerate().all(|(i, image)| i == *image) {
+                    continue;
+                }
+                let morphism = Arrow::new(
+                    simplex_map_id(&images, m),
+                    source.clone(),
+                    target.clone(),
+                    action(source, target, &images),
+                );
+                category.add_morphism(Rc::new(morphism)).unwrap();
+            }
+        }
+    }
+    category
+}
+
+// δi: [k-1] -> [k]
+pub fn face(
+    simplex: &Shape,
+    k: usize,
+    i: usize,
+) -> Result<&Rc<Morphism<DiscreteCategory>>, Errors> {
+    let source = k.checked_sub(1).ok_or(Errors::MorphismNotFound)?;
+    simplex.morphism(&format!("δ{}:[{}]→[{}]", i, source, k))
+}
+
+// σi: [k+1] -> [k]
+pub fn degeneracy(
+    simplex: &Shape,
+    k: usize,
+    i: usize,
+) -> Result<&Rc<Morphism<DiscreteCategory>>, Errors> {
+    simplex.morphism(&format!("σ{}:[{}]→[{}]", i, k + 1, k))
+}
+
+pub fn simplex_id(k: usize) -> ObjectId {
+    format!("[{}]", k).into()
+}
+
+// a category of the given objects and non-identity arrows (id, source, target) with no action
+fn shape(id: &str, objects: Vec<ObjectId>, arrows: Vec<(String, ObjectId, ObjectId)>) -> Shape {
+    let mut category = BaseCategory::new_with_id(id.into());
+    let mut stored = HashMap::new();
+    for object in objects {
+        let object = Rc::new(DiscreteCategory::new_with_id(object));
+        stored.insert(object.category_id().clone(), object.clone());
+        // the ids are distinct, so adding cannot fail
+        category.add_object(object).unwrap();
+    }
+    for (id, source, target) in arrows {
+        let morphism = Arrow::new(
+            id,
+            stored[&source].clone(),
+            stored[&target].clone(),
+            HashMap::new(),
+        );
+        category.add_morphism(Rc::new(morphism)).unwrap();
+    }
+    category
+}
+
+fn arrow(id: &str, source: ObjectId, target: ObjectId) -> (String, ObjectId, ObjectId) {
+    (id.to_string(), source, target)
+}
+
+fn names(objects: &[&str]) -> Vec<ObjectId> {
+    objects.iter().map(|object| (*object).into()).collect()
+}
+
+fn element(i: usize) -> ObjectId {
+    ObjectId::Int(i as i32)
+}
+
+// the non-decreasing maps [k] -> [m], as the images of 0..k
+fn monotone_maps(k: usize, m: usize) -> Vec<Vec<usize>> {
+    let mut maps = vec![Vec::new()];
+    for _ in 0..=k {
+        maps = maps
+            .into_iter()
+            .flat_map(|images: Vec<usize>| {
+                let lowest = images.last().copied().unwrap_or(0);
+                (lowest..=m).map(move |image| {
+                    let mut images = images.clone();
+                    images.push(image);
+                    images
+                })
+            })
+            .collect();
+    }
+    maps
+}
+
+fn simplex_map_id(images: &[usize], m: usize) -> String {
+    let k = images.len() - 1;
+    let injective = images.windows(2).all(|pair| pair[0] < pair[1]);
+    let surjective = (0..=m).all(|i| images.contains(&i));
+    // an injection [m-1] -> [m] skips one element, a surjection [m+1] -> [m] hits one twice
+    let skipped = (0..=m).find(|i| !images.contains(i));
+    if let Some(i) = skipped.filter(|_| k + 1 == m && injective) {
+        return format!("δ{}:[{}]→[{}]", i, k, m);
+    }
+    let repeated = images.windows(2).position(|pair| pair[0] == pair[1]);
+    if let Some(i) = repeated.filter(|_| k == m + 1 && surjective) {
+        return format!("σ{}:[{}]→[{}]", i, k, m);
+    }
+    let images = ObjectId::product(images.iter().map(|image| element(*image)));
+    format!("{}:[{}]→[{}]", images, k, m)
+}
+
+// i ↦ images[i] on the identities of the elements
+fn action(
+    source: &DiscreteCategory,
+    target: &DiscreteCategory,
+    images: &[usize],
+) -> HashMap<Rc<Morphism<DiscreteCategory>>, Rc<Morphism<DiscreteCategory>>> {
+    images
+        .iter()
+        .enumerate()
+        .map(|(index, image)| {
+            let identity = |set: &DiscreteCategory, i: usize| {
+                set.get_identity_morphism(&DiscreteCategory::new_with_id(element(i)))
+                    .unwrap()
+                    .clone()
+            };
+            (identity(source, index), identity(target, *image))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::traits::arrow_trait::ArrowTrait;
+    use crate::core::utils::sorted_objects;
+
+    fn morphism_count(shape: &Shape) -> usize {
+        sorted_objects(shape.get_all_objects().unwrap())
+            .into_iter()
+            .map(|object| shape.get_hom_set_x(object).unwrap().len())
+            .sum()
+    }
+
+    #[test]
+    fn test_small_shapes() {
+        assert_eq!(morphism_count(&zero()), 0);
+        assert_eq!(morphism_count(&one()), 1);
+        assert_eq!(morphism_count(&walking_arrow()), 3);
+        assert_eq!(morphism_count(&span()), 5);
+        assert_eq!(morphism_count(&cospan()), 5);
+        assert_eq!(morphism_count(&parallel_pair()), 4);
+        // 0 ≤ 1 ≤ 2 ≤ 3 has a morphism for every pair i ≤ j
+        let four = ordinal(3);
+        assert_eq!(morphism_count(&four), 10);
+        let composite = four
+            .compose_morphisms(four.morphism("0≤1").unwrap(), four.morphism("1≤3").unwrap())
+            .unwrap();
+        assert_eq!(composite.arrow_id(), "0≤3");
+    }
+
+    #[test]
+    fn test_square_commutes() {
+        let square = commutative_square();
+        let through_b = square
+            .compose_morphisms(square.morphism("f").unwrap(), square.morphism("h").unwrap())
+            .unwrap();
+        let through_c = square
+            .compose_morphisms(square.morphism("g").unwrap(), square.morphism("k").unwrap())
+            .unwrap();
+        assert_eq!(through_b, through_c);
+        assert_eq!(through_b.arrow_id(), "a→d");
+    }
+
+    #[test]
+    fn test_simplicial_identities() {
+        let simplex = truncated_simplex(2);
+        // hom([k], [m]) has C(m + k + 1, k + 1) monotone maps
+        assert_eq!(morphism_count(&simplex), 6 + 10 + 15);
+        let compose = |first: &Rc<Morphism<DiscreteCategory>>,
+                       second: &Rc<Morphism<DiscreteCategory>>| {
+            simplex.compose_morphisms(first, second).unwrap()
+        };
+        // δj ∘ δi = δi ∘ δ(j-1) for i < j
+        assert_eq!(
+            compose(face(&simplex, 1, 0).unwrap(), face(&simplex, 2, 2).unwrap()),
+            compose(face(&simplex, 1, 1).unwrap(), face(&simplex, 2, 0).unwrap())
+        );
+        // σj ∘ σi = σi ∘ σ(j+1) for i ≤ j
+        assert_eq!(
+            compose(
+                degeneracy(&simplex, 1, 0).unwrap(),
+                degeneracy(&simplex, 0, 0).unwrap()
+            ),
+            compose(
+                degeneracy(&simplex, 1, 1).unwrap(),
+                degeneracy(&simplex, 0, 0).unwrap()
+            )
+        );
+        // σi ∘ δi = σi ∘ δ(i+1) = id
+        for i in 0..2 {
+            let sigma = degeneracy(&simplex, 1, i).unwrap();
+            assert!(compose(face(&simplex, 2, i).unwrap(), sigma).is_identity());
+            assert!(compose(face(&simplex, 2, i + 1).unwrap(), sigma).is_identity());
+        }
+        // σ0 ∘ δ2 = δ1 ∘ σ0 on [1]
+        assert_eq!(
+            compose(
+                face(&simplex, 2, 2).unwrap(),
+                degeneracy(&simplex, 1, 0).unwrap()
+            ),
+            compose(
+                degeneracy(&simplex, 0, 0).unwrap(),
+                face(&simplex, 1, 1).unwrap()
+            )
+        );
+        let object = simplex.get_object(&DiscreteCategory::new_with_id(simplex_id(2)));
+        assert_eq!(object.unwrap().get_all_objects().unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,836 @@
+/*
+Strict n-categories given by their cells.
+
+An n-cell (n > 0) has a source and a target (n-1)-cell, which are parallel:
+
+    s(s(x)) = s(t(x)) and t(s(x)) = t(t(x))      (globular conditions)
+
+Two n-cells a, b compose along a dimension k < n when t_k(a) = s_k(b),
+where s_k and t_k are the iterated sources and targets down to dimension k.
+Composites which are not forced by the unit laws are declared explicitly,
+and validate() checks that the declared composites are closed, associative
+and satisfy the interchange law
+
+    (a ∘_k b) ∘_j (c ∘_k d) = (a ∘_j c) ∘_k (b ∘_j d)      for j < k
+
+The cells above two parallel k-cells x, y form the hom category Hom(x, y), whose objects
+are the (k+1)-cells from x to y and whose morphisms are the (k+2)-cells between them,
+composed along k+1. hom_category gives it as a CategoryTrait category, and
+add_hom_category imports a category as the cells above x and y, so an n-category is
+assembled from nested categories level by level. from_category imports a category as
+the 0-cells and 1-cells.
+ */
+use crate::core::arrow::{Arrow, Morphism};
+use crate::core::base_category::BaseCategory;
+use crate::core::discrete_category::DiscreteCategory;
+use crate::core::errors::Errors;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::utils::{sorted_morphisms, sorted_objects};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+// the source and target of a cell
+type Boundary = (Option<Rc<Cell>>, Option<Rc<Cell>>);
+
+#[derive(Debug)]
+pub struct Cell {
+    id: ObjectId,
+    dimension: usize,
+    // both are None for 0-cells
+    source: Option<Rc<Cell>>,
+    target: Option<Rc<Cell>>,
+    // the cell this cell is the identity of
+    identity_of: Option<Rc<Cell>>,
+}
+
+impl PartialEq for Cell {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Cell {}
+
+impl Hash for Cell {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl Cell {
+    pub fn id(&self) -> &ObjectId {
+        &self.id
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    pub fn source(&self) -> Option<&Rc<Cell>> {
+        self.source.as_ref()
+    }
+
+    pub fn target(&self) -> Option<&Rc<Cell>> {
+        self.target.as_ref()
+    }
+
+    pub fn identity_of(&self) -> Option<&Rc<Cell>> {
+        self.identity_of.as_ref()
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.identity_of.is_some()
+    }
+
+    // s_k: the iterated source of dimension k
+    pub fn source_at(&self, dimension: usize) -> Result<&Cell, Errors> {
+        let mut cell = self;
+        while cell.dimension > dimension {
+            cell = cell.source.as_ref().ok_or(Errors::InvalidCell(format!(
+                "Cell {} has no source",
+                cell.id
+            )))?;
+        }
+        if cell.dimension != dimension {
+            return Err(Errors::InvalidCell(format!(
+                "Cell {} has no boundary of dimension {}",
+                self.id, dimension
+            )));
+        }
+        Ok(cell)
+    }
+
+    // t_k: the iterated target of dimension k
+    pub fn target_at(&self, dimension: usize) -> Result<&Cell, Errors> {
+        let mut cell = self;
+        while cell.dimension > dimension {
+            cell = cell.target.as_ref().ok_or(Errors::InvalidCell(format!(
+                "Cell {} has no target",
+                cell.id
+            )))?;
+        }
+        if cell.dimension != dimension {
+            return Err(Errors::InvalidCell(format!(
+                "Cell {} has no boundary of dimension {}",
+                self.id, dimension
+            )));
+        }
+        Ok(cell)
+    }
+
+    // the lowest cell this cell is an iterated identity of, itself if it is not an identity
+    pub fn identity_base(&self) -> &Cell {
+        let mut cell = self;
+        while let Some(base) = &cell.identity_of {
+            cell = base;
+        }
+        cell
+    }
+}
+
+#[derive(Debug)]
+pub struct StrictNCategory {
+    id: ObjectId,
+    dimension: usize,
+    cells: HashMap<ObjectId, Rc<Cell>>,
+    identities: HashMap<ObjectId, Rc<Cell>>,
+    // (first, second, dimension) -> composite
+    composites: HashMap<(ObjectId, ObjectId, usize), Rc<Cell>>,
+}
+
+impl StrictNCategory {
+    pub fn new(id: ObjectId, dimension: usize) -> Self {
+        StrictNCategory {
+            id,
+            dimension,
+            cells: HashMap::new(),
+            identities: HashMap::new(),
+            composites: HashMap::new(),
+        }
+    }
+
+    pub fn new_two_category(id: ObjectId) -> Self {
+        Self::new(id, 2)
+    }
+
+    /*
+    Objects become 0-cells and morphisms 1-cells, with the identity morphisms as identity cells
+    and composites taken from the category, so every composite has to be in its hom-sets.
+     */
+    pub fn from_category<Category: CategoryTrait>(
+        category: &Category,
+        dimension: usize,
+    ) -> Result<Self, Errors> {
+        let mut result = Self::new(category.category_id().clone(), dimension.max(1));
+        result.import_category(category, None)?;
+        Ok(result)
+    }
+
+    /*
+    Adds the objects of the category as cells from source to target and its morphisms as the
+    cells between them, composing along the dimension of those objects. Objects which are
+    already cells from source to target are reused.
+     */
+    pub fn add_hom_category<Category: CategoryTrait>(
+        &mut self,
+        source: &Rc<Cell>,
+        target: &Rc<Cell>,
+        category: &Category,
+    ) -> Result<(), Errors> {
+        Self::validate_parallel(source, target)?;
+        self.import_category(category, Some((source, target)))
+    }
+
+    // the objects become cells with the given boundary, 0-cells without one
+    fn import_category<Category: CategoryTrait>(
+        &mut self,
+        category: &Category,
+        boundary: Option<(&Rc<Cell>, &Rc<Cell>)>,
+    ) -> Result<(), Errors> {
+        let dimension = boundary.map_or(0, |(source, _)| source.dimension + 1);
+        let objects = sorted_objects(category.get_all_objects()?);
+        for object in &objects {
+            let id = object.category_id().clone();
+            let cell = match self.cells.get(&id) {
+                Some(cell)
+                    if boundary.is_some_and(|(source, target)| {
+                        cell.source.as_ref() == Some(source) && cell.target.as_ref() == Some(target)
+                    }) =>
+                {
+                    cell.clone()
+                }
+                _ => self.add_cell(
+                    id,
+                    boundary.map(|(source, _)| source),
+                    boundary.map(|(_, target)| target),
+                )?,
+            };
+            let identity = category.get_identity_morphism(object)?;
+            if !self.identities.contains_key(&cell.id) {
+                self.add_identity(&cell, morphism_cell_id(identity.arrow_id()))?;
+            }
+        }
+        let mut morphisms = Vec::new();
+        for object in &objects {
+            morphisms.extend(sorted_morphisms(category.get_hom_set_x(object)?));
+        }
+        for morphism in &morphisms {
+            if morphism.is_identity() {
+                continue;
+            }
+            let source = self
+                .get_cell(morphism.source_object().category_id())?
+                .clone();
+            let target = self
+                .get_cell(morphism.target_object().category_id())?
+                .clone();
+            self.add_cell(
+                morphism_cell_id(morphism.arrow_id()),
+                Some(&source),
+                Some(&target),
+            )?;
+        }
+        for first in &morphisms {
+            for second in &morphisms {
+                if first.is_identity()
+                    || second.is_identity()
+                    || first.target_object() != second.source_object()
+                {
+                    continue;
+                }
+                let composite = category.compose_morphisms(first, second)?;
+                let composite = match composite.is_identity() {
+                    true => {
+                        let cell = self
+                            .get_cell(composite.source_object().category_id())?
+                            .clone();
+                        self.identity(&cell)?
+                    }
+                    false => self
+                        .get_cell(&morphism_cell_id(composite.arrow_id()))?
+                        .clone(),
+                };
+                let first = self.get_cell(&morphism_cell_id(first.arrow_id()))?.clone();
+                let second = self.get_cell(&morphism_cell_id(second.arrow_id()))?.clone();
+                self.add_composite(&first, &second, dimension, &composite)?;
+            }
+        }
+        Ok(())
+    }
+
+    /*
+    Hom(x, y) of two parallel cells: the cells from x to y as objects, the cells between
+    those as morphisms, with the identity cells as identities and the composites along the
+    dimension of the objects. Fails when such a composite is not defined.
+     */
+    pub fn hom_category(
+        &mut self,
+        source: &Rc<Cell>,
+        target: &Rc<Cell>,
+    ) -> Result<BaseCategory<DiscreteCategory>, Errors> {
+        Self::validate_parallel(source, target)?;
+        let dimension = source.dimension + 1;
+        let between = |cells: Vec<&Rc<Cell>>, source: &Rc<Cell>, target: &Rc<Cell>| {
+            cells
+                .into_iter()
+                .filter(|cell| {
+                    cell.source.as_ref() == Some(source) && cell.target.as_ref() == Some(target)
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let objects = between(self.get_cells(dimension), source, target);
+        let mut category = BaseCategory::new_with_id(ObjectId::generated(
+            "Hom",
+            [source.id.clone(), target.id.clone()],
+        ));
+        let mut stored = HashMap::new();
+        for cell in &objects {
+            let object = Rc::new(DiscreteCategory::new_with_id(cell.id.clone()));
+            let identity = self.identity(cell)?;
+            category.add_object_with_identity(Arrow::new_identity_with_mappings(
+                cell_arrow_id(&identity.id),
+                object.clone(),
+                HashMap::new(),
+            ))?;
+            stored.insert(cell.id.clone(), object);
+        }
+        let mut morphisms = Vec::new();
+        for first in &objects {
+            for second in &objects {
+                let cells = between(self.get_cells(dimension + 1), first, second);
+                for cell in cells.into_iter().filter(|cell| !cell.is_identity()) {
+                    category.add_morphism(Rc::new(Morphism::new(
+                        cell_arrow_id(&cell.id),
+                        stored[&first.id].clone(),
+                        stored[&second.id].clone(),
+                        HashMap::new(),
+                    )))?;
+                    morphisms.push(cell);
+                }
+            }
+        }
+        for first in &morphisms {
+            for second in &morphisms {
+                if first.target != second.source {
+                    continue;
+                }
+                let composite = self.compose(first, second, dimension)?;
+                category.add_composition(
+                    &cell_arrow_id(&first.id),
+                    &cell_arrow_id(&second.id),
+                    &cell_arrow_id(&composite.id),
+                )?;
+            }
+        }
+        Ok(category)
+    }
+
+    pub fn id(&self) -> &ObjectId {
+        &self.id
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    pub fn get_cell(&self, id: &ObjectId) -> Result<&Rc<Cell>, Errors> {
+        self.cells
+            .get(id)
+            .ok_or_else(|| Errors::ObjectNotFound(id.clone()))
+    }
+
+    pub fn get_cells(&self, dimension: usize) -> Vec<&Rc<Cell>> {
+        let mut cells = self
+            .cells
+            .values()
+            .filter(|cell| cell.dimension == dimension)
+            .collect::<Vec<_>>();
+        cells.sort_by_key(|cell| cell.id.to_string());
+        cells
+    }
+
+    // a 0-cell when source and target are None, otherwise a cell one dimension above them
+    pub fn add_cell(
+        &mut self,
+        id: ObjectId,
+        source: Option<&Rc<Cell>>,
+        target: Option<&Rc<Cell>>,
+    ) -> Result<Rc<Cell>, Errors> {
+        self.insert_cell(id, source, target, None)
+    }
+
+    fn insert_cell(
+        &mut self,
+        id: ObjectId,
+        source: Option<&Rc<Cell>>,
+        target: Option<&Rc<Cell>>,
+        identity_of: Option<Rc<Cell>>,
+    ) -> Result<Rc<Cell>, Errors> {
+        if self.cells.contains_key(&id) {
+            return Err(Errors::ObjectAlreadyExists);
+        }
+        let dimension = match (source, target) {
+            (None, None) => 0,
+            (Some(source), Some(target)) => {
+                for boundary in [source, target] {
+                    if self.cells.get(&boundary.id) != Some(boundary) {
+                        return Err(Errors::ObjectNotFound(boundary.id.clone()));
+                    }
+                }
+                Self::validate_parallel(source, target)?;
+                source.dimension + 1
+            }
+            _ => {
+                return Err(Errors::InvalidCell(format!(
+                    "Cell {} needs both a source and a target",
+                    id
+                )));
+            }
+        };
+        if dimension > self.dimension {
+            return Err(Errors::InvalidCell(format!(
+                "Cell {} exceeds the dimension {}",
+                id, self.dimension
+            )));
+        }
+        let cell = Rc::new(Cell {
+            id: id.clone(),
+            dimension,
+            source: source.cloned(),
+            target: target.cloned(),
+            identity_of,
+        });
+        self.cells.insert(id, cell.clone());
+        Ok(cell)
+    }
+
+    // the globular conditions for the boundary of a new cell
+    fn validate_parallel(source: &Cell, target: &Cell) -> Result<(), Errors> {
+        if source.dimension != target.dimension {
+            return Err(Errors::InvalidCell(format!(
+                "Cells {} and {} have different dimensions",
+                source.id, target.id
+            )));
+        }
+        if source.dimension > 0
+            && (source.source != target.source || source.target != target.target)
+        {
+            return Err(Errors::InvalidCell(format!(
+                "Cells {} and {} are not parallel",
+                source.id, target.id
+            )));
+        }
+        Ok(())
+    }
+
+    fn add_identity(&mut self, cell: &Rc<Cell>, id: ObjectId) -> Result<Rc<Cell>, Errors> {
+        let identity = self.insert_cell(id, Some(cell), Some(cell), Some(cell.clone()))?;
+        self.identities.insert(cell.id.clone(), identity.clone());
+        Ok(identity)
+    }
+
+    // the identity (k+1)-cell on a k-cell, created the first time it is asked for
+    pub fn identity(&mut self, cell: &Rc<Cell>) -> Result<Rc<Cell>, Errors> {
+        if let Some(identity) = self.identities.get(&cell.id) {
+            return Ok(identity.clone());
+        }
+        self.add_identity(cell, ObjectId::Str(format!("id({})", cell.id)))
+    }
+
+    // the iterated identity of a cell up to the given dimension
+    pub fn identity_at(&mut self, cell: &Rc<Cell>, dimension: usize) -> Result<Rc<Cell>, Errors> {
+        let mut result = cell.clone();
+        while result.dimension < dimension {
+            result = self.identity(&result)?;
+        }
+        Ok(result)
+    }
+
+    pub fn add_composite(
+        &mut self,
+        first: &Rc<Cell>,
+        second: &Rc<Cell>,
+        dimension: usize,
+        composite: &Rc<Cell>,
+    ) -> Result<(), Errors> {
+        Self::validate_composable(first, second, dimension)?;
+        if first.identity_base().dimension <= dimension
+            || second.identity_base().dimension <= dimension
+        {
+            return Err(Errors::InvalidCell(
+                "Composites with identities are given by the unit laws".to_string(),
+            ));
+        }
+        let (source, target) = self.composite_boundary(first, second, dimension)?;
+        if composite.source.as_ref() != source.as_ref()
+            || composite.target.as_ref() != target.as_ref()
+        {
+            return Err(Errors::InvalidCell(format!(
+                "Composite {} has the wrong boundary",
+                composite.id
+            )));
+        }
+        self.composites.insert(
+            (first.id.clone(), second.id.clone(), dimension),
+            composite.clone(),
+        );
+        Ok(())
+    }
+
+    fn validate_composable(first: &Cell, second: &Cell, dimension: usize) -> Result<(), Errors> {
+        if first.dimension != second.dimension || dimension >= first.dimension {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        if first.target_at(dimension)? != second.source_at(dimension)? {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        Ok(())
+    }
+
+    // s(a ∘_k b) = s(a) ∘_k s(b) below the top dimension, s(a) along the top dimension
+    fn composite_boundary(
+        &mut self,
+        first: &Rc<Cell>,
+        second: &Rc<Cell>,
+        dimension: usize,
+    ) -> Result<Boundary, Errors> {
+        if dimension + 1 == first.dimension {
+            return Ok((first.source.clone(), second.target.clone()));
+        }
+        let (Some(first_source), Some(second_source), Some(first_target), Some(second_target)) =
+            (&first.source, &second.source, &first.target, &second.target)
+        else {
+            return Err(Errors::InvalidMorphismComposition);
+        };
+        let source = self.compose(first_source, second_source, dimension)?;
+        let target = self.compose(first_target, second_target, dimension)?;
+        Ok((Some(source), Some(target)))
+    }
+
+    // a ∘_k b, the composite of a followed by b along their k-dimensional boundary
+    pub fn compose(
+        &mut self,
+        first: &Rc<Cell>,
+        second: &Rc<Cell>,
+        dimension: usize,
+    ) -> Result<Rc<Cell>, Errors> {
+        Self::validate_composable(first, second, dimension)?;
+        // unit laws: identities along k or below are neutral
+        if second.identity_base().dimension <= dimension {
+            return Ok(first.clone());
+        }
+        if first.identity_base().dimension <= dimension {
+            return Ok(second.clone());
+        }
+        // id_x ∘_k id_y = id_(x ∘_k y)
+        if let (Some(first_base), Some(second_base)) = (&first.identity_of, &second.identity_of) {
+            let composite = self.compose(first_base, second_base, dimension)?;
+            return self.identity(&composite);
+        }
+        self.composites
+            .get(&(first.id.clone(), second.id.clone(), dimension))
+            .cloned()
+            .ok_or(Errors::InvalidCell(format!(
+                "Composite of {} and {} along dimension {} is not defined",
+                first.id, second.id, dimension
+            )))
+    }
+
+    // composes when both cells are composable, None otherwise
+    fn try_compose(
+        &mut self,
+        first: &Rc<Cell>,
+        second: &Rc<Cell>,
+        dimension: usize,
+    ) -> Result<Option<Rc<Cell>>, Errors> {
+        if Self::validate_composable(first, second, dimension).is_err() {
+            return Ok(None);
+        }
+        self.compose(first, second, dimension).map(Some)
+    }
+
+    pub fn validate(&mut self) -> Result<(), Errors> {
+        for cell in self.cells.values() {
+            if let (Some(source), Some(target)) = (&cell.source, &cell.target) {
+                Self::validate_parallel(source, target)?;
+            }
+        }
+        for dimension in 1..=self.dimension {
+            let cells = self
+                .get_cells(dimension)
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>();
+            for along in 0..dimension {
+                self.validate_associativity(&cells, along)?;
+                for inner in (along + 1)..dimension {
+                    self.validate_interchange(&cells, along, inner)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // (a ∘_k b) ∘_k c = a ∘_k (b ∘_k c), which also checks every composite is defined
+    fn validate_associativity(
+        &mut self,
+        cells: &[Rc<Cell>],
+        dimension: usize,
+    ) -> Result<(), Errors> {
+        for first in cells {
+            for second in cells {
+                let Some(first_second) = self.try_compose(first, second, dimension)? else {
+                    continue;
+                };
+                for third in cells {
+                    let Some(second_third) = self.try_compose(second, third, dimension)? else {
+                        continue;
+                    };
+                    let left = self.compose(&first_second, third, dimension)?;
+                    let right = self.compose(first, &second_third, dimension)?;
+                    if left != right {
+                        return Err(Errors::InvalidCell(format!(
+                            "Composition of {}, {} and {} along dimension {} is not associative",
+                            first.id, second.id, third.id, dimension
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // (a ∘_k b) ∘_j (c ∘_k d) = (a ∘_j c) ∘_k (b ∘_j d) for j < k
+    fn validate_interchange(
+        &mut self,
+        cells: &[Rc<Cell>],
+        outer: usize,
+        inner: usize,
+    ) -> Result<(), Errors> {
+        for a in cells {
+            for b in cells {
+                let Some(ab) = self.try_compose(a, b, inner)? else {
+                    continue;
+                };
+                for c in cells {
+                    let Some(ac) = self.try_compose(a, c, outer)? else {
+                        continue;
+                    };
+                    for d in cells {
+                        let Some(cd) = self.try_compose(c, d, inner)? else {
+                            continue;
+                        };
+                        let Some(bd) = self.try_compose(b, d, outer)? else {
+                            continue;
+                        };
+                        let left = self.compose(&ab, &cd, outer)?;
+                        let right = self.compose(&ac, &bd, inner)?;
+                        if left != right {
+                            return Err(Errors::InvalidCell(format!(
+                                "Interchange law fails for {}, {}, {} and {}",
+                                a.id, b.id, c.id, d.id
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn morphism_cell_id(arrow_id: &str) -> ObjectId {
+    ObjectId::Str(arrow_id.to_string())
+}
+
+// the inverse of morphism_cell_id
+fn cell_arrow_id(id: &ObjectId) -> String {
+    match id {
+        ObjectId::Str(arrow_id) => arrow_id.clone(),
+        id => id.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::shapes;
+
+    fn cell(category: &StrictNCategory, id: &str) -> Rc<Cell> {
+        category.get_cell(&id.into()).unwrap().clone()
+    }
+
+    /*
+       x --f,g--> y --p--> z   with α: f => g
+    */
+    fn two_category() -> StrictNCategory {
+        let mut category = StrictNCategory::new_two_category("C".into());
+        let x = category.add_cell("x".into(), None, None).unwrap();
+        let y = category.add_cell("y".into(), None, None).unwrap();
+        let z = category.add_cell("z".into(), None, None).unwrap();
+        let f = category.add_cell("f".into(), Some(&x), Some(&y)).unwrap();
+        let g = category.add_cell("g".into(), Some(&x), Some(&y)).unwrap();
+        let p = category.add_cell("p".into(), Some(&y), Some(&z)).unwrap();
+        let fp = category.add_cell("fp".into(), Some(&x), Some(&z)).unwrap();
+        let gp = category.add_cell("gp".into(), Some(&x), Some(&z)).unwrap();
+        category.add_composite(&f, &p, 0, &fp).unwrap();
+        category.add_composite(&g, &p, 0, &gp).unwrap();
+
+        let alpha = category.add_cell("α".into(), Some(&f), Some(&g)).unwrap();
+        let alpha_p = category
+            .add_cell("αp".into(), Some(&fp), Some(&gp))
+            .unwrap();
+        let identity_p = category.identity(&p).unwrap();
+        category
+            .add_composite(&alpha, &identity_p, 0, &alpha_p)
+            .unwrap();
+        category
+    }
+
+    #[test]
+    fn test_strict_two_category() {
+        let mut category = two_category();
+        let alpha = cell(&category, "α");
+        let f = cell(&category, "f");
+        let g = cell(&category, "g");
+        let p = cell(&category, "p");
+
+        assert_eq!(alpha.dimension(), 2);
+        assert_eq!(alpha.source_at(0).unwrap().id(), &ObjectId::from("x"));
+        assert_eq!(alpha.target_at(0).unwrap().id(), &ObjectId::from("y"));
+
+        // unit laws in both directions
+        let identity_f = category.identity(&f).unwrap();
+        let identity_g = category.identity(&g).unwrap();
+        assert_eq!(category.compose(&identity_f, &alpha, 1).unwrap(), alpha);
+        assert_eq!(category.compose(&alpha, &identity_g, 1).unwrap(), alpha);
+        assert_eq!(
+            category.identity(&f).unwrap(),
+            identity_f,
+            "identities are stable"
+        );
+
+        // whiskering and the interchange law with identities
+        let identity_p = category.identity(&p).unwrap();
+        let whiskered = category.compose(&alpha, &identity_p, 0).unwrap();
+        assert_eq!(whiskered, cell(&category, "αp"));
+        let identity_gp = category.compose(&identity_g, &identity_p, 0).unwrap();
+        assert_eq!(identity_gp.identity_of(), Some(&cell(&category, "gp")));
+        category.validate().unwrap();
+    }
+
+    #[test]
+    fn test_globular_conditions() {
+        let mut category = two_category();
+        let x = cell(&category, "x");
+        let y = cell(&category, "y");
+        let f = cell(&category, "f");
+        let back = category
+            .add_cell("back".into(), Some(&y), Some(&x))
+            .unwrap();
+        // f and back are not parallel
+        assert!(
+            category
+                .add_cell("β".into(), Some(&f), Some(&back))
+                .is_err()
+        );
+        // 3-cells do not fit in a 2-category
+        let alpha = cell(&category, "α");
+        assert!(
+            category
+                .add_cell("Γ".into(), Some(&alpha), Some(&alpha))
+                .is_err()
+        );
+        // f ends at y and starts at x, so f does not compose with itself
+        assert!(category.compose(&f, &f, 0).is_err());
+    }
+
+    #[test]
+    fn test_from_category() {
+        let mut category: BaseCategory<DiscreteCategory> = vec!["a", "b"].into();
+        let a = category
+            .get_object(&DiscreteCategory::new_with_id("a".into()))
+            .unwrap()
+            .clone();
+        let b = category
+            .get_object(&DiscreteCategory::new_with_id("b".into()))
+            .unwrap()
+            .clone();
+        category
+            .add_morphism(Rc::new(Morphism::new(
+                "h".to_string(),
+                a,
+                b,
+                HashMap::new(),
+            )))
+            .unwrap();
+
+        let mut two_category = StrictNCategory::from_category(&category, 2).unwrap();
+        assert_eq!(two_category.get_cells(0).len(), 2);
+        // two identities and h
+        assert_eq!(two_category.get_cells(1).len(), 3);
+        let h = cell(&two_category, "h");
+        let identity_h = two_category.identity(&h).unwrap();
+        assert_eq!(identity_h.dimension(), 2);
+        two_category.validate().unwrap();
+    }
+
+    #[test]
+    fn test_hom_categories() {
+        let mut category = two_category();
+        let x = cell(&category, "x");
+        let y = cell(&category, "y");
+
+        // Hom(x, y) holds f, g and α: f => g
+        let hom = category.hom_category(&x, &y).unwrap();
+        assert_eq!(hom.get_all_objects().unwrap().len(), 2);
+        let object = |hom: &BaseCategory<DiscreteCategory>, id: ObjectId| {
+            hom.get_object(&DiscreteCategory::new_with_id(id))
+                .unwrap()
+                .clone()
+        };
+        let (f, g) = (object(&hom, "f".into()), object(&hom, "g".into()));
+        let hom_set = hom.get_hom_set(&f, &g).unwrap();
+        assert_eq!(hom_set.len(), 1);
+        assert!(hom_set.into_iter().all(|alpha| alpha.arrow_id() == "α"));
+
+        // the chain 0 -> 1 -> 2 as 1-cells from x to y and 2-cells between them
+        let mut category = StrictNCategory::new_two_category("D".into());
+        let x = category.add_cell("x".into(), None, None).unwrap();
+        let y = category.add_cell("y".into(), None, None).unwrap();
+        category
+            .add_hom_category(&x, &y, &shapes::ordinal(2))
+            .unwrap();
+        let up = cell(&category, "0≤1");
+        assert_eq!(up.dimension(), 2);
+        assert_eq!(up.source_at(0).unwrap(), &*x);
+        let vertical = category.compose(&up, &cell(&category, "1≤2"), 1).unwrap();
+        assert_eq!(vertical, cell(&category, "0≤2"));
+        category.validate().unwrap();
+
+        // and back
+        let hom = category.hom_category(&x, &y).unwrap();
+        assert_eq!(hom.get_all_objects().unwrap().len(), 3);
+        let (zero, one, two) = (
+            object(&hom, ObjectId::Int(0)),
+            object(&hom, ObjectId::Int(1)),
+            object(&hom, ObjectId::Int(2)),
+        );
+        let morphism = |source, target| {
+            hom.get_hom_set(source, target)
+                .unwrap()
+                .into_iter()
+                .next()
+                .unwrap()
+                .clone()
+        };
+        let composite = hom
+            .compose_morphisms(&morphism(&zero, &one), &morphism(&one, &two))
+            .unwrap();
+        assert_eq!(composite.arrow_id(), "0≤2");
+    }
+}
@@ -0,0 +1,671 @@
+/*
+String diagrams for morphisms of a monoidal category.
+
+A diagram is stored as a sequence of slices, each slice holding exactly one box
+placed at an offset in the current list of wires, with identity wires on both sides:
+
+    wires:   A   B   C          offset 1, box g: B -> D ⊗ E
+             |   g   |
+             A  D E  C
+
+Two diagrams built from the same boxes are equal up to the interchange law
+(f ⊗ id) ∘ (id ⊗ g) = (id ⊗ g) ∘ (f ⊗ id) and planar isotopy when their normal forms agree.
+The normal form always places next the leftmost box whose input wires are available.
+Boxes without input wires are anchored to the wire on their left when they were placed.
+ */
+use crate::core::errors::Errors;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::traits::monoidal_category_trait::MonoidalCategoryTrait;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+// a morphism drawn as a box with ordered input and output wires
+#[derive(Debug)]
+pub struct DiagramBox<Category: CategoryTrait> {
+    morphism: Rc<Category::Morphism>,
+    inputs: Vec<Rc<Category::Object>>,
+    outputs: Vec<Rc<Category::Object>>,
+}
+
+impl<Category: CategoryTrait> DiagramBox<Category> {
+    // a box with a single input wire and a single output wire
+    pub fn new(morphism: Rc<Category::Morphism>) -> Self {
+        let inputs = vec![morphism.source_object().clone()];
+        let outputs = vec![morphism.target_object().clone()];
+        DiagramBox {
+            morphism,
+            inputs,
+            outputs,
+        }
+    }
+
+    pub fn morphism(&self) -> &Rc<Category::Morphism> {
+        &self.morphism
+    }
+
+    pub fn inputs(&self) -> &Vec<Rc<Category::Object>> {
+        &self.inputs
+    }
+
+    pub fn outputs(&self) -> &Vec<Rc<Category::Object>> {
+        &self.outputs
+    }
+}
+
+impl<Category: MonoidalCategoryTrait> DiagramBox<Category> {
+    // the source of the morphism has to be the tensor of the inputs, and its target of the outputs
+    pub fn new_with_wires(
+        category: &Category,
+        morphism: Rc<Category::Morphism>,
+        inputs: Vec<Rc<Category::Object>>,
+        outputs: Vec<Rc<Category::Object>>,
+    ) -> Result<Self, Errors> {
+        let diagram_box = DiagramBox {
+            morphism,
+            inputs,
+            outputs,
+        };
+        diagram_box.validate_wires(category)?;
+        Ok(diagram_box)
+    }
+
+    fn validate_wires(&self, category: &Category) -> Result<(), Errors> {
+        if self.morphism.source_object() != &fold_wires(category, &self.inputs)?
+            || self.morphism.target_object() != &fold_wires(category, &self.outputs)?
+        {
+            return Err(Errors::InvalidStringDiagram(
+                "Box morphism does not match its wires".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<Category: CategoryTrait> PartialEq for DiagramBox<Category> {
+    fn eq(&self, other: &Self) -> bool {
+        self.morphism == other.morphism
+            && self.inputs == other.inputs
+            && self.outputs == other.outputs
+    }
+}
+
+// nested expressions built from boxes with sequential and parallel composition
+#[derive(Debug)]
+pub enum MorphismTerm<Category: CategoryTrait> {
+    Identity(Vec<Rc<Category::Object>>),
+    Box(Rc<DiagramBox<Category>>),
+    // applied from first to last, i.e. Sequential([f, g]) is g ∘ f
+    Sequential(Vec<MorphismTerm<Category>>),
+    // tensored from left to right, i.e. Parallel([f, g]) is f ⊗ g
+    Parallel(Vec<MorphismTerm<Category>>),
+}
+
+#[derive(Debug)]
+pub struct Slice<Category: CategoryTrait> {
+    offset: usize,
+    diagram_box: Rc<DiagramBox<Category>>,
+}
+
+impl<Category: CategoryTrait> Clone for Slice<Category> {
+    fn clone(&self) -> Self {
+        Slice {
+            offset: self.offset,
+            diagram_box: self.diagram_box.clone(),
+        }
+    }
+}
+
+impl<Category: CategoryTrait> Slice<Category> {
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn diagram_box(&self) -> &Rc<DiagramBox<Category>> {
+        &self.diagram_box
+    }
+}
+
+#[derive(Debug)]
+pub struct StringDiagram<Category: CategoryTrait> {
+    domain: Vec<Rc<Category::Object>>,
+    codomain: Vec<Rc<Category::Object>>,
+    slices: Vec<Slice<Category>>,
+}
+
+impl<Category: CategoryTrait> Clone for StringDiagram<Category> {
+    fn clone(&self) -> Self {
+        StringDiagram {
+            domain: self.domain.clone(),
+            codomain: self.codomain.clone(),
+            slices: self.slices.clone(),
+        }
+    }
+}
+
+// a box in the normalisation, with its wires named by ids
+struct WiredBox {
+    slice: usize,
+    inputs: Vec<usize>,
+    outputs: Vec<usize>,
+    // for boxes without inputs, the wire on their left when placed (None for the left edge)
+    anchor: Option<usize>,
+}
+
+impl<Category: CategoryTrait> StringDiagram<Category> {
+    pub fn identity(wires: Vec<Rc<Category::Object>>) -> Self {
+        StringDiagram {
+            domain: wires.clone(),
+            codomain: wires,
+            slices: Vec::new(),
+        }
+    }
+
+    pub fn from_box(diagram_box: Rc<DiagramBox<Category>>) -> Self {
+        StringDiagram {
+            domain: diagram_box.inputs.clone(),
+            codomain: diagram_box.outputs.clone(),
+            slices: vec![Slice {
+                offset: 0,
+                diagram_box,
+            }],
+        }
+    }
+
+    pub fn from_morphism(morphism: Rc<Category::Morphism>) -> Self {
+        Self::from_box(Rc::new(DiagramBox::new(morphism)))
+    }
+
+    pub fn from_term(term: &MorphismTerm<Category>) -> Result<Self, Errors> {
+        match term {
+            MorphismTerm::Identity(wires) => Ok(Self::identity(wires.clone())),
+            MorphismTerm::Box(diagram_box) => Ok(Self::from_box(diagram_box.clone())),
+            MorphismTerm::Sequential(terms) => {
+                let (first, rest) = terms.split_first().ok_or(Errors::InvalidStringDiagram(
+                    "Empty sequential composition".to_string(),
+                ))?;
+                let mut result = Self::from_term(first)?;
+                for term in rest {
+                    result = result.then(&Self::from_term(term)?)?;
+                }
+                Ok(result)
+            }
+            MorphismTerm::Parallel(terms) => {
+                let mut result = Self::identity(Vec::new());
+                for term in terms {
+                    result = result.tensor(&Self::from_term(term)?);
+                }
+                Ok(result)
+            }
+        }
+    }
+
+    // one layer id ⊗ box ⊗ id per slice
+    pub fn to_term(&self) -> MorphismTerm<Category> {
+        let mut wires = self.domain.clone();
+        let mut layers = vec![MorphismTerm::Identity(wires.clone())];
+        for slice in &self.slices {
+            let diagram_box = &slice.diagram_box;
+            let right = wires.split_off(slice.offset + diagram_box.inputs.len());
+            wires.truncate(slice.offset);
+            layers.push(MorphismTerm::Parallel(vec![
+                MorphismTerm::Identity(wires.clone()),
+                MorphismTerm::Box(diagram_box.clone()),
+                MorphismTerm::Identity(right.clone()),
+            ]));
+            wires.extend(diagram_box.outputs.iter().cloned());
+            wires.extend(right);
+        }
+        MorphismTerm::Sequential(layers)
+    }
+
+    pub fn domain(&self) -> &Vec<Rc<Category::Object>> {
+        &self.domain
+    }
+
+    pub fn codomain(&self) -> &Vec<Rc<Category::Object>> {
+        &self.codomain
+    }
+
+    pub fn slices(&self) -> &Vec<Slice<Category>> {
+        &self.slices
+    }
+
+    // sequential composition, self first and then next
+    pub fn then(&self, next: &Self) -> Result<Self, Errors> {
+        if self.codomain != next.domain {
+            return Err(Errors::InvalidStringDiagram(
+                "Codomain does not match the next domain".to_string(),
+            ));
+        }
+        let mut slices = self.slices.clone();
+        slices.extend(next.slices.iter().cloned());
+        Ok(StringDiagram {
+            domain: self.domain.clone(),
+            codomain: next.codomain.clone(),
+            slices,
+        })
+    }
+
+    // parallel composition, self on the left: (self ⊗ id) then (id ⊗ other)
+    pub fn tensor(&self, other: &Self) -> Self {
+        let mut slices = self.slices.clone();
+        slices.extend(other.slices.iter().map(|slice| Slice {
+            offset: slice.offset + self.codomain.len(),
+            diagram_box: slice.diagram_box.clone(),
+        }));
+        let mut domain = self.domain.clone();
+        domain.extend(other.domain.iter().cloned());
+        let mut codomain = self.codomain.clone();
+        codomain.extend(other.codomain.iter().cloned());
+        StringDiagram {
+            domain,
+            codomain,
+            slices,
+        }
+    }
+
+    fn wired_boxes(&self) -> Vec<WiredBox> {
+        let mut next_wire = self.domain.len();
+        let mut wires = (0..next_wire).collect::<Vec<_>>();
+        let mut result = Vec::new();
+        for (index, slice) in self.slices.iter().enumerate() {
+            let diagram_box = &slice.diagram_box;
+            let end = slice.offset + diagram_box.inputs.len();
+            let outputs = (next_wire..next_wire + diagram_box.outputs.len()).collect::<Vec<_>>();
+            next_wire += outputs.len();
+            let anchor = match slice.offset {
+                0 => None,
+                offset => Some(wires[offset - 1]),
+            };
+            let inputs = wires
+                .splice(slice.offset..end, outputs.iter().copied())
+                .collect::<Vec<_>>();
+            result.push(WiredBox {
+                slice: index,
+                inputs,
+                outputs,
+                anchor,
+            });
+        }
+        result
+    }
+
+    pub fn normal_form(&self) -> Self {
+        let mut pending = self.wired_boxes();
+        let mut wires = (0..self.domain.len()).collect::<Vec<_>>();
+        let mut slices = Vec::new();
+
+        while !pending.is_empty() {
+            // (offset, boxes without inputs first, original position)
+            let mut best: Option<((usize, bool, usize), usize)> = None;
+            for (index, wired_box) in pending.iter().enumerate() {
+                let offset = if let Some(first) = wired_box.inputs.first() {
+                    // a box is blocked while an input-less box anchored to its inputs is pending
+                    let blocked = pending.iter().any(|other| {
+                        other.inputs.is_empty()
+                            && other
+                                .anchor
+                                .is_some_and(|anchor| wired_box.inputs.contains(&anchor))
+                    });
+                    match wires.iter().position(|wire| wire == first) {
+                        Some(start)
+                            if !blocked && wires[start..].starts_with(&wired_box.inputs[..]) =>
+                        {
+                            start
+                        }
+                        _ => continue,
+                    }
+                } else {
+                    match wired_box.anchor {
+                        None => 0,
+                        Some(anchor) => match wires.iter().position(|wire| *wire == anchor) {
+                            Some(position) => position + 1,
+                            None => continue,
+                        },
+                    }
+                };
+                let key = (offset, !wired_box.inputs.is_empty(), wired_box.slice);
+                if best.as_ref().is_none_or(|(best_key, _)| key < *best_key) {
+                    best = Some((key, index));
+                }
+            }
+            // a well formed diagram always has an available box
+            let Some(((offset, _, _), index)) = best else {
+                break;
+            };
+            let wired_box = pending.remove(index);
+            wires.splice(
+                offset..offset + wired_box.inputs.len(),
+                wired_box.outputs.iter().copied(),
+            );
+            slices.push(Slice {
+                offset,
+                diagram_box: self.slices[wired_box.slice].diagram_box.clone(),
+            });
+        }
+
+        StringDiagram {
+            domain: self.domain.clone(),
+            codomain: self.codomain.clone(),
+            slices,
+        }
+    }
+
+    // equality up to the interchange law
+    pub fn is_equivalent(&self, other: &Self) -> bool {
+        if self.domain != other.domain
+            || self.codomain != other.codomain
+            || self.slices.len() != other.slices.len()
+        {
+            return false;
+        }
+        let left = self.normal_form();
+        let right = other.normal_form();
+        left.slices
+            .iter()
+            .zip(right.slices.iter())
+            .all(|(left, right)| {
+                left.offset == right.offset && left.diagram_box == right.diagram_box
+            })
+    }
+}
+
+impl<Category: MonoidalCategoryTrait> StringDiagram<Category> {
+    // evaluates the diagram to a morphism ⊗domain -> ⊗codomain,
+    // where lists of wires are tensored from the left: ((A ⊗ B) ⊗ C)
+    pub fn to_morphism(&self, category: &Category) -> Result<Rc<Category::Morphism>, Errors> {
+        let mut wires = self.domain.clone();
+        let mut result = category
+            .get_identity_morphism(&*fold_wires(category, &wires)?)?
+            .clone();
+        for slice in &self.slices {
+            let diagram_box = &slice.diagram_box;
+            diagram_box.validate_wires(category)?;
+            let right = wires.split_off(slice.offset + diagram_box.inputs.len());
+            wires.truncate(slice.offset);
+            let left = wires;
+
+            let right_identity = category
+                .get_identity_morphism(&*fold_wires(category, &right)?)?
+                .clone();
+            let split_inputs = split_three(category, &left, &diagram_box.inputs, &right)?;
+            let layer = category.tensor_morphisms(
+                &category.tensor_morphisms(
+                    category.get_identity_morphism(&*fold_wires(category, &left)?)?,
+                    &diagram_box.morphism,
+                )?,
+                &right_identity,
+            )?;
+            let split_outputs = split_three(category, &left, &diagram_box.outputs, &right)?;
+            let merge_outputs = category.inverse_morphism(&split_outputs)?.ok_or(
+                Errors::InvalidMonoidalStructure("Rebracketing is not invertible".to_string()),
+            )?;
+
+            result = category.compose_morphisms(&result, &split_inputs)?;
+            result = category.compose_morphisms(&result, &layer)?;
+            result = category.compose_morphisms(&result, &merge_outputs)?;
+
+            wires = left;
+            wires.extend(diagram_box.outputs.iter().cloned());
+            wires.extend(right);
+        }
+        Ok(result)
+    }
+}
+
+// ⊗ of a list of wires bracketed to the left, the unit object for no wires
+fn fold_wires<Category: MonoidalCategoryTrait>(
+    category: &Category,
+    wires: &[Rc<Category::Object>],
+) -> Result<Rc<Category::Object>, Errors> {
+    let Some((first, rest)) = wires.split_first() else {
+        return category.unit_object();
+    };
+    let mut result = first.clone();
+    for wire in rest {
+        result = category.tensor_objects(&result, wire)?;
+    }
+    Ok(result)
+}
+
+// rebracketing ⊗(left ++ right) -> ⊗left ⊗ ⊗right built from associators and unitors
+fn split_wires<Category: MonoidalCategoryTrait>(
+    category: &Category,
+    left: &[Rc<Category::Object>],
+    right: &[Rc<Category::Object>],
+) -> Result<Rc<Category::Morphism>, Errors> {
+    let invert = |morphism: Rc<Category::Morphism>| {
+        category
+            .inverse_morphism(&morphism)?
+            .ok_or(Errors::InvalidMonoidalStructure(
+                "Unitor is not invertible".to_string(),
+            ))
+    };
+    if right.is_empty() {
+        return invert(category.right_unitor(&fold_wires(category, left)?)?);
+    }
+    if left.is_empty() {
+        return invert(category.left_unitor(&fold_wires(category, right)?)?);
+    }
+    let (last, init) = right
+        .split_last()
+        .ok_or(Errors::InvalidStringDiagram("Missing wires".to_string()))?;
+    if init.is_empty() {
+        let wires = [left, right].concat();
+        return Ok(category
+            .get_identity_morphism(&*fold_wires(category, &wires)?)?
+            .clone());
+    }
+    // ⊗(L ++ M) ⊗ x -> (⊗L ⊗ ⊗M) ⊗ x -> ⊗L ⊗ (⊗M ⊗ x)
+    let split_init = category.tensor_morphisms(
+        &split_wires(category, left, init)?,
+        category.get_identity_morphism(last)?,
+    )?;
+    let associator = category.associator(
+        &fold_wires(category, left)?,
+        &fold_wires(category, init)?,
+        last,
+    )?;
+    category.compose_morphisms(&split_init, &associator)
+}
+
+// ⊗(left ++ middle ++ right) -> (⊗left ⊗ ⊗middle) ⊗ ⊗right
+fn split_three<Category: MonoidalCategoryTrait>(
+    category: &Category,
+    left: &[Rc<Category::Object>],
+    middle: &[Rc<Category::Object>],
+    right: &[Rc<Category::Object>],
+) -> Result<Rc<Category::Morphism>, Errors> {
+    let left_middle = [left, middle].concat();
+    let outer = split_wires(category, &left_middle, right)?;
+    let inner = category.tensor_morphisms(
+        &split_wires(category, left, middle)?,
+        category.get_identity_morphism(&*fold_wires(category, right)?)?,
+    )?;
+    category.compose_morphisms(&outer, &inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::base_category::BaseCategory;
+    use crate::core::cartesian_monoidal_category::CartesianMonoidalCategory;
+    use crate::core::concrete_category::mat::{Mat, Matrix, Scalars};
+    use crate::core::discrete_category::DiscreteCategory;
+    use crate::core::shapes;
+
+    type Category = CartesianMonoidalCategory<BaseCategory<DiscreteCategory>>;
+
+    // the chain 0 ≤ 1 ≤ 2 with products as tensor
+    fn chain() -> Category {
+        CartesianMonoidalCategory::new(shapes::ordinal(2))
+    }
+
+    fn boxed(category: &Category, source: i32, target: i32) -> MorphismTerm<Category> {
+        let source = DiscreteCategory::new_with_id(source.into());
+        let target = DiscreteCategory::new_with_id(target.into());
+        let morphism = category
+            .get_hom_set(&source, &target)
+            .unwrap()
+            .into_iter()
+            .next()
+            .unwrap()
+            .clone();
+        MorphismTerm::Box(Rc::new(DiagramBox::new(morphism)))
+    }
+
+    fn wire(category: &Category, id: i32) -> Rc<DiscreteCategory> {
+        category
+            .get_object(&DiscreteCategory::new_with_id(id.into()))
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn test_interchange_law() {
+        let category = chain();
+        // f ⊗ g
+        let parallel = StringDiagram::from_term(&MorphismTerm::Parallel(vec![
+            boxed(&category, 0, 1),
+            boxed(&category, 1, 2),
+        ]))
+        .unwrap();
+        // (f ⊗ id) then (id ⊗ g)
+        let left_first = StringDiagram::from_term(&MorphismTerm::Sequential(vec![
+            MorphismTerm::Parallel(vec![
+                boxed(&category, 0, 1),
+                MorphismTerm::Identity(vec![wire(&category, 1)]),
+            ]),
+            MorphismTerm::Parallel(vec![
+                MorphismTerm::Identity(vec![wire(&category, 1)]),
+                boxed(&category, 1, 2),
+            ]),
+        ]))
+        .unwrap();
+        // (id ⊗ g) then (f ⊗ id)
+        let right_first = StringDiagram::from_term(&MorphismTerm::Sequential(vec![
+            MorphismTerm::Parallel(vec![
+                MorphismTerm::Identity(vec![wire(&category, 0)]),
+                boxed(&category, 1, 2),
+            ]),
+            MorphismTerm::Parallel(vec![
+                boxed(&category, 0, 1),
+                MorphismTerm::Identity(vec![wire(&category, 2)]),
+            ]),
+        ]))
+        .unwrap();
+
+        assert!(parallel.is_equivalent(&left_first));
+        assert!(parallel.is_equivalent(&right_first));
+        assert_eq!(right_first.normal_form().slices()[0].offset(), 0);
+
+        let morphism = right_first.to_morphism(&category).unwrap();
+        assert_eq!(morphism, parallel.to_morphism(&category).unwrap());
+        assert_eq!(morphism.source_object(), &wire(&category, 0));
+        assert_eq!(morphism.target_object(), &wire(&category, 1));
+
+        let round_trip = StringDiagram::from_term(&right_first.to_term()).unwrap();
+        assert!(round_trip.is_equivalent(&parallel));
+    }
+
+    #[test]
+    fn test_sequential_boxes_do_not_commute() {
+        let category = chain();
+        let first = StringDiagram::from_term(&MorphismTerm::Sequential(vec![
+            boxed(&category, 0, 1),
+            boxed(&category, 1, 2),
+        ]))
+        .unwrap();
+        let second = StringDiagram::from_term(&boxed(&category, 0, 2)).unwrap();
+        assert!(!first.is_equivalent(&second));
+        // but they evaluate to the same morphism in a thin category
+        assert_eq!(
+            first.to_morphism(&category).unwrap(),
+            second.to_morphism(&category).unwrap()
+        );
+
+        let mismatched = StringDiagram::from_term(&MorphismTerm::Sequential(vec![
+            boxed(&category, 1, 2),
+            boxed(&category, 0, 1),
+        ]));
+        assert!(mismatched.is_err());
+    }
+
+    #[test]
+    fn test_boxes_with_wires() {
+        let mat = Mat::up_to(4, Scalars::Integers).unwrap();
+        let (two, three) = (
+            mat.dimension(2).unwrap().clone(),
+            mat.dimension(3).unwrap().clone(),
+        );
+        // the sum of two vectors, 2 ⊗ 2 -> 2
+        let rows = vec![vec![1, 0, 0, 1], vec![0, 1, 1, 0]];
+        let sum = mat.matrix("sum", 4, 2, rows).unwrap();
+        let diagram_box = DiagramBox::new_with_wires(
+            &mat,
+            sum.clone(),
+            vec![two.clone(), two.clone()],
+            vec![two.clone()],
+        )
+        .unwrap();
+        assert_eq!(diagram_box.inputs().len(), 2);
+        assert!(
+            DiagramBox::new_with_wires(
+                &mat,
+                sum.clone(),
+                vec![two.clone(), three.clone()],
+                vec![two.clone()]
+            )
+            .is_err()
+        );
+        assert!(DiagramBox::new_with_wires(&mat, sum, vec![two.clone()], vec![two]).is_err());
+    }
+
+    #[test]
+    fn test_interchange_keeps_order_of_non_commuting_boxes() {
+        let mat = Mat::up_to(4, Scalars::Integers).unwrap();
+        let f = mat.matrix("f", 2, 2, vec![vec![0, 1], vec![1, 0]]).unwrap();
+        let g = mat.matrix("g", 2, 2, vec![vec![1, 1], vec![0, 1]]).unwrap();
+        let two = mat.dimension(2).unwrap().clone();
+        let boxed =
+            |matrix: &Rc<Matrix>| MorphismTerm::Box(Rc::new(DiagramBox::new(matrix.clone())));
+
+        // f ⊗ g and g ⊗ f have the same boxes and wires, but f and g change places
+        let f_g =
+            StringDiagram::from_term(&MorphismTerm::Parallel(vec![boxed(&f), boxed(&g)])).unwrap();
+        let g_f =
+            StringDiagram::from_term(&MorphismTerm::Parallel(vec![boxed(&g), boxed(&f)])).unwrap();
+        assert!(!f_g.is_equivalent(&g_f));
+        assert_ne!(
+            f_g.to_morphism(&mat).unwrap(),
+            g_f.to_morphism(&mat).unwrap()
+        );
+
+        // g after f on the same wire is not f after g, as g ∘ f ≠ f ∘ g
+        let f_then_g =
+            StringDiagram::from_term(&MorphismTerm::Sequential(vec![boxed(&f), boxed(&g)]))
+                .unwrap();
+        let g_then_f =
+            StringDiagram::from_term(&MorphismTerm::Sequential(vec![boxed(&g), boxed(&f)]))
+                .unwrap();
+        assert!(!f_then_g.is_equivalent(&g_then_f));
+        assert_ne!(
+            f_then_g.to_morphism(&mat).unwrap(),
+            g_then_f.to_morphism(&mat).unwrap()
+        );
+
+        // only boxes on different wires slide past each other
+        let sliding = StringDiagram::from_term(&MorphismTerm::Sequential(vec![
+            MorphismTerm::Parallel(vec![MorphismTerm::Identity(vec![two.clone()]), boxed(&g)]),
+            MorphismTerm::Parallel(vec![boxed(&f), MorphismTerm::Identity(vec![two.clone()])]),
+        ]))
+        .unwrap();
+        assert!(sliding.is_equivalent(&f_g));
+        assert!(!sliding.is_equivalent(&g_f));
+        assert_eq!(
+            sliding.to_morphism(&mat).unwrap(),
+            f_g.to_morphism(&mat).unwrap()
+        );
+    }
+}
@@ -0,0 +1,268 @@
+/*
+Monoidal categories with a deliberately wrong structure map, to check that the
+monoidal and braided validators reject them.
+ */
+use crate::core::cartesian_monoidal_category::CartesianMonoidalCategory;
+use crate::core::concrete_category::fin_set::{FinSet, Function};
+use crate::core::concrete_category::mat::{Mat, Matrix, Scalars};
+use crate::core::discrete_category::DiscreteCategory;
+use crate::core::errors::Errors;
+use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::braided_monoidal_category_trait::{
+    BraidedMonoidalCategoryTrait, SymmetricMonoidalCategoryTrait,
+};
+use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::traits::finite_limits_trait::FiniteDiagram;
+use crate::core::traits::monoidal_category_trait::MonoidalCategoryTrait;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Twist {
+    Associator,
+    LeftUnitor,
+    Braiding,
+}
+
+pub type Automorphism<Category> = fn(
+    &Category,
+    &Rc<<Category as CategoryTrait>::Object>,
+) -> Result<Rc<<Category as CategoryTrait>::Morphism>, Errors>;
+
+// a monoidal category whose associator, left unitor or braiding is followed by an automorphism
+#[derive(Debug, Clone)]
+pub struct Twisted<Category: MonoidalCategoryTrait + Clone> {
+    pub category: Category,
+    pub twist: Twist,
+    pub automorphism: Automorphism<Category>,
+}
+
+impl<Category: MonoidalCategoryTrait + Clone> Twisted<Category> {
+    fn twisted(
+        &self,
+        twist: Twist,
+        morphism: Rc<Category::Morphism>,
+    ) -> Result<Rc<Category::Morphism>, Errors> {
+        if twist != self.twist {
+            return Ok(morphism);
+        }
+        let automorphism = (self.automorphism)(&self.category, morphism.target_object())?;
+        self.category.compose_morphisms(&morphism, &automorphism)
+    }
+}
+
+pub fn untwisted<Category: CategoryTrait>(
+    category: &Category,
+    object: &Rc<Category::Object>,
+) -> Result<Rc<Category::Morphism>, Errors> {
+    Ok(category.get_identity_morphism(object)?.clone())
+}
+
+impl<Category: MonoidalCategoryTrait + Clone> CategoryTrait for Twisted<Category> {
+    type Object = Category::Object;
+
+    type Morphism = Category::Morphism;
+
+    fn new() -> Self {
+        Twisted {
+            category: Category::new(),
+            twist: Twist::Associator,
+            automorphism: untwisted,
+        }
+    }
+
+    fn new_with_id(id: &ObjectId) -> Self {
+        Twisted {
+            category: Category::new_with_id(id),
+            twist: Twist::Associator,
+            automorphism: untwisted,
+        }
+    }
+
+    fn category_id(&self) -> &ObjectId {
+        self.category.category_id()
+    }
+
+    fn update_category_id(&mut self, new_id: ObjectId) {
+        self.category.update_category_id(new_id);
+    }
+
+    fn add_object(&mut self, object: Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        self.category.add_object(object)
+    }
+
+    fn add_morphism(
+        &mut self,
+        morphism: Rc<Self::Morphism>,
+    ) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.category.add_morphism(morphism)
+    }
+
+    fn get_identity_morphism(&self, object: &Self::Object) -> Result<&Rc<Self::Morphism>, Errors> {
+        self.category.get_identity_morphism(object)
+    }
+
+    fn get_object(&self, object: &Self::Object) -> Result<&Rc<Self::Object>, Errors> {
+        self.category.get_object(object)
+    }
+
+    fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
+        self.category.get_all_objects()
+    }
+
+    fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        self.category.get_all_morphisms()
+    }
+
+    fn get_hom_set_x(
+        &self,
+        source_object: &Self::Object,
+    ) -> Result<HashSet<&Rc<Self::Morphism>>, Errors> {
+        self.category.get_hom_set_x(source_object)
+    }
+
+    fn get_object_morphisms(
+        &self,
+        object: &Self::Object,
+    ) -> Result<Vec<&Rc<Self::Morphism>>, Errors> {
+        self.category.get_object_morphisms(object)
+    }
+
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        self.category.compose_morphisms(first, second)
+    }
+
+    fn inverse_morphism(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        self.category.inverse_morphism(morphism)
+    }
+}
+
+impl<Category: MonoidalCategoryTrait + Clone> MonoidalCategoryTrait for Twisted<Category> {
+    fn tensor_objects(
+        &self,
+        left: &Rc<Self::Object>,
+        right: &Rc<Self::Object>,
+    ) -> Result<Rc<Self::Object>, Errors> {
+        self.category.tensor_objects(left, right)
+    }
+
+    fn tensor_morphisms(
+        &self,
+        left: &Rc<Self::Morphism>,
+        right: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        self.category.tensor_morphisms(left, right)
+    }
+
+    fn unit_object(&self) -> Result<Rc<Self::Object>, Errors> {
+        self.category.unit_object()
+    }
+
+    fn associator(
+        &self,
+        first: &Rc<Self::Object>,
+        second: &Rc<Self::Object>,
+        third: &Rc<Self::Object>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        let associator = self.category.associator(first, second, third)?;
+        self.twisted(Twist::Associator, associator)
+    }
+
+    fn left_unitor(&self, object: &Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        let left_unitor = self.category.left_unitor(object)?;
+        self.twisted(Twist::LeftUnitor, left_unitor)
+    }
+
+    fn right_unitor(&self, object: &Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors> {
+        self.category.right_unitor(object)
+    }
+}
+
+impl<Category: BraidedMonoidalCategoryTrait + Clone> BraidedMonoidalCategoryTrait
+    for Twisted<Category>
+{
+    fn braiding(
+        &self,
+        left: &Rc<Self::Object>,
+        right: &Rc<Self::Object>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        let braiding = self.category.braiding(left, right)?;
+        self.twisted(Twist::Braiding, braiding)
+    }
+}
+
+impl<Category: BraidedMonoidalCategoryTrait + Clone> SymmetricMonoidalCategoryTrait
+    for Twisted<Category>
+{
+}
+
+// Mat over the integers on 0 and 1, with the scalars 2 and -1 as extra endomorphisms of 1
+pub fn scalars() -> Mat {
+    let mut mat = Mat::up_to(1, Scalars::Integers).unwrap();
+    mat.add_matrix("2", 1, 1, vec![vec![2]]).unwrap();
+    mat.add_matrix("-1", 1, 1, vec![vec![-1]]).unwrap();
+    mat
+}
+
+// -1 on every dimension, which is natural but breaks the coherence laws
+pub fn negate(mat: &Mat, object: &Rc<DiscreteCategory>) -> Result<Rc<Matrix>, Errors> {
+    let dimension = Mat::dimension_of(object);
+    let rows = (0..dimension)
+        .map(|row| {
+            (0..dimension)
+                .map(|column| if row == column { -1 } else { 0 })
+                .collect()
+        })
+        .collect();
+    mat.matrix("-1", dimension, dimension, rows)
+}
+
+// the set {0, 1} with both constant functions
+pub fn constants() -> CartesianMonoidalCategory<FinSet> {
+    let mut fin_set = FinSet::new();
+    let bit = fin_set.add_set("bit", vec![0, 1]).unwrap();
+    for value in [0, 1] {
+        let table = [(0, value), (1, value)]
+            .into_iter()
+            .map(|(element, image)| (ObjectId::Int(element), ObjectId::Int(image)))
+            .collect::<HashMap<_, _>>();
+        fin_set
+            .add_function(&format!("const {}", value), &bit, &bit, table)
+            .unwrap();
+    }
+    // the unit 1 is an object up front, its products are built as they are needed
+    fin_set.add_limit(&FiniteDiagram::new()).unwrap();
+    CartesianMonoidalCategory::new(fin_set)
+}
+
+// exchanges the first two elements, which no constant function commutes with
+pub fn exchange(
+    _: &CartesianMonoidalCategory<FinSet>,
+    object: &Rc<DiscreteCategory>,
+) -> Result<Rc<Function>, Errors> {
+    let elements = FinSet::elements(object);
+    let mut table = elements
+        .iter()
+        .map(|element| (element.clone(), element.clone()))
+        .collect::<HashMap<_, _>>();
+    if let [first, second, ..] = elements.as_slice() {
+        table.insert(first.clone(), second.clone());
+        table.insert(second.clone(), first.clone());
+    }
+    FinSet::function(object, object, &table)
+}
+
+pub fn failure(result: Result<(), Errors>) -> String {
+    match result {
+        Err(Errors::InvalidMonoidalStructure(message)) => message,
+        other => panic!("expected an invalid monoidal structure, got {:?}", other),
+    }
+}
@@ -1,6 +1,9 @@
 use crate::core::arrow::{Arrow, Functor, Morphism};
 use crate::core::dynamic_category::DynamicCategory;
+use crate::core::errors::Errors;
 use crate::core::object_id::ObjectId;
+use crate::core::object_path::ObjectPath;
+use crate::core::traits::arrow_trait::ArrowTrait;
 use crate::core::traits::category_trait::CategoryTrait;
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -20,8 +23,8 @@ pub fn test_base_scenario() {
     let morphisms = morphisms.unwrap();
     assert_eq!(morphisms.len(), 0);
 
-    // let level = category.level();
-    // assert_eq!(level, 0);
+    let level = category.level().unwrap();
+    assert_eq!(level, 0);
 
     // now add a set of a, b, c
     let object_a: DynamicCategory = vec!["a", "b", "c"].into();
@@ -46,7 +49,7 @@ pub fn test_base_scenario() {
             .iter()
             .any(|o| o.equal_to(&<&str as Into<DynamicCategory>>::into("c")))
     );
-    // assert_eq!(object_a.level(), 1);
+    assert_eq!(object_a.level().unwrap(), 1);
     let object_a = Rc::new(object_a);
 
     category.add_object(object_a.clone()).unwrap();
@@ -57,7 +60,8 @@ pub fn test_base_scenario() {
     let objects = objects.unwrap();
     assert_eq!(objects.len(), 1);
     let first_object = objects.iter().next().unwrap();
-    // assert_eq!(first_object.level(), 1);
+    assert_eq!(first_object.level().unwrap(), 1);
+    assert_eq!(category.level().unwrap(), 2);
     assert!(first_object.equal_to(&*object_a));
     assert!(first_object.equal_to(&*object_a));
 
@@ -219,3 +223,134 @@ pub fn test_base_scenario() {
         DynamicCategory::functor_to_category(morphism_a_num).expect("Expecting category"),
     ));
 }
+
+// root contains schema, which contains the tables users and orders with columns
+fn schema_category() -> DynamicCategory {
+    let mut users: DynamicCategory = vec!["id", "name"].into();
+    users.update_category_id("users".into());
+    let mut orders: DynamicCategory = vec!["id"].into();
+    orders.update_category_id("orders".into());
+    let mut schema = DynamicCategory::new_with_id("schema".into());
+    schema.add_object(Rc::new(users)).unwrap();
+    schema.add_object(Rc::new(orders)).unwrap();
+    // a point next to the tables, so the depth varies per object
+    schema.add_object(Rc::new("empty".into())).unwrap();
+    let mut root = DynamicCategory::new_with_id("root".into());
+    root.add_object(Rc::new(schema)).unwrap();
+    root
+}
+
+#[test]
+pub fn test_nested_elements() {
+    let root = schema_category();
+    assert_eq!(root.level().unwrap(), 3);
+
+    let root_level = root.nested_elements(0).unwrap().collect::<Vec<_>>();
+    assert_eq!(root_level.len(), 1);
+    assert_eq!(root_level[0].path(), &[ObjectId::from("root")]);
+
+    // the three objects of schema and their identities
+    let tables = root.nested_elements(2).unwrap().collect::<Vec<_>>();
+    assert_eq!(
+        tables.iter().filter(|element| element.is_object()).count(),
+        3
+    );
+    assert_eq!(
+        tables
+            .iter()
+            .filter(|element| element.is_morphism())
+            .count(),
+        3
+    );
+    let users = tables
+        .iter()
+        .find(|element| element.path() == [ObjectId::from("root"), "schema".into(), "users".into()])
+        .unwrap();
+    assert_eq!(
+        users
+            .as_object::<DynamicCategory>()
+            .unwrap()
+            .level()
+            .unwrap(),
+        1
+    );
+
+    let columns = root
+        .nested_elements(3)
+        .unwrap()
+        .filter(|element| element.is_object())
+        .map(|element| {
+            element
+                .path()
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        columns,
+        vec![
+            "root/schema/orders/id",
+            "root/schema/users/id",
+            "root/schema/users/name"
+        ]
+    );
+    assert_eq!(root.nested_elements(4).unwrap().count(), 0);
+}
+
+#[test]
+pub fn test_object_paths() {
+    let root = schema_category();
+
+    let column = root.resolve_path(&"root/schema/users/name".into()).unwrap();
+    let column = column.as_object::<DynamicCategory>().unwrap();
+    assert!(column.equal_to(&"name".into()));
+    assert_eq!(
+        root.path_of(column).unwrap().to_string(),
+        "root/schema/users/name"
+    );
+
+    // the identity of a column inside its table
+    let users = root
+        .resolve_path(&"root/schema/users".into())
+        .unwrap()
+        .as_object::<DynamicCategory>()
+        .unwrap();
+    let identity = users.get_identity_morphism(column).unwrap();
+    let path = ObjectPath::from("root/schema/users").child(identity.arrow_id().clone().into());
+    let morphism = root.resolve_path(&path).unwrap();
+    assert!(morphism.is_morphism());
+    assert_eq!(root.path_of(&**identity).unwrap(), path);
+
+    // the failing segment is named
+    assert_eq!(
+        root.resolve_path(&"root/schema/accounts/id".into()).err(),
+        Some(Errors::ObjectNotFound("accounts".into()))
+    );
+    assert_eq!(
+        root.resolve_path(&"other/schema".into()).err(),
+        Some(Errors::ObjectNotFound("other".into()))
+    );
+    // an equal copy is found by its id
+    assert_eq!(
+        root.path_of(&DynamicCategory::from("name"))
+            .unwrap()
+            .to_string(),
+        "root/schema/users/name"
+    );
+    assert!(root.path_of(&DynamicCategory::from("missing")).is_err());
+
+    // both tables have an id column, the stored one is told apart by identity
+    let users_id = users.get_object(&"id".into()).unwrap();
+    assert_eq!(
+        root.path_of(&**users_id).unwrap().to_string(),
+        "root/schema/users/id"
+    );
+    assert_eq!(
+        root.path_of(&DynamicCategory::from("id"))
+            .unwrap()
+            .to_string(),
+        "root/schema/orders/id"
+    );
+}
@@ -2,6 +2,7 @@ use crate::core::arrow::{Arrow, Functor, Morphism};
 use crate::core::base_category::*;
 use crate::core::discrete_category::DiscreteCategory;
 use crate::core::dynamic_category::DynamicCategory;
+use crate::core::errors::Errors;
 use crate::core::identifier::Identifier;
 use crate::core::object_id::ObjectId;
 use crate::core::tests::ncategory_test_helper::*;
@@ -791,3 +792,140 @@ pub fn test_nested_category() {
         .add_morphism(natural_transformation_morphism)
         .unwrap();
 }
+
+#[test]
+pub fn test_nested_level() {
+    assert_eq!(DiscreteCategory::nested_level(), Some(1));
+    assert_eq!(BaseCategory::<DiscreteCategory>::nested_level(), Some(2));
+    assert_eq!(
+        BaseCategory::<BaseCategory<DiscreteCategory>>::nested_level(),
+        Some(3)
+    );
+
+    // a and b are points, so the category of them is level 1 like its dynamic twin
+    let category: BaseCategory<DiscreteCategory> = vec!["a", "b"].into();
+    assert_eq!(category.level().unwrap(), 1);
+    assert_eq!(DynamicCategory::from(vec!["a", "b"]).level().unwrap(), 1);
+    assert!(Some(category.level().unwrap()) <= BaseCategory::<DiscreteCategory>::nested_level());
+
+    // a category of sets reaches the level of its type
+    let mut sets: BaseCategory<DiscreteCategory> = BaseCategory::new();
+    let set: DiscreteCategory = vec!["x", "y"].into();
+    assert_eq!(Some(set.level().unwrap()), DiscreteCategory::nested_level());
+    sets.add_object(Rc::new(set)).unwrap();
+    assert_eq!(
+        Some(sets.level().unwrap()),
+        BaseCategory::<DiscreteCategory>::nested_level()
+    );
+    // dynamic categories nest without bound, and so does anything holding them
+    assert_eq!(DynamicCategory::nested_level(), None);
+    assert_eq!(BaseCategory::<DynamicCategory>::nested_level(), None);
+    let objects = category
+        .nested_elements(1)
+        .unwrap()
+        .filter(|element| element.is_object())
+        .map(|element| element.path().to_vec())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        objects,
+        vec![
+            vec![category.category_id().clone(), "a".into()],
+            vec![category.category_id().clone(), "b".into()]
+        ]
+    );
+}
+
+// f, g: A -> B, m: B -> C and the composites mf, mg: A -> C, none carrying an action
+fn parallel_composites() -> BaseCategory<DiscreteCategory> {
+    let mut category: BaseCategory<DiscreteCategory> = vec!["A", "B", "C"].into();
+    let object = |category: &BaseCategory<DiscreteCategory>, id: &str| {
+        category
+            .get_object(&DiscreteCategory::new_with_id(id.into()))
+            .unwrap()
+            .clone()
+    };
+    for (id, source, target) in [
+        ("f", "A", "B"),
+        ("g", "A", "B"),
+        ("m", "B", "C"),
+        ("mf", "A", "C"),
+        ("mg", "A", "C"),
+    ] {
+        let morphism = Morphism::new(
+            id.to_string(),
+            object(&category, source),
+            object(&category, target),
+            HashMap::new(),
+        );
+        category.add_morphism(Rc::new(morphism)).unwrap();
+    }
+    category
+}
+
+#[test]
+pub fn test_composition_table() {
+    let mut category = parallel_composites();
+    let morphism = |category: &BaseCategory<DiscreteCategory>, id: &str| {
+        category.morphism(id).unwrap().clone()
+    };
+    let (f, m) = (morphism(&category, "f"), morphism(&category, "m"));
+    // two candidates and no action, the composite is unknown until recorded
+    assert_eq!(
+        category.compose_morphisms(&f, &m),
+        Err(Errors::InvalidMorphismComposition)
+    );
+    assert_eq!(
+        category.add_composition("f", "m", "f"),
+        Err(Errors::InvalidMorphismComposition)
+    );
+    category.add_composition("f", "m", "mf").unwrap();
+    category.add_composition("g", "m", "mg").unwrap();
+    assert_eq!(
+        category.add_composition("f", "m", "mg"),
+        Err(Errors::InvalidMorphismComposition)
+    );
+    assert_eq!(
+        category.compose_morphisms(&f, &m).unwrap(),
+        morphism(&category, "mf")
+    );
+    let g = morphism(&category, "g");
+    assert_eq!(
+        category.compose_morphisms(&g, &m).unwrap(),
+        morphism(&category, "mg")
+    );
+    let identity = category.get_identity_morphism(f.source_object()).unwrap();
+    assert_eq!(category.compose_morphisms(identity, &f).unwrap(), f);
+}
+
+#[test]
+pub fn test_mono_and_epi() {
+    let morphism = |category: &BaseCategory<DiscreteCategory>, id: &str| {
+        category.morphism(id).unwrap().clone()
+    };
+    // m tells f and g apart, it is mono
+    let mut category = parallel_composites();
+    category.add_composition("f", "m", "mf").unwrap();
+    category.add_composition("g", "m", "mg").unwrap();
+    let (f, m) = (morphism(&category, "f"), morphism(&category, "m"));
+    assert!(category.is_mono(&m).unwrap());
+    assert!(category.is_epi(&f).unwrap());
+
+    // m ∘ f = m ∘ g with f ≠ g, it is not
+    let mut merging = parallel_composites();
+    merging.add_composition("f", "m", "mf").unwrap();
+    merging.add_composition("g", "m", "mf").unwrap();
+    assert!(!merging.is_mono(&morphism(&merging, "m")).unwrap());
+
+    // a second n: B -> C with n ∘ f = m ∘ f, so f is no longer epi
+    let n = Morphism::new(
+        "n".to_string(),
+        m.source_object().clone(),
+        m.target_object().clone(),
+        HashMap::new(),
+    );
+    category.add_morphism(Rc::new(n)).unwrap();
+    category.add_composition("f", "n", "mf").unwrap();
+    category.add_composition("g", "n", "mg").unwrap();
+    assert!(!category.is_epi(&f).unwrap());
+    assert!(category.is_mono(&morphism(&category, "n")).unwrap());
+}
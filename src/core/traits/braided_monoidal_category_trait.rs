@@ -0,0 +1,257 @@
+use crate::core::errors::Errors;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::traits::monoidal_category_trait::MonoidalCategoryTrait;
+use crate::core::utils::sorted_objects;
+use std::rc::Rc;
+
+/*
+A braided monoidal category has a natural isomorphism σ_{A,B}: A ⊗ B -> B ⊗ A
+compatible with the associator through the two hexagon identities.
+It is symmetric when crossing twice is the identity, σ_{B,A} ∘ σ_{A,B} = id_{A⊗B}.
+ */
+pub trait BraidedMonoidalCategoryTrait: MonoidalCategoryTrait {
+    fn braiding(
+        &self,
+        left: &Rc<Self::Object>,
+        right: &Rc<Self::Object>,
+    ) -> Result<Rc<Self::Morphism>, Errors>;
+
+    // checks on every object of the (finite) category that σ is a natural isomorphism
+    // satisfying both hexagon identities
+    fn validate_braided_structure(&self) -> Result<(), Errors> {
+        let objects = sorted_objects(self.get_all_objects()?);
+        for first in &objects {
+            for second in &objects {
+                self.validate_isomorphism(&self.braiding(first, second)?, "braiding")?;
+                self.validate_braiding_naturality(first, second)?;
+                for third in &objects {
+                    self.validate_hexagons(first, second, third)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // σ_{A',B'} ∘ (f ⊗ g) = (g ⊗ f) ∘ σ_{A,B} for all f: A -> A' and g: B -> B'
+    fn validate_braiding_naturality(
+        &self,
+        first: &Rc<Self::Object>,
+        second: &Rc<Self::Object>,
+    ) -> Result<(), Errors> {
+        let braiding = self.braiding(first, second)?;
+        for left in self.get_hom_set_x(first)? {
+            for right in self.get_hom_set_x(second)? {
+                let left_path = self.compose_morphisms(
+                    &self.tensor_morphisms(left, right)?,
+                    &self.braiding(left.target_object(), right.target_object())?,
+                )?;
+                let right_path =
+                    self.compose_morphisms(&braiding, &self.tensor_morphisms(right, left)?)?;
+                if left_path != right_path {
+                    return Err(Errors::InvalidMonoidalStructure(format!(
+                        "Braiding is not natural in {} and {}",
+                        first.category_id(),
+                        second.category_id()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // α_{B,C,A} ∘ σ_{A,B⊗C} ∘ α_{A,B,C} = (id_B ⊗ σ_{A,C}) ∘ α_{B,A,C} ∘ (σ_{A,B} ⊗ id_C)
+    // α⁻¹_{C,A,B} ∘ σ_{A⊗B,C} ∘ α⁻¹_{A,B,C} = (σ_{A,C} ⊗ id_B) ∘ α⁻¹_{A,C,B} ∘ (id_A ⊗ σ_{B,C})
+    fn validate_hexagons(
+        &self,
+        first: &Rc<Self::Object>,
+        second: &Rc<Self::Object>,
+        third: &Rc<Self::Object>,
+    ) -> Result<(), Errors> {
+        let first_identity = self.get_identity_morphism(first)?;
+        let second_identity = self.get_identity_morphism(second)?;
+        let third_identity = self.get_identity_morphism(third)?;
+
+        let left_path = self.compose_morphisms(
+            &self.associator(first, second, third)?,
+            &self.braiding(first, &self.tensor_objects(second, third)?)?,
+        )?;
+        let left_path =
+            self.compose_morphisms(&left_path, &self.associator(second, third, first)?)?;
+        let right_path = self.compose_morphisms(
+            &self.tensor_morphisms(&self.braiding(first, second)?, third_identity)?,
+            &self.associator(second, first, third)?,
+        )?;
+        let right_path = self.compose_morphisms(
+            &right_path,
+            &self.tensor_morphisms(second_identity, &self.braiding(first, third)?)?,
+        )?;
+        if left_path != right_path {
+            return Err(Errors::InvalidMonoidalStructure(format!(
+                "First hexagon identity fails for {}, {} and {}",
+                first.category_id(),
+                second.category_id(),
+                third.category_id()
+            )));
+        }
+
+        let left_path = self.compose_morphisms(
+            &self.inverse_associator(first, second, third)?,
+            &self.braiding(&self.tensor_objects(first, second)?, third)?,
+        )?;
+        let left_path =
+            self.compose_morphisms(&left_path, &self.inverse_associator(third, first, second)?)?;
+        let right_path = self.compose_morphisms(
+            &self.tensor_morphisms(first_identity, &self.braiding(second, third)?)?,
+            &self.inverse_associator(first, third, second)?,
+        )?;
+        let right_path = self.compose_morphisms(
+            &right_path,
+            &self.tensor_morphisms(&self.braiding(first, third)?, second_identity)?,
+        )?;
+        if left_path != right_path {
+            return Err(Errors::InvalidMonoidalStructure(format!(
+                "Second hexagon identity fails for {}, {} and {}",
+                first.category_id(),
+                second.category_id(),
+                third.category_id()
+            )));
+        }
+        Ok(())
+    }
+
+    // α⁻¹_{A,B,C}: A ⊗ (B ⊗ C) -> (A ⊗ B) ⊗ C
+    fn inverse_associator(
+        &self,
+        first: &Rc<Self::Object>,
+        second: &Rc<Self::Object>,
+        third: &Rc<Self::Object>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        let associator = self.associator(first, second, third)?;
+        self.inverse_morphism(&associator)?
+            .ok_or(Errors::InvalidMonoidalStructure(format!(
+                "The associator from {} is not an isomorphism",
+                associator.source_object().category_id()
+            )))
+    }
+}
+
+pub trait SymmetricMonoidalCategoryTrait: BraidedMonoidalCategoryTrait {
+    // a braided structure whose braiding is its own inverse, σ_{B,A} ∘ σ_{A,B} = id_{A⊗B}
+    fn validate_symmetric_structure(&self) -> Result<(), Errors> {
+        self.validate_braided_structure()?;
+        let objects = sorted_objects(self.get_all_objects()?);
+        for first in &objects {
+            for second in &objects {
+                let twice = self.compose_morphisms(
+                    &self.braiding(first, second)?,
+                    &self.braiding(second, first)?,
+                )?;
+                if !twice.is_identity() {
+                    return Err(Errors::InvalidMonoidalStructure(format!(
+                        "Braiding of {} and {} is not symmetric",
+                        first.category_id(),
+                        second.category_id()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::concrete_category::fin_set::FinSet;
+    use crate::core::concrete_category::mat::{Mat, Scalars};
+    use crate::core::discrete_category::DiscreteCategory;
+    use crate::core::object_id::ObjectId;
+    use crate::core::tests::monoidal_test_helper::{
+        Twist, Twisted, constants, exchange, failure, negate, scalars,
+    };
+
+    #[test]
+    fn test_braiding_swaps_wires() {
+        let mut mat = Mat::up_to(8, Scalars::Integers).unwrap();
+        mat.add_matrix("f", 2, 2, vec![vec![1, 2], vec![3, 4]])
+            .unwrap();
+        mat.add_matrix("g", 3, 3, vec![vec![0, 1, 0], vec![0, 0, 1], vec![5, 0, 0]])
+            .unwrap();
+        let (two, three) = (
+            mat.dimension(2).unwrap().clone(),
+            mat.dimension(3).unwrap().clone(),
+        );
+        // e_i ⊗ e_j is the basis vector 3i + j of 2 ⊗ 3 and 2j + i of 3 ⊗ 2
+        let braiding = mat.braiding(&two, &three).unwrap();
+        assert!(!braiding.is_identity());
+        for i in 0..2 {
+            for j in 0..3 {
+                assert_eq!(braiding.entry(2 * j + i, 3 * i + j), Some(1));
+            }
+        }
+        let back = mat.braiding(&three, &two).unwrap();
+        assert!(
+            mat.compose_morphisms(&braiding, &back)
+                .unwrap()
+                .is_identity()
+        );
+        // σ ∘ (f ⊗ g) = (g ⊗ f) ∘ σ
+        mat.validate_braiding_naturality(&two, &three).unwrap();
+        mat.validate_hexagons(&two, &two, &two).unwrap();
+
+        // in FinSet the braiding of bit × bit exchanges the components of the pairs
+        let fin_set = constants();
+        fin_set.validate_symmetric_structure().unwrap();
+        let bit = fin_set
+            .get_object(&DiscreteCategory::new_with_id("bit".into()))
+            .unwrap()
+            .clone();
+        let braiding = fin_set.braiding(&bit, &bit).unwrap();
+        let pair = |first, second| ObjectId::product([ObjectId::Int(first), ObjectId::Int(second)]);
+        assert_eq!(FinSet::apply(&braiding, &pair(0, 1)).unwrap(), pair(1, 0));
+        assert!(!braiding.is_identity());
+    }
+
+    #[test]
+    fn test_wrong_braidings_are_rejected() {
+        // -σ on the scalars is natural and crossing twice is still the identity,
+        // but the hexagons fail
+        let twisted = Twisted {
+            category: scalars(),
+            twist: Twist::Braiding,
+            automorphism: negate,
+        };
+        let one = twisted.category.dimension(1).unwrap().clone();
+        twisted.validate_braiding_naturality(&one, &one).unwrap();
+        assert!(
+            failure(twisted.validate_hexagons(&one, &one, &one))
+                .starts_with("First hexagon identity fails")
+        );
+        failure(twisted.validate_braided_structure());
+        failure(twisted.validate_symmetric_structure());
+
+        // exchanging two pairs after the swap is neither natural nor its own inverse
+        let twisted = Twisted {
+            category: constants(),
+            twist: Twist::Braiding,
+            automorphism: exchange,
+        };
+        let bit = twisted
+            .get_object(&DiscreteCategory::new_with_id("bit".into()))
+            .unwrap()
+            .clone();
+        assert!(
+            failure(twisted.validate_braiding_naturality(&bit, &bit))
+                .starts_with("Braiding is not natural")
+        );
+        let twice = twisted
+            .compose_morphisms(
+                &twisted.braiding(&bit, &bit).unwrap(),
+                &twisted.braiding(&bit, &bit).unwrap(),
+            )
+            .unwrap();
+        assert!(!twice.is_identity());
+        failure(twisted.validate_symmetric_structure());
+    }
+}
@@ -2,7 +2,9 @@ use crate::core::arrow::{Arrow, Morphism};
 use crate::core::errors::Errors;
 use crate::core::identifier::Identifier;
 use crate::core::object_id::ObjectId;
+use crate::core::object_path::ObjectPath;
 use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::utils::{sorted_morphisms, sorted_objects};
 use dyn_clone::DynClone;
 use std::any::Any;
 use std::collections::HashSet;
@@ -19,7 +21,7 @@ pub enum MorphismCommutationResult<Category: CategoryTrait> {
 pub trait CategoryTrait: Debug + Any + DynClone {
     type Object: CategoryTrait + Debug + Eq + Hash + DynClone;
 
-    type Morphism: ArrowTrait<Self::Object, Self::Object> + Debug;
+    type Morphism: ArrowTrait<Self::Object, Self::Object> + Debug + Any;
 
     fn new() -> Self
     where
@@ -29,11 +31,16 @@ pub trait CategoryTrait: Debug + Any + DynClone {
     where
         Self: Sized;
 
-    fn level(&self) -> usize
+    // a point, a category without objects, is level 0, a category is one above its deepest object
+    fn level(&self) -> Result<usize, Errors>
     where
         Self: Sized,
     {
-        Self::nested_level()
+        let mut level = 0;
+        for object in self.get_all_objects()? {
+            level = level.max(object.level()? + 1);
+        }
+        Ok(level)
     }
 
     fn new_instance(&self) -> Self
@@ -123,13 +130,127 @@ pub trait CategoryTrait: Debug + Any + DynClone {
         object: &Self::Object,
     ) -> Result<Vec<&Rc<Self::Morphism>>, Errors>;
 
+    // composes `first` followed by `second`, i.e. the result is second ∘ first.
+    // Every category supplies its own composition, e.g. by a table of composites or by
+    // composing the actions of its morphisms.
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Self::Morphism>,
+        second: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors>;
+
+    fn inverse_morphism(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        if morphism.is_identity() {
+            return Ok(Some(morphism.clone()));
+        }
+        let candidates = self.get_hom_set(morphism.target_object(), morphism.source_object())?;
+        for candidate in sorted_morphisms(candidates) {
+            if self.compose_morphisms(morphism, candidate)?.is_identity()
+                && self.compose_morphisms(candidate, morphism)?.is_identity()
+            {
+                return Ok(Some(candidate.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    // m: A -> B is mono when m ∘ g = m ∘ h implies g = h for all g, h: Z -> A
+    fn is_mono(&self, morphism: &Rc<Self::Morphism>) -> Result<bool, Errors> {
+        for object in self.get_all_objects()? {
+            let hom_set = self.get_hom_set(object, morphism.source_object())?;
+            let composites = hom_set
+                .iter()
+                .map(|candidate| self.compose_morphisms(candidate, morphism))
+                .collect::<Result<Vec<_>, Errors>>()?;
+            for (index, composite) in composites.iter().enumerate() {
+                if composites[..index].contains(composite) {
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    // e: A -> B is epi when g ∘ e = h ∘ e implies g = h for all g, h: B -> Z
+    fn is_epi(&self, morphism: &Rc<Self::Morphism>) -> Result<bool, Errors> {
+        for object in self.get_all_objects()? {
+            let hom_set = self.get_hom_set(morphism.target_object(), object)?;
+            let composites = hom_set
+                .iter()
+                .map(|candidate| self.compose_morphisms(morphism, candidate))
+                .collect::<Result<Vec<_>, Errors>>()?;
+            for (index, composite) in composites.iter().enumerate() {
+                if composites[..index].contains(composite) {
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    // a retraction r: B -> A of m: A -> B, r ∘ m = id_A
+    fn is_split_mono(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        let candidates = self.get_hom_set(morphism.target_object(), morphism.source_object())?;
+        for candidate in sorted_morphisms(candidates) {
+            if self.compose_morphisms(morphism, candidate)?.is_identity() {
+                return Ok(Some(candidate.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    // a section s: B -> A of e: A -> B, e ∘ s = id_B
+    fn is_split_epi(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+    ) -> Result<Option<Rc<Self::Morphism>>, Errors> {
+        let candidates = self.get_hom_set(morphism.target_object(), morphism.source_object())?;
+        for candidate in sorted_morphisms(candidates) {
+            if self.compose_morphisms(candidate, morphism)?.is_identity() {
+                return Ok(Some(candidate.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    // whether the paths of morphisms, each composed first to last, have the same composite
     fn morphism_commute(
         &self,
-        left_morphisms: Vec<&Self::Morphism>,
-        right_morphisms: Vec<&Self::Morphism>,
-    ) -> Result<MorphismCommutationResult<Self::Object>, Errors> {
-        self.validate_morphisms_commutation(left_morphisms, right_morphisms)?;
-        Ok(MorphismCommutationResult::Commutative)
+        left_morphisms: Vec<&Rc<Self::Morphism>>,
+        right_morphisms: Vec<&Rc<Self::Morphism>>,
+    ) -> Result<MorphismCommutationResult<Self>, Errors>
+    where
+        Self: Sized,
+    {
+        self.validate_morphisms_commutation(
+            left_morphisms.iter().map(|morphism| &***morphism).collect(),
+            right_morphisms
+                .iter()
+                .map(|morphism| &***morphism)
+                .collect(),
+        )?;
+        let compose = |morphisms: &[&Rc<Self::Morphism>]| {
+            morphisms[1..]
+                .iter()
+                .try_fold(morphisms[0].clone(), |composite, next| {
+                    self.compose_morphisms(&composite, next)
+                })
+        };
+        let left = compose(&left_morphisms)?;
+        let right = compose(&right_morphisms)?;
+        if left == right {
+            Ok(MorphismCommutationResult::Commutative)
+        } else {
+            Ok(MorphismCommutationResult::NonCommutative(HashSet::from([
+                left, right,
+            ])))
+        }
     }
 
     fn validate_morphisms_commutation(
@@ -137,75 +258,268 @@ pub trait CategoryTrait: Debug + Any + DynClone {
         left_morphisms: Vec<&Self::Morphism>,
         right_morphisms: Vec<&Self::Morphism>,
     ) -> Result<(), Errors> {
-        // // source and target of left cells id should be same with right cells
-        // let left_source_object = left_morphisms
-        //     .first()
-        //     .ok_or(NCategoryError::InvalidMorphismCommutation)?
-        //     .source_object();
-        // let right_source_object = right_morphisms
-        //     .first()
-        //     .ok_or(NCategoryError::InvalidMorphismCommutation)?
-        //     .source_object();
-        //
-        // if left_source_object != right_source_object {
-        //     return Err(NCategoryError::InvalidMorphismComposition);
-        // }
-        //
-        // let left_target_object = left_morphisms
-        //     .first()
-        //     .ok_or(NCategoryError::InvalidMorphismCommutation)?
-        //     .target_object();
-        // let right_target_object = right_morphisms
-        //     .first()
-        //     .ok_or(NCategoryError::InvalidMorphismCommutation)?
-        //     .target_object();
-        //
-        // if left_target_object != right_target_object {
-        //     return Err(NCategoryError::InvalidMorphismComposition);
-        // }
-        //
-        // // confirm composition is correct
-        // self.validate_morphisms_composition(left_morphisms)?;
-        // self.validate_morphisms_composition(right_morphisms)?;
-        //
+        // both paths go from the same source to the same target
+        let (Some(left_first), Some(left_last)) = (left_morphisms.first(), left_morphisms.last())
+        else {
+            return Err(Errors::InvalidMorphismCommutation);
+        };
+        let (Some(right_first), Some(right_last)) =
+            (right_morphisms.first(), right_morphisms.last())
+        else {
+            return Err(Errors::InvalidMorphismCommutation);
+        };
+        if left_first.source_object() != right_first.source_object()
+            || left_last.target_object() != right_last.target_object()
+        {
+            return Err(Errors::InvalidMorphismCommutation);
+        }
+
+        self.validate_morphisms_composition(left_morphisms)?;
+        self.validate_morphisms_composition(right_morphisms)?;
         Ok(())
-        // todo!()
     }
 
     fn validate_morphisms_composition(&self, morphims: Vec<&Self::Morphism>) -> Result<(), Errors> {
-        // if morphims.is_empty() {
-        //     return Err(NCategoryError::InvalidMorphismComposition);
-        // }
-        //
-        // // composition of only once cell is always valid
-        // if morphims.len() <= 1 {
-        //     return Ok(());
-        // }
-        // // target of first cell needs to be the source of subsequent cell
-        // let mut target_object = morphims
-        //     .first()
-        //     .ok_or(NCategoryError::InvalidMorphismComposition)?
-        //     .target_object();
-        //
-        // for morphism in &morphims[1..] {
-        //     if morphism.source_object() != target_object {
-        //         return Err(NCategoryError::InvalidMorphismComposition);
-        //     }
-        //     target_object = morphism.target_object();
-        // }
-        // Ok(())
-        todo!()
+        if morphims.is_empty() {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        // target of each morphism needs to be the source of the next one
+        for pair in morphims.windows(2) {
+            if pair[0].target_object() != pair[1].source_object() {
+                return Err(Errors::InvalidMorphismComposition);
+            }
+        }
+        Ok(())
     }
 
     fn is_zero_category(&self) -> bool {
         false
     }
 
-    fn nested_level() -> usize
+    // the level of a category of this type whose objects nest all the way, level() never exceeds it,
+    // None when the objects can nest without bound
+    fn nested_level() -> Option<usize>
+    where
+        Self: Sized,
+    {
+        <Self::Object as CategoryTrait>::nested_level().map(|level| level + 1)
+    }
+
+    /*
+    Every sub-object and sub-morphism `level` steps below this category, with its path of ids
+    from this category: level 0 is the category itself, level 1 its objects and morphisms,
+    level 2 the objects and morphisms of its objects, and so on.
+    Morphisms are addressed by the path of their source category followed by their arrow id.
+     */
+    fn nested_elements(&self, level: usize) -> Result<NestedElements<'_>, Errors>
+    where
+        Self: Sized,
+    {
+        let path = vec![self.category_id().clone()];
+        let mut elements = Vec::new();
+        if level == 0 {
+            elements.push(NestedElement::new(path, NestedValue::Object(self)));
+        } else {
+            self.collect_nested_elements(level, &path, &mut elements)?;
+        }
+        Ok(NestedElements {
+            elements: elements.into_iter(),
+        })
+    }
+
+    fn collect_nested_elements<'a>(
+        &'a self,
+        level: usize,
+        path: &[ObjectId],
+        elements: &mut Vec<NestedElement<'a>>,
+    ) -> Result<(), Errors>
+    where
+        Self: Sized,
+    {
+        let objects = self.get_all_objects()?;
+        if objects.is_empty() {
+            return Ok(());
+        }
+        let objects = sorted_objects(objects);
+        for object in &objects {
+            let mut object_path = path.to_vec();
+            object_path.push(object.category_id().clone());
+            if level == 1 {
+                elements.push(NestedElement::new(
+                    object_path,
+                    NestedValue::Object(&***object),
+                ));
+            } else {
+                object.collect_nested_elements(level - 1, &object_path, elements)?;
+            }
+        }
+        if level == 1 {
+            for object in &objects {
+                for morphism in sorted_morphisms(self.get_hom_set_x(object)?) {
+                    let mut morphism_path = path.to_vec();
+                    morphism_path.push(ObjectId::Str(morphism.arrow_id().clone()));
+                    elements.push(NestedElement::new(
+                        morphism_path,
+                        NestedValue::Morphism(&**morphism),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // the object or morphism at the path, whose first segment is the id of this category
+    fn resolve_path(&self, path: &ObjectPath) -> Result<NestedElement<'_>, Errors>
+    where
+        Self: Sized,
+    {
+        let Some((root, rest)) = path.segments().split_first() else {
+            return Err(Errors::ObjectNotFound(self.category_id().clone()));
+        };
+        if root != self.category_id() {
+            return Err(Errors::ObjectNotFound(root.clone()));
+        }
+        if rest.is_empty() {
+            return Ok(NestedElement::new(
+                vec![root.clone()],
+                NestedValue::Object(self),
+            ));
+        }
+        self.resolve_segments(std::slice::from_ref(root), rest)
+    }
+
+    fn resolve_segments<'a>(
+        &'a self,
+        path: &[ObjectId],
+        segments: &[ObjectId],
+    ) -> Result<NestedElement<'a>, Errors>
     where
         Self: Sized,
     {
-        todo!()
-        // 1 + <Self::Object as CategoryTrait>::nested_level()
+        let Some((segment, rest)) = segments.split_first() else {
+            return Err(Errors::ObjectNotFound(self.category_id().clone()));
+        };
+        let mut segment_path = path.to_vec();
+        segment_path.push(segment.clone());
+        let object = self
+            .get_object(&Self::Object::new_with_id(segment))
+            .map_err(|_| Errors::ObjectNotFound(segment.clone()));
+        if !rest.is_empty() {
+            return object?.resolve_segments(&segment_path, rest);
+        }
+        if let Ok(object) = object {
+            return Ok(NestedElement::new(
+                segment_path,
+                NestedValue::Object(&**object),
+            ));
+        }
+        // not an object, so look for a morphism with the segment as id
+        let arrow_id = match segment {
+            ObjectId::Str(arrow_id) => arrow_id.clone(),
+            other => other.to_string(),
+        };
+        for object in self.get_all_objects()? {
+            for morphism in self.get_hom_set_x(object)? {
+                if morphism.arrow_id() == &arrow_id {
+                    return Ok(NestedElement::new(
+                        segment_path,
+                        NestedValue::Morphism(&**morphism),
+                    ));
+                }
+            }
+        }
+        Err(Errors::ObjectNotFound(segment.clone()))
+    }
+
+    // the path of an object or morphism below this category equal to the value, the value
+    // itself winning when equal ones are stored at several paths
+    fn path_of<T: Any + PartialEq>(&self, value: &T) -> Result<ObjectPath, Errors>
+    where
+        Self: Sized,
+    {
+        let mut first_equal = None;
+        for level in 0..=self.level()? {
+            for element in self.nested_elements(level)? {
+                let Some(candidate) = element
+                    .as_object::<T>()
+                    .or_else(|| element.as_morphism::<T>())
+                else {
+                    continue;
+                };
+                if candidate != value {
+                    continue;
+                }
+                if std::ptr::eq(candidate, value) {
+                    return Ok(element.object_path());
+                }
+                first_equal.get_or_insert_with(|| element.object_path());
+            }
+        }
+        first_equal.ok_or_else(|| Errors::ObjectNotFound(self.category_id().clone()))
+    }
+}
+
+pub enum NestedValue<'a> {
+    Object(&'a dyn Any),
+    Morphism(&'a dyn Any),
+}
+
+// an element of a nested category together with its path from the root
+pub struct NestedElement<'a> {
+    path: Vec<ObjectId>,
+    value: NestedValue<'a>,
+}
+
+impl<'a> NestedElement<'a> {
+    pub fn new(path: Vec<ObjectId>, value: NestedValue<'a>) -> Self {
+        NestedElement { path, value }
+    }
+
+    pub fn path(&self) -> &[ObjectId] {
+        &self.path
+    }
+
+    pub fn value(&self) -> &NestedValue<'a> {
+        &self.value
+    }
+
+    pub fn object_path(&self) -> ObjectPath {
+        ObjectPath::new(self.path.clone())
+    }
+
+    pub fn is_object(&self) -> bool {
+        matches!(self.value, NestedValue::Object(_))
+    }
+
+    pub fn is_morphism(&self) -> bool {
+        matches!(self.value, NestedValue::Morphism(_))
+    }
+
+    // the object, when it is one of type T
+    pub fn as_object<T: Any>(&self) -> Option<&'a T> {
+        match self.value {
+            NestedValue::Object(object) => object.downcast_ref(),
+            NestedValue::Morphism(_) => None,
+        }
+    }
+
+    // the morphism, when it is one of type T
+    pub fn as_morphism<T: Any>(&self) -> Option<&'a T> {
+        match self.value {
+            NestedValue::Morphism(morphism) => morphism.downcast_ref(),
+            NestedValue::Object(_) => None,
+        }
+    }
+}
+
+pub struct NestedElements<'a> {
+    elements: std::vec::IntoIter<NestedElement<'a>>,
+}
+
+impl<'a> Iterator for NestedElements<'a> {
+    type Item = NestedElement<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.elements.next()
     }
 }
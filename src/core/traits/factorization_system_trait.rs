@@ -1,4 +1,7 @@
 use crate::core::errors::Errors;
+use crate::core::factorization_system::{
+    FactorizationSystemResult, verify_orthogonal_factorization_system,
+};
 use crate::core::traits::category_trait::CategoryTrait;
 use std::rc::Rc;
 
@@ -11,4 +14,18 @@ pub trait FactorizationSystemTrait: CategoryTrait {
         &self,
         morphism: &Self::Morphism,
     ) -> Result<&(Rc<Self::Morphism>, Rc<Self::Morphism>), Errors>;
+
+    // checks the factors of morphism_factors against the declared classes E and M
+    fn verify_orthogonal_factorization(
+        &self,
+        in_left: impl Fn(&Rc<Self::Morphism>) -> bool,
+        in_right: impl Fn(&Rc<Self::Morphism>) -> bool,
+    ) -> Result<FactorizationSystemResult<Self>, Errors>
+    where
+        Self: Sized,
+    {
+        verify_orthogonal_factorization_system(self, in_left, in_right, |morphism| {
+            self.morphism_factors(morphism).ok().cloned()
+        })
+    }
 }
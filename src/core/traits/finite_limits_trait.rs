@@ -0,0 +1,276 @@
+use crate::core::errors::Errors;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::utils::{cartesian_product, sorted_morphisms, sorted_objects};
+use std::rc::Rc;
+
+/*
+A finite diagram is a finite graph of objects and morphisms in a category.
+Limits and colimits only depend on a generating set of arrows of the shape,
+so the edges do not need to be closed under composition nor include identities.
+ */
+#[derive(Debug)]
+pub struct FiniteDiagram<Category: CategoryTrait> {
+    objects: Vec<Rc<Category::Object>>,
+    edges: Vec<(usize, usize, Rc<Category::Morphism>)>,
+}
+
+impl<Category: CategoryTrait> Default for FiniteDiagram<Category> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Category: CategoryTrait> FiniteDiagram<Category> {
+    pub fn new() -> Self {
+        FiniteDiagram {
+            objects: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn add_object(&mut self, object: Rc<Category::Object>) -> usize {
+        self.objects.push(object);
+        self.objects.len() - 1
+    }
+
+    pub fn add_edge(
+        &mut self,
+        source: usize,
+        target: usize,
+        morphism: Rc<Category::Morphism>,
+    ) -> Result<(), Errors> {
+        let source_object = self.objects.get(source).ok_or(Errors::InvalidDiagram)?;
+        let target_object = self.objects.get(target).ok_or(Errors::InvalidDiagram)?;
+        if morphism.source_object() != source_object || morphism.target_object() != target_object {
+            return Err(Errors::InvalidDiagram);
+        }
+        self.edges.push((source, target, morphism));
+        Ok(())
+    }
+
+    pub fn objects(&self) -> &Vec<Rc<Category::Object>> {
+        &self.objects
+    }
+
+    pub fn edges(&self) -> &Vec<(usize, usize, Rc<Category::Morphism>)> {
+        &self.edges
+    }
+}
+
+// legs go from the apex to each object of the diagram, in the order of the diagram objects
+#[derive(Debug)]
+pub struct Cone<Category: CategoryTrait> {
+    apex: Rc<Category::Object>,
+    legs: Vec<Rc<Category::Morphism>>,
+}
+
+impl<Category: CategoryTrait> Cone<Category> {
+    pub fn new(apex: Rc<Category::Object>, legs: Vec<Rc<Category::Morphism>>) -> Self {
+        Cone { apex, legs }
+    }
+
+    pub fn apex(&self) -> &Rc<Category::Object> {
+        &self.apex
+    }
+
+    pub fn legs(&self) -> &Vec<Rc<Category::Morphism>> {
+        &self.legs
+    }
+}
+
+// legs go from each object of the diagram to the apex, in the order of the diagram objects
+#[derive(Debug)]
+pub struct Cocone<Category: CategoryTrait> {
+    apex: Rc<Category::Object>,
+    legs: Vec<Rc<Category::Morphism>>,
+}
+
+impl<Category: CategoryTrait> Cocone<Category> {
+    pub fn new(apex: Rc<Category::Object>, legs: Vec<Rc<Category::Morphism>>) -> Self {
+        Cocone { apex, legs }
+    }
+
+    pub fn apex(&self) -> &Rc<Category::Object> {
+        &self.apex
+    }
+
+    pub fn legs(&self) -> &Vec<Rc<Category::Morphism>> {
+        &self.legs
+    }
+}
+
+/*
+The default implementations search the (finite) category for a universal cone,
+so any finite category gets limits for free whenever they exist.
+Concrete categories which can construct their limits should override them.
+ */
+pub trait FiniteLimitsTrait: CategoryTrait + Sized {
+    fn limit(&self, diagram: &FiniteDiagram<Self>) -> Result<Cone<Self>, Errors> {
+        let mut cones = Vec::new();
+        for object in sorted_objects(self.get_all_objects()?) {
+            cones.extend(self.cones_over(object, diagram)?);
+        }
+        for candidate in &cones {
+            let mut is_universal = true;
+            for cone in &cones {
+                if self.cone_factorizations(candidate, cone)?.len() != 1 {
+                    is_universal = false;
+                    break;
+                }
+            }
+            if is_universal {
+                return Ok(Cone::new(candidate.apex.clone(), candidate.legs.clone()));
+            }
+        }
+        Err(Errors::LimitNotFound)
+    }
+
+    // the unique morphism u: cone.apex -> limit.apex such that limit.legs[i] ∘ u = cone.legs[i]
+    fn limit_mediating_morphism(
+        &self,
+        _diagram: &FiniteDiagram<Self>,
+        limit: &Cone<Self>,
+        cone: &Cone<Self>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        let mut factorizations = self.cone_factorizations(limit, cone)?;
+        match factorizations.len() {
+            1 => Ok(factorizations.remove(0)),
+            _ => Err(Errors::LimitNotFound),
+        }
+    }
+
+    fn cones_over(
+        &self,
+        apex: &Rc<Self::Object>,
+        diagram: &FiniteDiagram<Self>,
+    ) -> Result<Vec<Cone<Self>>, Errors> {
+        let mut choices = Vec::new();
+        for object in diagram.objects() {
+            let hom_set = self.get_hom_set(apex, object)?;
+            choices.push(
+                sorted_morphisms(hom_set)
+                    .into_iter()
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            );
+        }
+        let mut cones = Vec::new();
+        for legs in cartesian_product(&choices) {
+            let mut commutes = true;
+            for (source, target, morphism) in diagram.edges() {
+                if self.compose_morphisms(&legs[*source], morphism)? != legs[*target] {
+                    commutes = false;
+                    break;
+                }
+            }
+            if commutes {
+                cones.push(Cone::new(apex.clone(), legs));
+            }
+        }
+        Ok(cones)
+    }
+
+    fn cone_factorizations(
+        &self,
+        limit: &Cone<Self>,
+        cone: &Cone<Self>,
+    ) -> Result<Vec<Rc<Self::Morphism>>, Errors> {
+        let mut result = Vec::new();
+        'candidates: for candidate in sorted_morphisms(self.get_hom_set(&cone.apex, &limit.apex)?) {
+            for (limit_leg, cone_leg) in limit.legs.iter().zip(cone.legs.iter()) {
+                if self.compose_morphisms(candidate, limit_leg)? != *cone_leg {
+                    continue 'candidates;
+                }
+            }
+            result.push(candidate.clone());
+        }
+        Ok(result)
+    }
+}
+
+pub trait FiniteColimitsTrait: CategoryTrait + Sized {
+    fn colimit(&self, diagram: &FiniteDiagram<Self>) -> Result<Cocone<Self>, Errors> {
+        let mut cocones = Vec::new();
+        for object in sorted_objects(self.get_all_objects()?) {
+            cocones.extend(self.cocones_under(object, diagram)?);
+        }
+        for candidate in &cocones {
+            let mut is_universal = true;
+            for cocone in &cocones {
+                if self.cocone_factorizations(candidate, cocone)?.len() != 1 {
+                    is_universal = false;
+                    break;
+                }
+            }
+            if is_universal {
+                return Ok(Cocone::new(candidate.apex.clone(), candidate.legs.clone()));
+            }
+        }
+        Err(Errors::ColimitNotFound)
+    }
+
+    // the unique morphism u: colimit.apex -> cocone.apex such that u ∘ colimit.legs[i] = cocone.legs[i]
+    fn colimit_mediating_morphism(
+        &self,
+        _diagram: &FiniteDiagram<Self>,
+        colimit: &Cocone<Self>,
+        cocone: &Cocone<Self>,
+    ) -> Result<Rc<Self::Morphism>, Errors> {
+        let mut factorizations = self.cocone_factorizations(colimit, cocone)?;
+        match factorizations.len() {
+            1 => Ok(factorizations.remove(0)),
+            _ => Err(Errors::ColimitNotFound),
+        }
+    }
+
+    fn cocones_under(
+        &self,
+        apex: &Rc<Self::Object>,
+        diagram: &FiniteDiagram<Self>,
+    ) -> Result<Vec<Cocone<Self>>, Errors> {
+        let mut choices = Vec::new();
+        for object in diagram.objects() {
+            let hom_set = self.get_hom_set(object, apex)?;
+            choices.push(
+                sorted_morphisms(hom_set)
+                    .into_iter()
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            );
+        }
+        let mut cocones = Vec::new();
+        for legs in cartesian_product(&choices) {
+            let mut commutes = true;
+            for (source, target, morphism) in diagram.edges() {
+                if self.compose_morphisms(morphism, &legs[*target])? != legs[*source] {
+                    commutes = false;
+                    break;
+                }
+            }
+            if commutes {
+                cocones.push(Cocone::new(apex.clone(), legs));
+            }
+        }
+        Ok(cocones)
+    }
+
+    fn cocone_factorizations(
+        &self,
+        colimit: &Cocone<Self>,
+        cocone: &Cocone<Self>,
+    ) -> Result<Vec<Rc<Self::Morphism>>, Errors> {
+        let mut result = Vec::new();
+        'candidates: for candidate in
+            sorted_morphisms(self.get_hom_set(&colimit.apex, &cocone.apex)?)
+        {
+            for (colimit_leg, cocone_leg) in colimit.legs.iter().zip(cocone.legs.iter()) {
+                if self.compose_morphisms(colimit_leg, candidate)? != *cocone_leg {
+                    continue 'candidates;
+                }
+            }
+            result.push(candidate.clone());
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,321 @@
+use crate::core::errors::Errors;
+use crate::core::traits::arrow_trait::ArrowTrait;
+use crate::core::traits::category_trait::CategoryTrait;
+use crate::core::utils::sorted_objects;
+use std::rc::Rc;
+
+/*
+A monoidal category is a category with a tensor bifunctor ⊗, a unit object I
+and natural isomorphisms
+
+    associator     α_{A,B,C}: (A ⊗ B) ⊗ C -> A ⊗ (B ⊗ C)
+    left unitor    λ_A: I ⊗ A -> A
+    right unitor   ρ_A: A ⊗ I -> A
+
+satisfying the pentagon and triangle identities.
+Tensoring by a fixed object, (-) ⊗ X, is the endofunctor product_endofunctor builds for products.
+ */
+pub trait MonoidalCategoryTrait: CategoryTrait + Sized {
+    fn tensor_objects(
+        &self,
+        left: &Rc<Self::Object>,
+        right: &Rc<Self::Object>,
+    ) -> Result<Rc<Self::Object>, Errors>;
+
+    // f ⊗ g: A ⊗ B -> A' ⊗ B' for f: A -> A' and g: B -> B'
+    fn tensor_morphisms(
+        &self,
+        left: &Rc<Self::Morphism>,
+        right: &Rc<Self::Morphism>,
+    ) -> Result<Rc<Self::Morphism>, Errors>;
+
+    fn unit_object(&self) -> Result<Rc<Self::Object>, Errors>;
+
+    fn associator(
+        &self,
+        first: &Rc<Self::Object>,
+        second: &Rc<Self::Object>,
+        third: &Rc<Self::Object>,
+    ) -> Result<Rc<Self::Morphism>, Errors>;
+
+    fn left_unitor(&self, object: &Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors>;
+
+    fn right_unitor(&self, object: &Rc<Self::Object>) -> Result<Rc<Self::Morphism>, Errors>;
+
+    // checks the structure on every object of the (finite) category:
+    // ⊗ preserves identities, α, λ, ρ are natural isomorphisms and the triangle and pentagon commute
+    fn validate_monoidal_structure(&self) -> Result<(), Errors> {
+        let objects = sorted_objects(self.get_all_objects()?);
+        let unit = self.unit_object()?;
+
+        for first in &objects {
+            self.validate_isomorphism(&self.left_unitor(first)?, "left unitor")?;
+            self.validate_isomorphism(&self.right_unitor(first)?, "right unitor")?;
+            self.validate_unitor_naturality(first, &unit)?;
+            for second in &objects {
+                let tensor = self.tensor_objects(first, second)?;
+                let tensor_identity = self.tensor_morphisms(
+                    self.get_identity_morphism(first)?,
+                    self.get_identity_morphism(second)?,
+                )?;
+                if !tensor_identity.is_identity() || tensor_identity.source_object() != &tensor {
+                    return Err(Errors::InvalidMonoidalStructure(format!(
+                        "Tensor of identities on {} and {} is not an identity",
+                        first.category_id(),
+                        second.category_id()
+                    )));
+                }
+                self.validate_triangle(first, &unit, second)?;
+                for third in &objects {
+                    self.validate_isomorphism(
+                        &self.associator(first, second, third)?,
+                        "associator",
+                    )?;
+                    self.validate_associator_naturality(first, second, third)?;
+                    for fourth in &objects {
+                        self.validate_pentagon(first, second, third, fourth)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // λ_{A'} ∘ (id_I ⊗ f) = f ∘ λ_A and ρ_{A'} ∘ (f ⊗ id_I) = f ∘ ρ_A for all f: A -> A'
+    fn validate_unitor_naturality(
+        &self,
+        object: &Rc<Self::Object>,
+        unit: &Rc<Self::Object>,
+    ) -> Result<(), Errors> {
+        let unit_identity = self.get_identity_morphism(unit)?;
+        for morphism in self.get_hom_set_x(object)? {
+            let target = morphism.target_object();
+            let left_path = self.compose_morphisms(
+                &self.tensor_morphisms(unit_identity, morphism)?,
+                &self.left_unitor(target)?,
+            )?;
+            let right_path = self.compose_morphisms(&self.left_unitor(object)?, morphism)?;
+            if left_path != right_path {
+                return Err(Errors::InvalidMonoidalStructure(format!(
+                    "Left unitor is not natural in {}",
+                    object.category_id()
+                )));
+            }
+            let left_path = self.compose_morphisms(
+                &self.tensor_morphisms(morphism, unit_identity)?,
+                &self.right_unitor(target)?,
+            )?;
+            let right_path = self.compose_morphisms(&self.right_unitor(object)?, morphism)?;
+            if left_path != right_path {
+                return Err(Errors::InvalidMonoidalStructure(format!(
+                    "Right unitor is not natural in {}",
+                    object.category_id()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    // α_{A',B',C'} ∘ ((f ⊗ g) ⊗ h) = (f ⊗ (g ⊗ h)) ∘ α_{A,B,C} for all f: A -> A', g: B -> B', h: C -> C'
+    fn validate_associator_naturality(
+        &self,
+        first: &Rc<Self::Object>,
+        second: &Rc<Self::Object>,
+        third: &Rc<Self::Object>,
+    ) -> Result<(), Errors> {
+        let associator = self.associator(first, second, third)?;
+        for left in self.get_hom_set_x(first)? {
+            for middle in self.get_hom_set_x(second)? {
+                let left_middle = self.tensor_morphisms(left, middle)?;
+                for right in self.get_hom_set_x(third)? {
+                    let left_path = self.compose_morphisms(
+                        &self.tensor_morphisms(&left_middle, right)?,
+                        &self.associator(
+                            left.target_object(),
+                            middle.target_object(),
+                            right.target_object(),
+                        )?,
+                    )?;
+                    let right_path = self.compose_morphisms(
+                        &associator,
+                        &self.tensor_morphisms(left, &self.tensor_morphisms(middle, right)?)?,
+                    )?;
+                    if left_path != right_path {
+                        return Err(Errors::InvalidMonoidalStructure(format!(
+                            "Associator is not natural in {}, {} and {}",
+                            first.category_id(),
+                            second.category_id(),
+                            third.category_id()
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // (id_A ⊗ λ_B) ∘ α_{A,I,B} = ρ_A ⊗ id_B
+    fn validate_triangle(
+        &self,
+        first: &Rc<Self::Object>,
+        unit: &Rc<Self::Object>,
+        second: &Rc<Self::Object>,
+    ) -> Result<(), Errors> {
+        let left_path = self.compose_morphisms(
+            &self.associator(first, unit, second)?,
+            &self.tensor_morphisms(
+                self.get_identity_morphism(first)?,
+                &self.left_unitor(second)?,
+            )?,
+        )?;
+        let right_path = self.tensor_morphisms(
+            &self.right_unitor(first)?,
+            self.get_identity_morphism(second)?,
+        )?;
+        if left_path != right_path {
+            return Err(Errors::InvalidMonoidalStructure(format!(
+                "Triangle identity fails for {} and {}",
+                first.category_id(),
+                second.category_id()
+            )));
+        }
+        Ok(())
+    }
+
+    // α_{A,B,C⊗D} ∘ α_{A⊗B,C,D} = (id_A ⊗ α_{B,C,D}) ∘ α_{A,B⊗C,D} ∘ (α_{A,B,C} ⊗ id_D)
+    fn validate_pentagon(
+        &self,
+        first: &Rc<Self::Object>,
+        second: &Rc<Self::Object>,
+        third: &Rc<Self::Object>,
+        fourth: &Rc<Self::Object>,
+    ) -> Result<(), Errors> {
+        let first_second = self.tensor_objects(first, second)?;
+        let third_fourth = self.tensor_objects(third, fourth)?;
+        let second_third = self.tensor_objects(second, third)?;
+
+        let left_path = self.compose_morphisms(
+            &self.associator(&first_second, third, fourth)?,
+            &self.associator(first, second, &third_fourth)?,
+        )?;
+
+        let right_path = self.compose_morphisms(
+            &self.tensor_morphisms(
+                &self.associator(first, second, third)?,
+                self.get_identity_morphism(fourth)?,
+            )?,
+            &self.associator(first, &second_third, fourth)?,
+        )?;
+        let right_path = self.compose_morphisms(
+            &right_path,
+            &self.tensor_morphisms(
+                self.get_identity_morphism(first)?,
+                &self.associator(second, third, fourth)?,
+            )?,
+        )?;
+
+        if left_path != right_path {
+            return Err(Errors::InvalidMonoidalStructure(format!(
+                "Pentagon identity fails for {}, {}, {} and {}",
+                first.category_id(),
+                second.category_id(),
+                third.category_id(),
+                fourth.category_id()
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_isomorphism(
+        &self,
+        morphism: &Rc<Self::Morphism>,
+        name: &str,
+    ) -> Result<(), Errors> {
+        match self.inverse_morphism(morphism)? {
+            Some(_) => Ok(()),
+            None => Err(Errors::InvalidMonoidalStructure(format!(
+                "The {} from {} is not an isomorphism",
+                name,
+                morphism.source_object().category_id()
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::discrete_category::DiscreteCategory;
+    use crate::core::tests::monoidal_test_helper::{
+        Twist, Twisted, constants, exchange, failure, negate, scalars,
+    };
+
+    #[test]
+    fn test_validate_non_thin_categories() {
+        // 1 has the parallel endomorphisms 1, 2 and -1
+        let mat = scalars();
+        let one = mat.dimension(1).unwrap().clone();
+        assert_eq!(mat.get_hom_set(&one, &one).unwrap().len(), 3);
+        mat.validate_monoidal_structure().unwrap();
+
+        let fin_set = constants();
+        fin_set.validate_monoidal_structure().unwrap();
+    }
+
+    #[test]
+    fn test_wrong_associator_and_unitor_are_rejected() {
+        let twisted = Twisted {
+            category: scalars(),
+            twist: Twist::Associator,
+            automorphism: negate,
+        };
+        let one = twisted.category.dimension(1).unwrap().clone();
+        // -α is still a natural isomorphism
+        twisted
+            .validate_associator_naturality(&one, &one, &one)
+            .unwrap();
+        assert!(failure(twisted.validate_pentagon(&one, &one, &one, &one)).starts_with("Pentagon"));
+        failure(twisted.validate_monoidal_structure());
+
+        let twisted = Twisted {
+            twist: Twist::LeftUnitor,
+            ..twisted
+        };
+        twisted.validate_unitor_naturality(&one, &one).unwrap();
+        assert!(failure(twisted.validate_triangle(&one, &one, &one)).starts_with("Triangle"));
+        failure(twisted.validate_monoidal_structure());
+    }
+
+    #[test]
+    fn test_non_natural_structure_maps_are_rejected() {
+        let twisted = Twisted {
+            category: constants(),
+            twist: Twist::Associator,
+            automorphism: exchange,
+        };
+        let bit = twisted
+            .get_object(&DiscreteCategory::new_with_id("bit".into()))
+            .unwrap()
+            .clone();
+        // exchanging two elements of bit × (bit × bit) is an isomorphism, but not natural
+        twisted
+            .validate_isomorphism(&twisted.associator(&bit, &bit, &bit).unwrap(), "associator")
+            .unwrap();
+        assert!(
+            failure(twisted.validate_associator_naturality(&bit, &bit, &bit))
+                .starts_with("Associator is not natural")
+        );
+        failure(twisted.validate_monoidal_structure());
+
+        let twisted = Twisted {
+            twist: Twist::LeftUnitor,
+            ..twisted
+        };
+        let unit = twisted.unit_object().unwrap();
+        assert!(
+            failure(twisted.validate_unitor_naturality(&bit, &unit))
+                .starts_with("Left unitor is not natural")
+        );
+        failure(twisted.validate_monoidal_structure());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::core::arrow::Morphism;
 use crate::core::errors::Errors;
 use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
 use crate::core::traits::category_trait::CategoryTrait;
 use crate::core::unit::unit_identifier::UnitIdentifier;
 use crate::core::unit::unit_morphism::UnitMorphism;
@@ -54,8 +55,9 @@ impl CategoryTrait for UnitCategory {
         todo!()
     }
 
+    // a point, without objects
     fn get_all_objects(&self) -> Result<HashSet<&Rc<Self::Object>>, Errors> {
-        todo!()
+        Ok(HashSet::new())
     }
 
     fn get_all_morphisms(&self) -> Result<HashSet<&Rc<Morphism<Self::Object>>>, Errors> {
@@ -76,7 +78,22 @@ impl CategoryTrait for UnitCategory {
         todo!()
     }
 
-    fn nested_level() -> usize {
-        0
+    fn compose_morphisms(
+        &self,
+        first: &Rc<Morphism<Self::Object>>,
+        second: &Rc<Morphism<Self::Object>>,
+    ) -> Result<Rc<Morphism<Self::Object>>, Errors> {
+        if first.target_object() != second.source_object() {
+            return Err(Errors::InvalidMorphismComposition);
+        }
+        Ok(first.clone())
+    }
+
+    fn level(&self) -> Result<usize, Errors> {
+        Ok(0)
+    }
+
+    fn nested_level() -> Option<usize> {
+        Some(0)
     }
 }
@@ -1,5 +1,7 @@
 use crate::core::object_id::ObjectId;
+use crate::core::traits::arrow_trait::ArrowTrait;
 use crate::core::traits::category_trait::CategoryTrait;
+use std::collections::HashSet;
 use std::hash::Hash;
 use std::rc::Rc;
 
@@ -20,3 +22,41 @@ pub fn find_object_index<Category: CategoryTrait + Eq + Hash>(
         .map(|idx| idx as isize)
         .unwrap_or(-1)
 }
+
+// all ways of picking one element from each of the choices
+pub fn cartesian_product<T: Clone>(choices: &[Vec<T>]) -> Vec<Vec<T>> {
+    let mut result = vec![Vec::new()];
+    for options in choices {
+        let mut next = Vec::new();
+        for partial in &result {
+            for option in options {
+                let mut extended = partial.clone();
+                extended.push(option.clone());
+                next.push(extended);
+            }
+        }
+        result = next;
+    }
+    result
+}
+
+// objects ordered by id, so searches over a category are deterministic
+pub fn sorted_objects<Object: CategoryTrait>(objects: HashSet<&Rc<Object>>) -> Vec<&Rc<Object>> {
+    let mut objects = objects.into_iter().collect::<Vec<_>>();
+    objects.sort_by_key(|object| object.category_id().to_string());
+    objects
+}
+
+// morphisms ordered by id, so searches over a category are deterministic
+pub fn sorted_morphisms<SourceObject, TargetObject, Morphism>(
+    morphisms: HashSet<&Rc<Morphism>>,
+) -> Vec<&Rc<Morphism>>
+where
+    SourceObject: CategoryTrait,
+    TargetObject: CategoryTrait,
+    Morphism: ArrowTrait<SourceObject, TargetObject>,
+{
+    let mut morphisms = morphisms.into_iter().collect::<Vec<_>>();
+    morphisms.sort_by_key(|morphism| morphism.arrow_id().clone());
+    morphisms
+}
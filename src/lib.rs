@@ -1,4 +1,5 @@
 pub mod core {
+    pub mod diagram;
     pub mod discrete_category;
 
     pub mod identifier;
@@ -6,13 +7,26 @@ pub mod core {
     pub mod errors;
 
     pub mod product_endofunctor;
+    pub mod shapes;
 
     pub mod expand_functor;
+    pub mod factorization_system;
+    pub mod groupoid;
+    pub mod indiscrete_category;
+
+    pub mod kan_extension;
+    pub mod monoid_category;
+    pub mod natural_transformation;
 
     pub mod base_category;
+    pub mod cartesian_monoidal_category;
     pub mod dynamic_category;
     pub mod epic_monic_category;
     pub mod object_id;
+    pub mod object_path;
+    pub mod preorder_category;
+    pub mod strict_n_category;
+    pub mod string_diagram;
 
     pub mod utils;
 
@@ -20,12 +34,20 @@ pub mod core {
 
     pub mod traits {
         pub mod arrow_trait;
+        pub mod braided_monoidal_category_trait;
         pub mod category_trait;
 
         pub mod factorization_system_trait;
+        pub mod finite_limits_trait;
+        pub mod monoidal_category_trait;
     }
 
-    pub mod concrete_category {}
+    pub mod concrete_category {
+        pub mod fin_set;
+        pub mod graph;
+        pub mod mat;
+        pub mod rel;
+    }
 
     pub mod unit {
         pub mod unit_category;
@@ -36,6 +58,7 @@ pub mod core {
 
     #[cfg(test)]
     mod tests {
+        pub mod monoidal_test_helper;
         pub mod ncategory_test_helper;
         pub mod test_generic_ncategory;
 